@@ -0,0 +1,217 @@
+use crate::commands::timing::Percentiles;
+use crate::filter::eval::{Field, FilterExpr, Value};
+use crate::har;
+use crate::output::{separator, OutputFormat};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use std::io::Read;
+use std::ops::ControlFlow;
+
+#[derive(Debug, Args)]
+pub struct AggCmd {
+    /// Numeric field to aggregate (e.g., time, response.bodySize, timings.wait)
+    #[arg()]
+    pub field: String,
+
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Only aggregate entries matching this filter expression
+    #[arg(long, value_name = "EXPR")]
+    pub filter: Option<String>,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+}
+
+/// Summary statistics of one field; min/max/mean/percentiles are null without values
+#[derive(Debug, serde::Serialize)]
+pub struct Aggregate {
+    pub field: String,
+    pub count: usize,
+    /// Matching entries where the field was missing or not a number
+    pub skipped: usize,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub p50: Option<f64>,
+    pub p95: Option<f64>,
+}
+
+impl Aggregate {
+    fn from_values(field: &str, values: &[f64], skipped: usize) -> Self {
+        let sum: f64 = values.iter().sum();
+        let percentiles = Percentiles::from_times(values);
+
+        Self {
+            field: field.to_string(),
+            count: values.len(),
+            skipped,
+            sum,
+            min: values.iter().copied().reduce(f64::min),
+            max: values.iter().copied().reduce(f64::max),
+            mean: (!values.is_empty()).then(|| sum / values.len() as f64),
+            p50: percentiles.as_ref().map(|p| p.p50),
+            p95: percentiles.as_ref().map(|p| p.p95),
+        }
+    }
+}
+
+impl AggCmd {
+    pub fn run<R: Read>(&self, reader: R, color: bool) -> Result<()> {
+        let agg = self.aggregate(reader)?;
+
+        match self.output {
+            OutputFormat::Json => print_json(&agg, true)?,
+            OutputFormat::Ndjson => print_ndjson([&agg])?,
+            OutputFormat::Yaml => print_yaml(&agg)?,
+            OutputFormat::Compact => {
+                let fmt = |v: Option<f64>| v.map(format_number).unwrap_or_else(|| "-".to_string());
+                println!("{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    agg.field,
+                    agg.count,
+                    format_number(agg.sum),
+                    fmt(agg.min),
+                    fmt(agg.max),
+                    fmt(agg.mean),
+                    fmt(agg.p50),
+                    fmt(agg.p95)
+                );
+            }
+            OutputFormat::Table => print_text(&agg, color),
+        }
+
+        Ok(())
+    }
+
+    fn aggregate<R: Read>(&self, reader: R) -> Result<Aggregate> {
+        let field = Field::parse(&self.field)?;
+        let filter = self.filter.as_deref().map(FilterExpr::parse).transpose()?;
+
+        let mut values = Vec::new();
+        let mut skipped = 0usize;
+        har::stream_entries(reader, |entry| {
+            if filter.as_ref().is_none_or(|f| f.matches(&entry)) {
+                match field.get_value(&entry).and_then(numeric) {
+                    Some(n) => values.push(n),
+                    None => skipped += 1,
+                }
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(Aggregate::from_values(&self.field, &values, skipped))
+    }
+}
+
+/// Numbers, and strings holding a number (e.g. a Content-Length header)
+fn numeric(value: Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => Some(n),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        Value::Bool(_) | Value::Time(_) => None,
+    }
+    .filter(|n| n.is_finite())
+}
+
+/// Whole numbers without a fraction, others to two decimals
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{:.2}", n)
+    }
+}
+
+fn print_text(agg: &Aggregate, color: bool) {
+    let label = |s: &str| {
+        if color {
+            s.bold().to_string()
+        } else {
+            s.to_string()
+        }
+    };
+
+    println!("{}", label(&agg.field));
+    println!("{}", separator(40));
+    println!("{}: {}", label("Count"), agg.count);
+    if agg.skipped > 0 {
+        println!("{}: {}", label("Skipped"), agg.skipped);
+    }
+    if agg.count == 0 {
+        println!("No numeric values.");
+        return;
+    }
+
+    let fmt = |v: Option<f64>| v.map(format_number).unwrap_or_default();
+    println!("{}: {}", label("Sum"), format_number(agg.sum));
+    println!("{}: {}", label("Min"), fmt(agg.min));
+    println!("{}: {}", label("Max"), fmt(agg.max));
+    println!("{}: {}", label("Mean"), fmt(agg.mean));
+    println!("{}: {}", label("p50"), fmt(agg.p50));
+    println!("{}: {}", label("p95"), fmt(agg.p95));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn cmd(field: &str, filter: Option<&str>) -> AggCmd {
+        AggCmd {
+            field: field.to_string(),
+            file: "-".to_string(),
+            filter: filter.map(String::from),
+            output: OutputFormat::Table,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_time() {
+        let mut jsons = Vec::new();
+        for (status, time) in [(200, 100.0), (200, 200.0), (500, 300.0), (200, 400.0)] {
+            let json = fixtures::entry_with("GET", "https://example.com/", status, |e| {
+                e["time"] = serde_json::json!(time);
+            });
+            jsons.push(json);
+        }
+        let har = fixtures::har_bytes(jsons);
+
+        let agg = cmd("time", None).aggregate(har.as_slice()).unwrap();
+        assert_eq!(agg.count, 4);
+        assert_eq!(agg.sum, 1000.0);
+        assert_eq!(agg.min, Some(100.0));
+        assert_eq!(agg.max, Some(400.0));
+        assert_eq!(agg.mean, Some(250.0));
+        assert_eq!(agg.p50, Some(250.0));
+
+        let agg = cmd("time", Some("status == 200")).aggregate(har.as_slice()).unwrap();
+        assert_eq!(agg.count, 3);
+        assert_eq!(agg.max, Some(400.0));
+    }
+
+    #[test]
+    fn test_missing_and_non_numeric_values_skipped() {
+        let with_dns = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["timings"]["dns"] = serde_json::json!(12.0);
+        });
+        let har = fixtures::har_bytes(vec![
+            with_dns,
+            fixtures::entry_json("GET", "https://example.com/", 200),
+        ]);
+
+        let agg = cmd("timings.dns", None).aggregate(har.as_slice()).unwrap();
+        assert_eq!((agg.count, agg.skipped), (1, 1));
+        assert_eq!(agg.sum, 12.0);
+
+        let agg = cmd("method", None).aggregate(har.as_slice()).unwrap();
+        assert_eq!((agg.count, agg.skipped), (0, 2));
+        assert_eq!(agg.mean, None);
+    }
+}
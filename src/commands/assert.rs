@@ -0,0 +1,187 @@
+use crate::filter::eval::{parse_duration, FilterExpr};
+use crate::har::{self, Entry};
+use crate::output::format_time;
+use crate::output::table::{colorize_status, status_label};
+use anyhow::{Result, bail};
+use clap::{ArgGroup, Args};
+use colored::Colorize;
+use std::io::Read;
+use std::ops::ControlFlow;
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("checks").required(true).multiple(true)))]
+pub struct AssertCmd {
+    /// HAR file to check (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Fail if any entry's total time is over this budget (ms, or with ms/s/m suffix)
+    #[arg(long, value_name = "TIME", value_parser = parse_budget, group = "checks")]
+    pub fail_over: Option<f64>,
+
+    /// Fail if any entry matches this filter expression (e.g. 'status >= 500')
+    #[arg(long, value_name = "EXPR", group = "checks")]
+    pub fail_status: Option<String>,
+}
+
+fn parse_budget(s: &str) -> std::result::Result<f64, String> {
+    s.trim()
+        .parse::<f64>()
+        .ok()
+        .or_else(|| parse_duration(s))
+        .filter(|ms| *ms >= 0.0)
+        .ok_or_else(|| format!("expected a time like 500, 500ms or 1.5s, got '{}'", s))
+}
+
+/// An entry that broke one of the checks
+#[derive(Debug)]
+pub struct Failure {
+    pub index: usize,
+    pub method: String,
+    pub url: String,
+    pub status: i32,
+    pub time_ms: f64,
+    /// Why it failed, e.g. "time 2.50s > 2.00s"
+    pub reasons: Vec<String>,
+}
+
+impl AssertCmd {
+    pub fn run<R: Read>(&self, reader: R, color: bool) -> Result<()> {
+        let (checked, failures) = self.check(reader)?;
+
+        for f in &failures {
+            let status = if color { colorize_status(f.status) } else { status_label(f.status) };
+            let reasons = f.reasons.join(", ");
+            println!(
+                "entry #{}: {} {} {} ({}): {}",
+                f.index,
+                f.method,
+                f.url,
+                status,
+                format_time(f.time_ms),
+                if color { reasons.red().to_string() } else { reasons }
+            );
+        }
+
+        if checked == 0 {
+            bail!("No entries to check");
+        }
+        if !failures.is_empty() {
+            bail!("{} of {} entries failed", failures.len(), checked);
+        }
+        println!("All {} entries passed.", checked);
+        Ok(())
+    }
+
+    /// Number of entries checked and those that failed
+    fn check<R: Read>(&self, reader: R) -> Result<(usize, Vec<Failure>)> {
+        let filter = self.fail_status.as_deref().map(FilterExpr::parse).transpose()?;
+        let mut checked = 0usize;
+        let mut failures = Vec::new();
+
+        har::stream_entries(reader, |entry| {
+            checked += 1;
+            let reasons = self.reasons(&entry, filter.as_ref());
+            if !reasons.is_empty() {
+                failures.push(Failure {
+                    index: checked,
+                    method: entry.request.method,
+                    url: entry.request.url,
+                    status: entry.response.status,
+                    time_ms: entry.time,
+                    reasons,
+                });
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        Ok((checked, failures))
+    }
+
+    fn reasons(&self, entry: &Entry, filter: Option<&FilterExpr>) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if let Some(budget) = self.fail_over {
+            if entry.time > budget {
+                reasons.push(format!("time {} > {}", format_time(entry.time), format_time(budget)));
+            }
+        }
+        if let (Some(filter), Some(source)) = (filter, self.fail_status.as_deref()) {
+            if filter.matches(entry) {
+                reasons.push(format!("matches `{}`", source));
+            }
+        }
+        reasons
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{parse_args, try_parse_args};
+    use crate::har::fixtures;
+
+    fn har() -> Vec<u8> {
+        let slow = fixtures::entry_with("GET", "https://example.com/slow", 200, |e| {
+            e["time"] = serde_json::json!(2500.0);
+        });
+        let broken = fixtures::entry_with("POST", "https://example.com/api", 503, |e| {
+            e["time"] = serde_json::json!(3000.0);
+        });
+        fixtures::har_bytes(vec![
+            fixtures::entry_json("GET", "https://example.com/", 200),
+            slow,
+            broken,
+        ])
+    }
+
+    fn check(args: &[&str]) -> (usize, Vec<Failure>) {
+        let cmd = parse_args::<AssertCmd>(args);
+        cmd.check(har().as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_fail_over_budget() {
+        let (checked, failures) = check(&["--fail-over", "2s"]);
+        assert_eq!(checked, 3);
+        let indexes: Vec<usize> = failures.iter().map(|f| f.index).collect();
+        assert_eq!(indexes, [2, 3]);
+        assert_eq!(failures[0].reasons, ["time 2.50s > 2.00s"]);
+
+        assert!(check(&["--fail-over", "5000"]).1.is_empty());
+    }
+
+    #[test]
+    fn test_fail_status_expression_and_both_checks() {
+        let (_, failures) = check(&["--fail-status", "status >= 500"]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!((failures[0].index, failures[0].status), (3, 503));
+
+        let (_, failures) = check(&["--fail-status", "status >= 500", "--fail-over", "2800ms"]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].reasons, ["time 3.00s > 2.80s", "matches `status >= 500`"]);
+    }
+
+    #[test]
+    fn test_non_har_and_empty_input_fail_and_single_entry_is_checked() {
+        let cmd = parse_args::<AssertCmd>(&["--fail-over", "2s"]);
+        assert!(cmd.check(&br#"{"foo": 1}"#[..]).is_err());
+        assert!(cmd.run(&br#"{"foo": 1}"#[..], false).is_err());
+
+        let empty = fixtures::har_bytes(vec![]);
+        assert_eq!(cmd.run(empty.as_slice(), false).unwrap_err().to_string(), "No entries to check");
+
+        let slow = fixtures::entry_with("GET", "https://example.com/slow", 200, |e| {
+            e["time"] = serde_json::json!(2500.0);
+        });
+        let (checked, failures) = cmd.check(slow.to_string().as_bytes()).unwrap();
+        assert_eq!((checked, failures.len()), (1, 1));
+    }
+
+    #[test]
+    fn test_needs_a_check() {
+        assert!(try_parse_args::<AssertCmd>(&["f.har"]).is_err());
+        assert!(try_parse_args::<AssertCmd>(&["--fail-over", "soon"]).is_err());
+        let cmd = parse_args::<AssertCmd>(&["--fail-status", "bogus == 1"]);
+        assert!(cmd.check(har().as_slice()).is_err());
+    }
+}
@@ -1,7 +1,11 @@
-use crate::har::Har;
-use anyhow::{Result, bail};
-use clap::Args;
+use crate::har::{self, Har};
+use crate::output::highlight::render_json;
+use crate::output::{extract_path, format_bytes, url_decode};
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+use std::fs;
 use std::io::{self, Write};
+use std::path::PathBuf;
 
 #[derive(Debug, Args)]
 pub struct BodyCmd {
@@ -24,10 +28,54 @@ pub struct BodyCmd {
     /// Output raw bytes (for binary content)
     #[arg(long)]
     pub raw: bool,
+
+    /// Print the decoded body bytes as base64
+    #[arg(long, conflicts_with_all = ["raw", "hexdump", "pretty", "as_format", "field", "out", "out_auto"])]
+    pub base64: bool,
+
+    /// Print the decoded body bytes as offset, hex and ASCII columns
+    #[arg(long, conflicts_with_all = ["raw", "pretty", "as_format", "field", "out", "out_auto"])]
+    pub hexdump: bool,
+
+    /// Decode the body as this format, ignoring the recorded mime type
+    #[arg(long = "as", value_name = "FORMAT")]
+    pub as_format: Option<BodyFormat>,
+
+    /// Print only the value at this JSON path (e.g., data.users[0].id)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["raw", "as_format"])]
+    pub field: Option<String>,
+
+    /// Keep the body compressed as recorded instead of undoing Content-Encoding
+    #[arg(long)]
+    pub no_decompress: bool,
+
+    /// Write the decoded body bytes to this file instead of stdout
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["field", "out_auto"])]
+    pub out: Option<String>,
+
+    /// Like --out, naming the file after the URL path with an extension from the content type
+    #[arg(long, conflicts_with = "field")]
+    pub out_auto: bool,
+
+    /// Let --out-auto replace an existing file of the same name
+    #[arg(long, requires = "out_auto")]
+    pub force: bool,
+
+    /// Directory holding response bodies referenced by `_file`/`_content`
+    #[arg(long, value_name = "DIR")]
+    pub body_dir: Option<String>,
+}
+
+/// Decoder used to pretty-print a body
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum BodyFormat {
+    Json,
+    Xml,
+    Form,
 }
 
 impl BodyCmd {
-    pub fn run(&self, har: &Har) -> Result<()> {
+    pub fn run(&self, har: &Har, color: bool) -> Result<()> {
         if self.index == 0 || self.index > har.log.entries.len() {
             bail!(
                 "Entry index {} out of range (1-{})",
@@ -36,16 +84,16 @@ impl BodyCmd {
             );
         }
 
-        let entry = &har.log.entries[self.index - 1];
+        let entry = har::with_external_body(&har.log.entries[self.index - 1], self.body_dir.as_deref())?;
 
         if self.request {
-            self.output_request_body(entry)
+            self.output_request_body(&entry, color)
         } else {
-            self.output_response_body(entry)
+            self.output_response_body(&entry, color)
         }
     }
 
-    fn output_request_body(&self, entry: &crate::har::Entry) -> Result<()> {
+    fn output_request_body(&self, entry: &crate::har::Entry, color: bool) -> Result<()> {
         let Some(ref post_data) = entry.request.post_data else {
             bail!("Entry {} has no request body", self.index);
         };
@@ -54,23 +102,34 @@ impl BodyCmd {
             bail!("Entry {} has no request body text", self.index);
         };
 
-        if self.pretty && post_data.mime_type.contains("json") {
-            self.pretty_print_json(text)?;
-        } else {
-            println!("{}", text);
+        if let Some((path, overwrite)) = self.out_path(&entry.request.url, &post_data.mime_type) {
+            return save(&path, text.as_bytes(), overwrite);
         }
 
-        Ok(())
+        if self.write_encoded(&mut io::stdout().lock(), text.as_bytes())? {
+            return Ok(());
+        }
+
+        self.print_text(text, &post_data.mime_type, color)
     }
 
-    fn output_response_body(&self, entry: &crate::har::Entry) -> Result<()> {
+    fn output_response_body(&self, entry: &crate::har::Entry, color: bool) -> Result<()> {
         let content = &entry.response.content;
 
         // Get decoded bytes
-        let Some(bytes) = content.decoded_text() else {
+        let Some(bytes) = entry.response_body(!self.no_decompress) else {
             bail!("Entry {} has no response body", self.index);
         };
 
+        let mime = content.mime_type.as_deref().unwrap_or("");
+        if let Some((path, overwrite)) = self.out_path(&entry.request.url, mime) {
+            return save(&path, &bytes, overwrite);
+        }
+
+        if self.write_encoded(&mut io::stdout().lock(), &bytes)? {
+            return Ok(());
+        }
+
         if self.raw {
             // Output raw bytes to stdout
             io::stdout().write_all(&bytes)?;
@@ -80,28 +139,401 @@ impl BodyCmd {
         // Convert to string
         let text = String::from_utf8_lossy(&bytes);
 
-        if self.pretty {
-            let mime = content.mime_type.as_deref().unwrap_or("");
-            if mime.contains("json") {
-                self.pretty_print_json(&text)?;
-                return Ok(());
+        self.print_text(&text, mime, color)
+    }
+
+    /// Write `bytes` for --base64 or --hexdump; false if neither is set
+    fn write_encoded<W: Write>(&self, out: &mut W, bytes: &[u8]) -> io::Result<bool> {
+        if self.base64 {
+            use base64::{Engine as _, engine::general_purpose::STANDARD};
+            writeln!(out, "{}", STANDARD.encode(bytes))?;
+        } else if self.hexdump {
+            write!(out, "{}", hexdump(bytes))?;
+        }
+        Ok(self.base64 || self.hexdump)
+    }
+
+    /// Destination for --out or --out-auto, if either is set, and whether an
+    /// existing file there may be replaced. A path given to --out is replaced;
+    /// a generated name only with --force
+    fn out_path(&self, url: &str, mime: &str) -> Option<(PathBuf, bool)> {
+        match self.out {
+            Some(ref path) => Some((PathBuf::from(path), true)),
+            None => self.out_auto.then(|| (PathBuf::from(auto_file_name(url, mime)), self.force)),
+        }
+    }
+
+    fn print_text(&self, text: &str, mime: &str, color: bool) -> Result<()> {
+        if let Some(ref path) = self.field {
+            let json: serde_json::Value = serde_json::from_str(text)
+                .map_err(|e| anyhow::anyhow!("Entry {} body is not JSON: {}", self.index, e))?;
+            match select_json_path(&json, path)? {
+                serde_json::Value::String(s) => println!("{}", s),
+                value => println!("{}", render_json(value, color)),
             }
+            return Ok(());
         }
 
-        println!("{}", text);
+        let format = self
+            .as_format
+            .or_else(|| self.pretty.then(|| BodyFormat::from_mime(mime)));
+
+        match format {
+            Some(BodyFormat::Json) if color => match serde_json::from_str(text) {
+                Ok(json) => println!("{}", render_json(&json, true)),
+                Err(_) => println!("{}", text),
+            },
+            Some(format) => println!("{}", format.render(text)),
+            None => println!("{}", text),
+        }
         Ok(())
     }
+}
 
-    fn pretty_print_json(&self, text: &str) -> Result<()> {
-        match serde_json::from_str::<serde_json::Value>(text) {
-            Ok(json) => {
-                println!("{}", serde_json::to_string_pretty(&json)?);
+/// `hexdump -C` style dump: 16 bytes per row as offset, hex in two groups
+/// of 8, and printable ASCII, then a line with the total length
+fn hexdump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let mut hex = String::new();
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(b) => hex.push_str(&format!("{:02x} ", b)),
+                None => hex.push_str("   "),
             }
-            Err(_) => {
-                // Not valid JSON, print as-is
-                println!("{}", text);
+            if i == 7 {
+                hex.push(' ');
             }
         }
-        Ok(())
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        out.push_str(&format!("{:08x}  {} |{}|\n", row * 16, hex, ascii));
+    }
+    out.push_str(&format!("{:08x}\n", bytes.len()));
+    out
+}
+
+/// Write body bytes unchanged and report where they went on stderr
+fn save(path: &std::path::Path, bytes: &[u8], overwrite: bool) -> Result<()> {
+    if overwrite {
+        fs::write(path, bytes).with_context(|| format!("Failed to write file: {}", path.display()))?;
+    } else {
+        let mut file = match fs::OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                bail!("{} already exists; pass --force to overwrite it", path.display())
+            }
+            Err(err) => return Err(err).with_context(|| format!("Failed to create file: {}", path.display())),
+        };
+        file.write_all(bytes).with_context(|| format!("Failed to write file: {}", path.display()))?;
+    }
+    eprintln!("Wrote {} to {}", format_bytes(bytes.len() as i64), path.display());
+    Ok(())
+}
+
+/// File name for --out-auto: the last URL path segment, with its extension
+/// replaced by one matching the content type (e.g. "/img/logo?v=2" + image/png -> "logo.png")
+fn auto_file_name(url: &str, mime: &str) -> String {
+    let path = extract_path(url).split(['?', '#']).next().unwrap_or("");
+    let segment = url_decode(path.rsplit('/').next().unwrap_or(""));
+    let segment: String = segment
+        .chars()
+        .map(|c| if c.is_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+        .collect();
+    let segment = segment.trim_start_matches('.');
+
+    let (stem, existing) = match segment.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (segment, None),
+    };
+    let stem = if stem.is_empty() { "body" } else { stem };
+
+    match extension_for_mime(mime).or(existing) {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => format!("{}.bin", stem),
+    }
+}
+
+/// Usual file extension for a mime type, ignoring parameters like charset
+fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    let mime = mime.split(';').next().unwrap_or("").trim().to_lowercase();
+    Some(match mime.as_str() {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "font/woff" | "application/font-woff" => "woff",
+        "font/woff2" => "woff2",
+        "font/ttf" | "application/x-font-ttf" => "ttf",
+        "font/otf" => "otf",
+        "application/json" => "json",
+        "application/javascript" | "text/javascript" | "application/x-javascript" => "js",
+        "text/css" => "css",
+        "text/html" => "html",
+        "text/plain" => "txt",
+        "text/csv" => "csv",
+        "application/xml" | "text/xml" => "xml",
+        "application/pdf" => "pdf",
+        "application/wasm" => "wasm",
+        "application/zip" => "zip",
+        "video/mp4" => "mp4",
+        "video/webm" => "webm",
+        "audio/mpeg" => "mp3",
+        _ => return None,
+    })
+}
+
+/// Follow a dotted path with `[index]` array access (e.g. `data.items[2].id`)
+fn select_json_path<'a>(json: &'a serde_json::Value, path: &str) -> Result<&'a serde_json::Value> {
+    let mut current = json;
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+
+    while !rest.is_empty() {
+        rest = rest.strip_prefix('.').unwrap_or(rest);
+
+        if let Some(after) = rest.strip_prefix('[') {
+            let Some(end) = after.find(']') else {
+                bail!("Unclosed '[' in path '{}'", path);
+            };
+            let index: usize = after[..end]
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid array index '{}' in path '{}'", &after[..end], path))?;
+            let Some(items) = current.as_array() else {
+                bail!("Cannot index into non-array with [{}] in path '{}'", index, path);
+            };
+            let Some(item) = items.get(index) else {
+                bail!("Index [{}] out of range (length {}) in path '{}'", index, items.len(), path);
+            };
+            current = item;
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            let key = &rest[..end];
+            let Some(value) = current.as_object().and_then(|obj| obj.get(key)) else {
+                bail!("Key '{}' not found in path '{}'", key, path);
+            };
+            current = value;
+            rest = &rest[end..];
+        }
+    }
+
+    Ok(current)
+}
+
+impl BodyFormat {
+    /// Pick a decoder from a mime type, falling back to JSON for unknown types
+    pub fn from_mime(mime: &str) -> Self {
+        let mime = mime.to_lowercase();
+        if mime.contains("xml") {
+            BodyFormat::Xml
+        } else if mime.contains("x-www-form-urlencoded") {
+            BodyFormat::Form
+        } else {
+            BodyFormat::Json
+        }
+    }
+
+    /// Pretty-print text in this format, returning it unchanged if it doesn't parse
+    pub fn render(&self, text: &str) -> String {
+        let rendered = match self {
+            BodyFormat::Json => serde_json::from_str::<serde_json::Value>(text)
+                .ok()
+                .and_then(|json| serde_json::to_string_pretty(&json).ok()),
+            BodyFormat::Xml => pretty_xml(text),
+            BodyFormat::Form => pretty_form(text),
+        };
+        rendered.unwrap_or_else(|| text.to_string())
+    }
+}
+
+/// Indent XML one element per line
+fn pretty_xml(text: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut rest = text.trim();
+
+    if !rest.starts_with('<') {
+        return None;
+    }
+
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = rest.find('>')? + 1;
+            let tag = &rest[..end];
+
+            if tag.starts_with("</") {
+                depth = depth.saturating_sub(1);
+                lines.push(format!("{}{}", "  ".repeat(depth), tag));
+            } else {
+                lines.push(format!("{}{}", "  ".repeat(depth), tag));
+                if !tag.ends_with("/>") && !tag.starts_with("<?") && !tag.starts_with("<!") {
+                    depth += 1;
+                }
+            }
+            rest = rest[end..].trim_start();
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let content = rest[..end].trim();
+            if !content.is_empty() {
+                lines.push(format!("{}{}", "  ".repeat(depth), content));
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+/// Decode an urlencoded form into one `name = value` line per field
+fn pretty_form(text: &str) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() || !text.contains('=') {
+        return None;
+    }
+
+    let lines: Vec<String> = text
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            format!("{} = {}", url_decode(name), url_decode(value))
+        })
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{parse_args, try_parse_args};
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_pretty_json_labeled_text_plain() {
+        let format = BodyFormat::from_mime("text/plain");
+        assert_eq!(format, BodyFormat::Json);
+        assert_eq!(format.render(r#"{"a":1}"#), "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn test_render_falls_back_to_raw_text() {
+        assert_eq!(BodyFormat::Json.render("not json"), "not json");
+    }
+
+    #[test]
+    fn test_render_form() {
+        let rendered = BodyFormat::Form.render("name=John+Doe&email=a%40b.com");
+        assert_eq!(rendered, "name = John Doe\nemail = a@b.com");
+    }
+
+    #[test]
+    fn test_select_json_path() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"data": {"users": [{"id": 7}, {"id": 9, "tags": ["a", "b"]}]}}"#).unwrap();
+
+        assert_eq!(select_json_path(&json, "data.users[1].id").unwrap(), 9);
+        assert_eq!(select_json_path(&json, "$.data.users[1].tags[0]").unwrap(), "a");
+        assert_eq!(select_json_path(&json, "data.users").unwrap().as_array().unwrap().len(), 2);
+        assert!(select_json_path(&json, "data.missing").is_err());
+        assert!(select_json_path(&json, "data.users[5]").is_err());
+        assert!(select_json_path(&json, "data[0]").is_err());
+    }
+
+    #[test]
+    fn test_render_xml() {
+        let rendered = BodyFormat::Xml.render("<a><b>text</b><c/></a>");
+        assert_eq!(rendered, "<a>\n  <b>\n    text\n  </b>\n  <c/>\n</a>");
+    }
+
+    #[test]
+    fn test_out_writes_png_bytes_unchanged() {
+        // 1x1 transparent PNG; not valid UTF-8, so a lossy conversion would corrupt it
+        const PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let json = fixtures::entry_with("GET", "https://example.com/img/pixel", 200, |e| {
+            e["response"]["content"] = serde_json::json!({"size": 70, "mimeType": "image/png", "text": PNG, "encoding": "base64"});
+        });
+        let har = fixtures::har(vec![json]);
+
+        let path = std::env::temp_dir().join(format!("harq-body-out-{}.png", std::process::id()));
+        let cmd = BodyCmd {
+            index: 1,
+            file: "-".to_string(),
+            request: false,
+            pretty: false,
+            raw: false,
+            base64: false,
+            hexdump: false,
+            as_format: None,
+            field: None,
+            no_decompress: false,
+            out: Some(path.display().to_string()),
+            out_auto: false,
+            force: false,
+            body_dir: None,
+        };
+        cmd.run(&har, false).unwrap();
+        let written = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        use base64::{Engine as _, engine::general_purpose::STANDARD};
+        assert_eq!(written, STANDARD.decode(PNG).unwrap());
+        assert!(written.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_generated_name_is_not_overwritten_without_force() {
+        let path = std::env::temp_dir().join(format!("harq-body-force-{}.txt", std::process::id()));
+        fs::write(&path, "keep me").unwrap();
+
+        let err = save(&path, b"new", false).unwrap_err();
+        assert!(err.to_string().ends_with("already exists; pass --force to overwrite it"), "{}", err);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "keep me");
+
+        save(&path, b"new", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_auto_file_name_from_url_and_mime() {
+        assert_eq!(auto_file_name("https://example.com/img/logo?v=2", "image/png"), "logo.png");
+        assert_eq!(auto_file_name("https://example.com/photo.php", "image/jpeg"), "photo.jpg");
+        assert_eq!(auto_file_name("https://example.com/app.js", "application/octet-stream"), "app.js");
+        assert_eq!(auto_file_name("https://example.com/", "text/html; charset=utf-8"), "body.html");
+        assert_eq!(auto_file_name("https://example.com/..%2Fetc", "x/unknown"), "_etc.bin");
+    }
+
+    #[test]
+    fn test_hexdump_rows() {
+        let bytes: Vec<u8> = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0xff, b' ', b'~', 0x7f, b'a', b'b', b'c', b'z', 0x80]
+            .to_vec();
+        assert_eq!(
+            hexdump(&bytes),
+            "00000000  89 50 4e 47 0d 0a 1a 0a  00 ff 20 7e 7f 61 62 63  |.PNG...... ~.abc|\n\
+             00000010  7a 80                                             |z.|\n\
+             00000012\n"
+        );
+        assert_eq!(hexdump(&[]), "00000000\n");
+    }
+
+    #[test]
+    fn test_base64_and_hexdump_flags() {
+        let encoded = |args: &[&str]| {
+            let mut out = Vec::new();
+            let written = parse_args::<BodyCmd>(&[&["1"], args].concat()).write_encoded(&mut out, &[0, 1, 2]).unwrap();
+            (written, String::from_utf8(out).unwrap())
+        };
+        assert_eq!(encoded(&["--base64"]), (true, "AAEC\n".to_string()));
+        assert_eq!(encoded(&["--hexdump"]), (true, hexdump(&[0, 1, 2])));
+        assert_eq!(encoded(&[]), (false, String::new()));
+        assert!(try_parse_args::<BodyCmd>(&["1", "--base64", "--hexdump"]).is_err());
+        assert!(try_parse_args::<BodyCmd>(&["1", "--hexdump", "--raw"]).is_err());
     }
 }
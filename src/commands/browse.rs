@@ -0,0 +1,307 @@
+use crate::filter::eval::FilterExpr;
+use crate::har::Har;
+use crate::output::table::{status_label, write_entry_detail};
+use anyhow::Result;
+use clap::Args;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Position};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+const HELP: &str = "j/k move  J/K scroll detail  / filter  g/G first/last  q quit";
+
+#[derive(Debug, Args)]
+pub struct BrowseCmd {
+    /// HAR file to browse (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+}
+
+impl BrowseCmd {
+    pub fn run(&self, har: Har) -> Result<()> {
+        // The detail pane is plain text; ANSI codes would show up literally
+        colored::control::set_override(false);
+
+        let mut terminal = ratatui::init();
+        let result = App::new(har).run(&mut terminal);
+        ratatui::restore();
+        result
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Browse,
+    Filter,
+}
+
+struct App {
+    har: Har,
+    /// Indexes into `har.log.entries` that pass the current filter
+    visible: Vec<usize>,
+    list: ListState,
+    detail_scroll: u16,
+    mode: Mode,
+    input: String,
+    filter: Option<String>,
+    message: Option<String>,
+    quit: bool,
+}
+
+impl App {
+    fn new(har: Har) -> Self {
+        let visible = (0..har.log.entries.len()).collect();
+        let mut app = Self {
+            har,
+            visible,
+            list: ListState::default(),
+            detail_scroll: 0,
+            mode: Mode::Browse,
+            input: String::new(),
+            filter: None,
+            message: None,
+            quit: false,
+        };
+        app.select(0);
+        app
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+
+        match self.mode {
+            Mode::Browse => self.handle_browse_key(key.code),
+            Mode::Filter => self.handle_filter_key(key.code),
+        }
+    }
+
+    fn handle_browse_key(&mut self, code: KeyCode) {
+        let current = self.list.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('j') | KeyCode::Down => self.select(current + 1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(current.saturating_sub(1)),
+            KeyCode::PageDown => self.select(current + 10),
+            KeyCode::PageUp => self.select(current.saturating_sub(10)),
+            KeyCode::Char('g') | KeyCode::Home => self.select(0),
+            KeyCode::Char('G') | KeyCode::End => self.select(usize::MAX),
+            KeyCode::Char('J') => self.detail_scroll = self.detail_scroll.saturating_add(1),
+            KeyCode::Char('K') => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+            KeyCode::Char('/') => {
+                self.mode = Mode::Filter;
+                self.input = self.filter.clone().unwrap_or_default();
+                self.message = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_filter_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut self.input);
+                self.mode = Mode::Browse;
+                if let Err(err) = self.apply_filter(&input) {
+                    self.message = Some(format!("Invalid filter: {}", err));
+                }
+            }
+            KeyCode::Esc => {
+                self.input.clear();
+                self.mode = Mode::Browse;
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Show only entries matching `input`; an empty expression clears the filter
+    fn apply_filter(&mut self, input: &str) -> Result<()> {
+        let input = input.trim();
+        if input.is_empty() {
+            self.filter = None;
+            self.visible = (0..self.har.log.entries.len()).collect();
+        } else {
+            let expr = FilterExpr::parse(input)?;
+            self.filter = Some(input.to_string());
+            self.visible = self.har.log.entries
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| expr.matches(e))
+                .map(|(i, _)| i)
+                .collect();
+        }
+        self.select(0);
+        Ok(())
+    }
+
+    /// Select a row, clamped to the visible entries
+    fn select(&mut self, row: usize) {
+        self.detail_scroll = 0;
+        if self.visible.is_empty() {
+            self.list.select(None);
+        } else {
+            self.list.select(Some(row.min(self.visible.len() - 1)));
+        }
+    }
+
+    /// Index into `har.log.entries` of the selected row
+    fn selected_entry(&self) -> Option<usize> {
+        self.list.selected().and_then(|row| self.visible.get(row).copied())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+        let items: Vec<ListItem> = self.visible
+            .iter()
+            .map(|&i| {
+                let e = &self.har.log.entries[i];
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("#{:<4} {:<7} ", i + 1, e.request.method)),
+                    Span::styled(format!("{:<4}", status_label(e.response.status)), status_style(e.response.status)),
+                    Span::raw(format!(" {}", e.request.url)),
+                ]))
+            })
+            .collect();
+
+        let mut title = format!(" {}/{} entries ", self.visible.len(), self.har.log.entries.len());
+        if let Some(ref filter) = self.filter {
+            title.push_str(&format!("[{}] ", filter));
+        }
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left, &mut self.list);
+
+        let detail = self.selected_entry().map(|i| self.detail_text(i)).unwrap_or_default();
+        let detail = Paragraph::new(detail)
+            .block(Block::default().borders(Borders::ALL).title(" Detail "))
+            .scroll((self.detail_scroll, 0));
+        frame.render_widget(detail, right);
+
+        match self.mode {
+            Mode::Filter => {
+                frame.render_widget(Paragraph::new(format!("/{}", self.input)), status);
+                let cursor_x = status.x + 1 + self.input.chars().count() as u16;
+                frame.set_cursor_position(Position::new(cursor_x.min(status.right().saturating_sub(1)), status.y));
+            }
+            Mode::Browse => {
+                let line = match self.message {
+                    Some(ref msg) => Line::styled(msg.as_str(), Style::default().fg(Color::Red)),
+                    None => Line::styled(HELP, Style::default().add_modifier(Modifier::DIM)),
+                };
+                frame.render_widget(Paragraph::new(line), status);
+            }
+        }
+    }
+
+    /// The same text `view` prints for the entry, bodies included
+    fn detail_text(&self, i: usize) -> String {
+        let mut buf = Vec::new();
+        let _ = write_entry_detail(&mut buf, i + 1, &self.har.log.entries[i], false, true, false);
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+}
+
+fn status_style(status: i32) -> Style {
+    match status {
+        200..=299 => Style::default().fg(Color::Green),
+        300..=399 => Style::default().fg(Color::Yellow),
+        400..=599 => Style::default().fg(Color::Red),
+        _ => Style::default().fg(Color::DarkGray),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn app() -> App {
+        App::new(fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/", 200),
+            fixtures::entry_json("POST", "https://api.example.com/login", 401),
+            fixtures::entry_json("GET", "https://example.com/missing", 404),
+        ]))
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_filter(app: &mut App, text: &str) {
+        press(app, KeyCode::Char('/'));
+        app.input.clear();
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+        press(app, KeyCode::Enter);
+    }
+
+    #[test]
+    fn test_navigation_is_clamped() {
+        let mut app = app();
+        assert_eq!(app.selected_entry(), Some(0));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.selected_entry(), Some(0));
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.selected_entry(), Some(2));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_entry(), Some(2));
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_filter_narrows_and_clears() {
+        let mut app = app();
+        type_filter(&mut app, "status >= 400");
+        assert_eq!(app.visible, [1, 2]);
+        assert_eq!(app.filter.as_deref(), Some("status >= 400"));
+        assert_eq!(app.selected_entry(), Some(1));
+        assert_eq!(app.mode, Mode::Browse);
+
+        type_filter(&mut app, "");
+        assert_eq!(app.visible, [0, 1, 2]);
+        assert_eq!(app.filter, None);
+    }
+
+    #[test]
+    fn test_invalid_filter_keeps_previous_selection() {
+        let mut app = app();
+        type_filter(&mut app, "method == POST");
+        type_filter(&mut app, "bogus == 1");
+        assert!(app.message.as_deref().unwrap().starts_with("Invalid filter"));
+        assert_eq!(app.visible, [1]);
+        assert_eq!(app.filter.as_deref(), Some("method == POST"));
+    }
+
+    #[test]
+    fn test_detail_matches_view_output() {
+        let app = app();
+        let text = app.detail_text(1);
+        assert!(text.contains("Entry #2"));
+        assert!(text.contains("https://api.example.com/login"));
+    }
+}
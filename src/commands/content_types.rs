@@ -0,0 +1,206 @@
+use crate::commands::domains::SizeMode;
+use crate::har::{Entry, Har};
+use crate::output::{format_bytes, OutputFormat};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use crate::output::table::style_table;
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::collections::HashMap;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct ContentTypesCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Group by exact content type or by broad category
+    #[arg(short, long, default_value = "exact")]
+    pub group: ContentTypeGrouping,
+
+    /// Count the recorded body size, bytes sent over the network, or bytes after decompression
+    #[arg(long, default_value = "transfer")]
+    pub size_mode: SizeMode,
+}
+
+/// How `content-types --group` buckets responses
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum ContentTypeGrouping {
+    /// Content type without parameters, e.g. `text/html`
+    #[default]
+    Exact,
+    /// image, script, style, document, json, font or other
+    Coarse,
+}
+
+impl ContentTypeGrouping {
+    /// Bucket an entry's response falls into
+    pub fn key(self, entry: &Entry) -> String {
+        match self {
+            ContentTypeGrouping::Exact => normalized_content_type(entry).unwrap_or_else(|| "unknown".to_string()),
+            ContentTypeGrouping::Coarse => category(normalized_content_type(entry).as_deref()).to_string(),
+        }
+    }
+}
+
+/// Totals for one content type or category
+#[derive(Debug, serde::Serialize)]
+pub struct ContentTypeCount {
+    pub content_type: String,
+    pub count: usize,
+    pub bytes: i64,
+}
+
+#[derive(Tabled)]
+struct ContentTypeRow {
+    #[tabled(rename = "Type")]
+    content_type: String,
+    #[tabled(rename = "Requests")]
+    count: usize,
+    #[tabled(rename = "Bytes")]
+    bytes: String,
+}
+
+impl ContentTypesCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let types = collect_content_types(har, self.group, self.size_mode);
+
+        match self.output {
+            OutputFormat::Json => print_json(&types, true)?,
+            OutputFormat::Ndjson => print_ndjson(&types)?,
+            OutputFormat::Yaml => print_yaml(&types)?,
+            OutputFormat::Compact => {
+                for t in &types {
+                    println!("{}\t{}\t{}", t.content_type, t.count, t.bytes);
+                }
+            }
+            OutputFormat::Table => {
+                if types.is_empty() {
+                    println!("No entries found.");
+                    return Ok(());
+                }
+
+                let rows: Vec<ContentTypeRow> = types
+                    .iter()
+                    .map(|t| ContentTypeRow {
+                        content_type: t.content_type.clone(),
+                        count: t.count,
+                        bytes: format_bytes(t.bytes),
+                    })
+                    .collect();
+
+                let mut table = Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Count responses and bytes per content type, most requests first
+pub fn collect_content_types(har: &Har, group: ContentTypeGrouping, size_mode: SizeMode) -> Vec<ContentTypeCount> {
+    let mut groups: HashMap<String, ContentTypeCount> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let key = group.key(entry);
+        let counts = groups.entry(key).or_insert_with_key(|key| ContentTypeCount {
+            content_type: key.clone(),
+            count: 0,
+            bytes: 0,
+        });
+        counts.count += 1;
+        counts.bytes += size_mode.size(entry).unwrap_or(0);
+    }
+
+    let mut types: Vec<ContentTypeCount> = groups.into_values().collect();
+    types.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.content_type.cmp(&b.content_type)));
+    types
+}
+
+/// Lowercased response content type without parameters such as `; charset=utf-8`
+fn normalized_content_type(entry: &Entry) -> Option<String> {
+    let mime = entry.content_type()?.split(';').next().unwrap_or("").trim();
+    (!mime.is_empty()).then(|| mime.to_lowercase())
+}
+
+/// Broad category of a normalized content type
+fn category(mime: Option<&str>) -> &'static str {
+    let Some(mime) = mime else {
+        return "other";
+    };
+
+    if mime.starts_with("image/") {
+        "image"
+    } else if mime.contains("javascript") || mime.contains("ecmascript") {
+        "script"
+    } else if mime == "text/css" {
+        "style"
+    } else if mime.contains("html") {
+        "document"
+    } else if mime.contains("json") {
+        "json"
+    } else if mime.starts_with("font/") || mime.contains("woff") || mime.contains("opentype") || mime.contains("truetype") {
+        "font"
+    } else {
+        "other"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn entry(mime: &str, size: i64) -> serde_json::Value {
+        fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["content"] = serde_json::json!({"size": size, "mimeType": mime});
+            e["response"]["bodySize"] = serde_json::json!(size);
+        })
+    }
+
+    fn har() -> Har {
+        fixtures::har(vec![
+            entry("text/html; charset=utf-8", 1000),
+            entry("application/json", 200),
+            entry("application/json;charset=UTF-8", 300),
+            entry("application/vnd.api+json", 50),
+            entry("image/png", 4000),
+            entry("text/javascript", 700),
+            entry("font/woff2", 900),
+            entry("", 10),
+        ])
+    }
+
+    #[test]
+    fn test_exact_strips_parameters() {
+        let types = collect_content_types(&har(), ContentTypeGrouping::Exact, SizeMode::Transfer);
+
+        assert_eq!(types[0].content_type, "application/json");
+        assert_eq!((types[0].count, types[0].bytes), (2, 500));
+        assert!(types.iter().any(|t| t.content_type == "text/html"));
+        assert!(types.iter().any(|t| t.content_type == "unknown"));
+        assert_eq!(types.len(), 7);
+    }
+
+    #[test]
+    fn test_coarse_categories() {
+        let types = collect_content_types(&har(), ContentTypeGrouping::Coarse, SizeMode::Transfer);
+        let find = |name: &str| types.iter().find(|t| t.content_type == name).map(|t| (t.count, t.bytes));
+
+        assert_eq!(types[0].content_type, "json");
+        assert_eq!(find("json"), Some((3, 550)));
+        assert_eq!(find("document"), Some((1, 1000)));
+        assert_eq!(find("image"), Some((1, 4000)));
+        assert_eq!(find("script"), Some((1, 700)));
+        assert_eq!(find("font"), Some((1, 900)));
+        assert_eq!(find("other"), Some((1, 10)));
+        assert_eq!(find("style"), None);
+    }
+}
@@ -1,17 +1,221 @@
-use crate::har::Har;
+use crate::commands::content_types::ContentTypeGrouping;
+use crate::filter::eval::Field;
+use crate::har::{self, Entry};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use crate::output::{extract_host, OutputFormat};
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use std::ops::ControlFlow;
 
 #[derive(Debug, Args)]
 pub struct CountCmd {
     /// HAR file to analyze (use - for stdin)
     #[arg(default_value = "-")]
     pub file: String,
+
+    /// Count distinct values of a field instead of entries (e.g., host, status)
+    #[arg(long, value_name = "FIELD")]
+    pub distinct: Option<String>,
+
+    /// Print a count per status, host, method or content type instead of the total
+    #[arg(long, alias = "count-by", conflicts_with = "distinct")]
+    pub by: Option<CountBy>,
+
+    /// Output format for --by; json and yaml print an object of counts
+    #[arg(short, long, default_value = "table", requires = "by")]
+    pub output: OutputFormat,
+}
+
+/// Grouping used by `count --by`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CountBy {
+    Status,
+    Host,
+    Method,
+    /// Response content type without parameters, or `unknown`
+    ContentType,
+}
+
+impl CountBy {
+    fn key(self, entry: &Entry) -> String {
+        match self {
+            CountBy::Status => entry.response.status.to_string(),
+            CountBy::Host => match extract_host(&entry.request.url) {
+                "" => "unknown".to_string(),
+                host => host.to_string(),
+            },
+            CountBy::Method => entry.request.method.clone(),
+            CountBy::ContentType => ContentTypeGrouping::Exact.key(entry),
+        }
+    }
 }
 
 impl CountCmd {
-    pub fn run(&self, har: &Har) -> Result<()> {
-        println!("{}", har.log.entries.len());
+    pub fn run<R: Read>(&self, reader: R) -> Result<()> {
+        let Some(by) = self.by else {
+            println!("{}", self.count(reader)?);
+            return Ok(());
+        };
+
+        let counts = count_by(reader, by)?;
+        match self.output {
+            OutputFormat::Json => print_json(&counts, true)?,
+            OutputFormat::Ndjson => print_ndjson(tallies(&counts))?,
+            OutputFormat::Yaml => print_yaml(&counts)?,
+            OutputFormat::Table | OutputFormat::Compact => {
+                for (key, count) in most_common(&counts) {
+                    println!("{}\t{}", key, count);
+                }
+            }
+        }
         Ok(())
     }
+
+    fn count<R: Read>(&self, reader: R) -> Result<usize> {
+        let Some(ref name) = self.distinct else {
+            let mut count = 0usize;
+            har::stream_entries(reader, |_| {
+                count += 1;
+                ControlFlow::Continue(())
+            })?;
+            return Ok(count);
+        };
+
+        // Entries without the field don't contribute a value
+        let field = Field::parse(name)?;
+        let mut seen = HashSet::new();
+        har::stream_entries(reader, |entry| {
+            if let Some(value) = field.get_string(&entry) {
+                seen.insert(value);
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(seen.len())
+    }
+}
+
+/// Entries per `by` key
+fn count_by<R: Read>(reader: R, by: CountBy) -> Result<BTreeMap<String, usize>> {
+    let mut counts = BTreeMap::new();
+    har::stream_entries(reader, |entry| {
+        *counts.entry(by.key(&entry)).or_insert(0) += 1;
+        ControlFlow::Continue(())
+    })?;
+    Ok(counts)
+}
+
+/// Counts from most to least common, ties in key order
+pub fn most_common(counts: &BTreeMap<String, usize>) -> Vec<(&str, usize)> {
+    let mut sorted: Vec<(&str, usize)> = counts.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    sorted.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    sorted
+}
+
+/// One `-o ndjson` line: a key and how often it occurred
+#[derive(Debug, Serialize)]
+pub struct Tally<'a> {
+    pub key: &'a str,
+    pub count: usize,
+}
+
+/// Counts as `-o ndjson` lines, most common first
+pub fn tallies(counts: &BTreeMap<String, usize>) -> Vec<Tally<'_>> {
+    most_common(counts).into_iter().map(|(key, count)| Tally { key, count }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_distinct_hosts() {
+        let har = fixtures::har_bytes(vec![
+            fixtures::entry_json("GET", "https://a.example.com/1", 200),
+            fixtures::entry_json("GET", "https://b.example.com/2", 200),
+            fixtures::entry_json("POST", "https://a.example.com/3", 500),
+            fixtures::entry_json("GET", "https://a.example.com:8443/4", 200),
+        ]);
+
+        let cmd = |distinct: Option<&str>| CountCmd {
+            file: "-".to_string(),
+            distinct: distinct.map(String::from),
+            by: None,
+            output: OutputFormat::Table,
+        };
+
+        assert_eq!(cmd(None).count(har.as_slice()).unwrap(), 4);
+        assert_eq!(cmd(Some("host")).count(har.as_slice()).unwrap(), 2);
+        assert_eq!(cmd(Some("status")).count(har.as_slice()).unwrap(), 2);
+        assert!(cmd(Some("bogus")).count(har.as_slice()).is_err());
+    }
+
+    fn grouped_har() -> Vec<u8> {
+        let json_entry = fixtures::entry_with("POST", "https://api.example.com/login", 401, |e| {
+            e["response"]["content"]["mimeType"] = serde_json::json!("application/json; charset=utf-8");
+        });
+        let html = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["content"]["mimeType"] = serde_json::json!("text/html");
+        });
+        fixtures::har_bytes(vec![
+            html,
+            json_entry,
+            fixtures::entry_json("GET", "https://api.example.com/me", 200),
+            fixtures::entry_json("GET", "https://example.com/missing", 404),
+        ])
+    }
+
+    fn counts(by: CountBy) -> Vec<(String, usize)> {
+        let counts = count_by(grouped_har().as_slice(), by).unwrap();
+        most_common(&counts).into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+    }
+
+    fn pairs(expected: &[(&str, usize)]) -> Vec<(String, usize)> {
+        expected.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_count_by_status() {
+        assert_eq!(counts(CountBy::Status), pairs(&[("200", 2), ("401", 1), ("404", 1)]));
+    }
+
+    #[test]
+    fn test_count_by_host() {
+        assert_eq!(counts(CountBy::Host), pairs(&[("api.example.com", 2), ("example.com", 2)]));
+    }
+
+    #[test]
+    fn test_count_by_method() {
+        assert_eq!(counts(CountBy::Method), pairs(&[("GET", 3), ("POST", 1)]));
+    }
+
+    #[test]
+    fn test_count_by_content_type() {
+        let counts = counts(CountBy::ContentType);
+        assert!(counts.contains(&("application/json".to_string(), 1)));
+        assert!(counts.contains(&("text/html".to_string(), 1)));
+        assert_eq!(counts.iter().map(|(_, n)| n).sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn test_count_by_json_is_object() {
+        let counts = count_by(grouped_har().as_slice(), CountBy::Method).unwrap();
+        assert_eq!(serde_json::to_value(&counts).unwrap(), serde_json::json!({"GET": 3, "POST": 1}));
+    }
+
+    #[test]
+    fn test_count_by_ndjson_is_one_object_per_line() {
+        let counts = count_by(grouped_har().as_slice(), CountBy::Method).unwrap();
+        let mut out = Vec::new();
+        crate::output::json::write_ndjson(&mut out, tallies(&counts)).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"key\":\"GET\",\"count\":3}\n{\"key\":\"POST\",\"count\":1}\n"
+        );
+    }
 }
@@ -0,0 +1,189 @@
+use crate::har::{Entry, Har};
+use crate::output::{extract_host, format_bytes, format_time, OutputFormat};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use crate::output::table::style_table;
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::collections::HashMap;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct DomainsCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Sort hosts by (descending)
+    #[arg(short, long, default_value = "count")]
+    pub sort: DomainSort,
+
+    /// Count the recorded body size, bytes sent over the network, or bytes after decompression
+    #[arg(long, default_value = "body")]
+    pub size_mode: SizeMode,
+}
+
+/// Fields `domains --sort` can order by
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DomainSort {
+    Count,
+    Bytes,
+    Time,
+}
+
+/// Which response size `--size-mode` adds up
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum SizeMode {
+    /// Response `bodySize` as recorded
+    #[default]
+    Body,
+    /// Headers plus the body as sent, compressed if it was
+    Transfer,
+    /// Uncompressed body size
+    Decoded,
+}
+
+impl SizeMode {
+    /// Size of one response in this mode, None when the HAR doesn't record it
+    pub fn size(self, entry: &Entry) -> Option<i64> {
+        match self {
+            // -1 means unknown in HAR
+            SizeMode::Body => Some(entry.response.body_size).filter(|size| *size >= 0),
+            SizeMode::Transfer => entry.transfer_size(),
+            SizeMode::Decoded => entry.content_decoded_size(),
+        }
+    }
+}
+
+/// Traffic totals for one host
+#[derive(Debug, serde::Serialize)]
+pub struct Domain {
+    pub host: String,
+    pub count: usize,
+    pub bytes: i64,
+    pub time_ms: f64,
+}
+
+#[derive(Tabled)]
+struct DomainRow {
+    #[tabled(rename = "Host")]
+    host: String,
+    #[tabled(rename = "Requests")]
+    count: usize,
+    #[tabled(rename = "Bytes")]
+    bytes: String,
+    #[tabled(rename = "Time")]
+    time: String,
+}
+
+impl DomainsCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let domains = collect_domains(har, self.sort, self.size_mode);
+
+        match self.output {
+            OutputFormat::Json => print_json(&domains, true)?,
+            OutputFormat::Ndjson => print_ndjson(&domains)?,
+            OutputFormat::Yaml => print_yaml(&domains)?,
+            OutputFormat::Compact => {
+                for d in &domains {
+                    println!("{}\t{}\t{}\t{:.0}", d.host, d.count, d.bytes, d.time_ms);
+                }
+            }
+            OutputFormat::Table => {
+                if domains.is_empty() {
+                    println!("No entries found.");
+                    return Ok(());
+                }
+
+                let rows: Vec<DomainRow> = domains
+                    .iter()
+                    .map(|d| DomainRow {
+                        host: d.host.clone(),
+                        count: d.count,
+                        bytes: format_bytes(d.bytes),
+                        time: format_time(d.time_ms),
+                    })
+                    .collect();
+
+                let mut table = Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sum requests, response bytes and time per host, largest first
+pub fn collect_domains(har: &Har, sort: DomainSort, size_mode: SizeMode) -> Vec<Domain> {
+    let mut groups: HashMap<&str, Domain> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let host = extract_host(&entry.request.url);
+        let domain = groups.entry(host).or_insert_with(|| Domain {
+            host: host.to_string(),
+            count: 0,
+            bytes: 0,
+            time_ms: 0.0,
+        });
+        domain.count += 1;
+        domain.bytes += size_mode.size(entry).unwrap_or(0);
+        domain.time_ms += entry.time.max(0.0);
+    }
+
+    let mut domains: Vec<Domain> = groups.into_values().collect();
+    domains.sort_by(|a, b| {
+        let order = match sort {
+            DomainSort::Count => b.count.cmp(&a.count),
+            DomainSort::Bytes => b.bytes.cmp(&a.bytes),
+            DomainSort::Time => b.time_ms.total_cmp(&a.time_ms),
+        };
+        order.then_with(|| a.host.cmp(&b.host))
+    });
+    domains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn entry(url: &str, body_size: i64, time: f64) -> serde_json::Value {
+        fixtures::entry_with("GET", url, 200, |e| {
+            e["response"]["bodySize"] = serde_json::json!(body_size);
+            e["response"]["content"] = serde_json::json!({"size": body_size * 3, "compression": body_size * 2});
+            e["time"] = serde_json::json!(time);
+        })
+    }
+
+    #[test]
+    fn test_aggregate_per_host() {
+        let har = fixtures::har(vec![
+            entry("https://a.example.com/1", 100, 10.0),
+            entry("https://a.example.com:443/2", -1, 20.0),
+            entry("https://cdn.example.com/big.js", 5000, 5.0),
+        ]);
+
+        let by_count = collect_domains(&har, DomainSort::Count, SizeMode::Body);
+        assert_eq!(by_count.len(), 2);
+        assert_eq!(by_count[0].host, "a.example.com");
+        assert_eq!(by_count[0].count, 2);
+        assert_eq!(by_count[0].bytes, 100);
+        assert_eq!(by_count[0].time_ms, 30.0);
+
+        let by_bytes = collect_domains(&har, DomainSort::Bytes, SizeMode::Body);
+        assert_eq!(by_bytes[0].host, "cdn.example.com");
+
+        // No headersSize recorded, so transfer falls back to the body alone
+        let transfer = collect_domains(&har, DomainSort::Count, SizeMode::Transfer);
+        assert_eq!(transfer[0].bytes, 100);
+
+        let decoded = collect_domains(&har, DomainSort::Count, SizeMode::Decoded);
+        assert_eq!(decoded[0].bytes, 300);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::commands::timing::Percentiles;
+use crate::har::Har;
+use crate::output::{extract_path, format_time, templatize_path, OutputFormat};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use crate::output::table::style_table;
+use anyhow::Result;
+use clap::Args;
+use std::collections::{BTreeMap, HashMap};
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct EndpointsCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Text that replaces numeric, UUID and hash-like path segments (e.g. :id)
+    #[arg(long, value_name = "TEXT", default_value = "{id}")]
+    pub id_placeholder: String,
+}
+
+/// A distinct method + templatized path pair
+#[derive(Debug, serde::Serialize)]
+pub struct Endpoint {
+    pub method: String,
+    pub path: String,
+    pub count: usize,
+    pub statuses: BTreeMap<i32, usize>,
+    /// 95th percentile of the total request time
+    pub p95_ms: f64,
+    #[serde(skip)]
+    times: Vec<f64>,
+}
+
+#[derive(Tabled)]
+struct EndpointRow {
+    #[tabled(rename = "Method")]
+    method: String,
+    #[tabled(rename = "Endpoint")]
+    path: String,
+    #[tabled(rename = "Calls")]
+    count: usize,
+    #[tabled(rename = "Statuses")]
+    statuses: String,
+    #[tabled(rename = "p95")]
+    p95: String,
+}
+
+impl EndpointsCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let endpoints = collect_endpoints(har, &self.id_placeholder);
+
+        match self.output {
+            OutputFormat::Json => print_json(&endpoints, true)?,
+            OutputFormat::Ndjson => print_ndjson(&endpoints)?,
+            OutputFormat::Yaml => print_yaml(&endpoints)?,
+            OutputFormat::Compact => {
+                for e in &endpoints {
+                    println!("{}\t{}\t{}\t{}\t{:.0}", e.method, e.path, e.count, format_statuses(&e.statuses), e.p95_ms);
+                }
+            }
+            OutputFormat::Table => {
+                if endpoints.is_empty() {
+                    println!("No entries found.");
+                    return Ok(());
+                }
+
+                let rows: Vec<EndpointRow> = endpoints
+                    .iter()
+                    .map(|e| EndpointRow {
+                        method: e.method.clone(),
+                        path: e.path.clone(),
+                        count: e.count,
+                        statuses: format_statuses(&e.statuses),
+                        p95: format_time(e.p95_ms),
+                    })
+                    .collect();
+
+                let mut table = Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Group entries by method and path with ID segments replaced by
+/// `placeholder`, most called first
+pub fn collect_endpoints(har: &Har, placeholder: &str) -> Vec<Endpoint> {
+    let mut groups: HashMap<(String, String), Endpoint> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let method = entry.request.method.to_uppercase();
+        let path = templatize_path(extract_path(&entry.request.url), placeholder);
+
+        let endpoint = groups
+            .entry((method.clone(), path.clone()))
+            .or_insert_with(|| Endpoint {
+                method,
+                path,
+                count: 0,
+                statuses: BTreeMap::new(),
+                p95_ms: 0.0,
+                times: Vec::new(),
+            });
+        endpoint.count += 1;
+        *endpoint.statuses.entry(entry.response.status).or_insert(0) += 1;
+        endpoint.times.push(entry.time);
+    }
+
+    let mut endpoints: Vec<Endpoint> = groups.into_values().collect();
+    for endpoint in &mut endpoints {
+        endpoint.p95_ms = Percentiles::from_times(&endpoint.times).map_or(0.0, |p| p.p95);
+    }
+    endpoints.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.method.cmp(&b.method))
+    });
+    endpoints
+}
+
+fn format_statuses(statuses: &BTreeMap<i32, usize>) -> String {
+    statuses
+        .iter()
+        .map(|(status, count)| format!("{} ({})", status, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn har_with_urls(urls: &[(&str, i32)]) -> Har {
+        fixtures::har(
+            urls.iter()
+                .map(|(url, status)| fixtures::entry_json("GET", url, *status))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_numeric_segments_collapse() {
+        let har = har_with_urls(&[
+            ("https://api.example.com/users/123", 200),
+            ("https://api.example.com/users/456?full=1", 404),
+        ]);
+        let endpoints = collect_endpoints(&har, "{id}");
+
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].path, "/users/{id}");
+        assert_eq!(endpoints[0].count, 2);
+        assert_eq!(endpoints[0].statuses.get(&200), Some(&1));
+        assert_eq!(endpoints[0].statuses.get(&404), Some(&1));
+    }
+
+    #[test]
+    fn test_uuid_segments_collapse() {
+        let har = har_with_urls(&[
+            ("https://example.com/orders/550e8400-e29b-41d4-a716-446655440000/items", 200),
+            ("https://example.com/orders/6BA7B810-9DAD-11D1-80B4-00C04FD430C8/items", 200),
+            ("https://example.com/orders/latest/items", 200),
+        ]);
+        let endpoints = collect_endpoints(&har, "{id}");
+
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].path, "/orders/{id}/items");
+        assert_eq!(endpoints[0].count, 2);
+        assert_eq!(endpoints[1].path, "/orders/latest/items");
+    }
+
+    #[test]
+    fn test_api_summary_placeholder_and_p95() {
+        let mut entries = Vec::new();
+        for (i, url) in [
+            "https://api.example.com/users/1/orders/550e8400-e29b-41d4-a716-446655440000",
+            "https://api.example.com/users/2/orders/9e107d9d372bb6826bd81d3542a419d6",
+            "https://api.example.com/users/3/orders/42",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let json = fixtures::entry_with("GET", url, if i == 2 { 500 } else { 200 }, |e| {
+                e["time"] = serde_json::json!(100.0 * (i + 1) as f64);
+            });
+            entries.push(json);
+        }
+        let endpoints = collect_endpoints(&fixtures::har(entries), ":id");
+
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].path, "/users/:id/orders/:id");
+        assert_eq!(endpoints[0].count, 3);
+        assert_eq!(endpoints[0].statuses, BTreeMap::from([(200, 2), (500, 1)]));
+        assert!((endpoints[0].p95_ms - 290.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::commands::count::{most_common, tallies};
+use crate::filter::eval::{Field, FilterExpr};
+use crate::har;
+use crate::output::OutputFormat;
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::ops::ControlFlow;
+
+#[derive(Debug, Args)]
+pub struct ExtractCmd {
+    /// Field to read (e.g., url, path, response.header("Location"))
+    #[arg()]
+    pub field: String,
+
+    /// Regex with one capture group; the captured text is printed (e.g. '/users/(\d+)')
+    #[arg()]
+    pub pattern: String,
+
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Only look at entries matching this filter expression
+    #[arg(long, value_name = "EXPR")]
+    pub filter: Option<String>,
+
+    /// Count each distinct captured value instead of listing entries
+    #[arg(long)]
+    pub distinct: bool,
+
+    /// Match the regex ignoring case
+    #[arg(short = 'i', long)]
+    pub ignore_case: bool,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+}
+
+/// A captured value and the entry it came from
+#[derive(Debug, Serialize)]
+pub struct Extracted {
+    pub index: usize,
+    pub value: String,
+}
+
+impl ExtractCmd {
+    pub fn run<R: Read>(&self, reader: R) -> Result<()> {
+        let extracted = self.extract(reader)?;
+
+        if self.distinct {
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for e in extracted {
+                *counts.entry(e.value).or_insert(0) += 1;
+            }
+            match self.output {
+                OutputFormat::Json => print_json(&counts, true)?,
+                OutputFormat::Ndjson => print_ndjson(tallies(&counts))?,
+                OutputFormat::Yaml => print_yaml(&counts)?,
+                OutputFormat::Table | OutputFormat::Compact => {
+                    for (value, count) in most_common(&counts) {
+                        println!("{}\t{}", value, count);
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        match self.output {
+            OutputFormat::Json => print_json(&extracted, true)?,
+            OutputFormat::Ndjson => print_ndjson(&extracted)?,
+            OutputFormat::Yaml => print_yaml(&extracted)?,
+            OutputFormat::Table | OutputFormat::Compact => {
+                for e in &extracted {
+                    println!("{}\t{}", e.index, e.value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Captured values in entry order; entries without the field or a match are skipped
+    fn extract<R: Read>(&self, reader: R) -> Result<Vec<Extracted>> {
+        let field = Field::parse(&self.field)?;
+        let regex = self.regex()?;
+        let filter = self.filter.as_deref().map(FilterExpr::parse).transpose()?;
+
+        let mut extracted = Vec::new();
+        let mut index = 0usize;
+        har::stream_entries(reader, |entry| {
+            index += 1;
+            if filter.as_ref().is_none_or(|f| f.matches(&entry)) {
+                let captured = field
+                    .get_string(&entry)
+                    .and_then(|text| regex.captures(&text).and_then(|c| c.get(1)).map(|m| m.as_str().to_string()));
+                if let Some(value) = captured {
+                    extracted.push(Extracted { index, value });
+                }
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(extracted)
+    }
+
+    fn regex(&self) -> Result<Regex> {
+        let regex = RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .with_context(|| format!("Invalid regex '{}'", self.pattern))?;
+        // captures_len counts the whole match as group 0
+        if regex.captures_len() != 2 {
+            bail!(
+                "Regex '{}' must have exactly one capture group, e.g. '/users/(\\d+)' (use (?:...) for other groups)",
+                self.pattern
+            );
+        }
+        Ok(regex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::parse_args;
+    use crate::har::fixtures;
+
+    fn har() -> Vec<u8> {
+        let entries = [
+            ("GET", "https://example.com/users/42"),
+            ("GET", "https://example.com/users/me"),
+            ("POST", "https://example.com/users/7/posts"),
+            ("GET", "https://example.com/about"),
+            ("GET", "https://example.com/users/42?tab=posts"),
+        ]
+        .into_iter()
+        .map(|(method, url)| fixtures::entry_json(method, url, 200))
+        .collect();
+        fixtures::har_bytes(entries)
+    }
+
+    fn extract(args: &[&str]) -> Result<Vec<(usize, String)>> {
+        let cmd = parse_args::<ExtractCmd>(args);
+        Ok(cmd.extract(har().as_slice())?.into_iter().map(|e| (e.index, e.value)).collect())
+    }
+
+    #[test]
+    fn test_extracts_ids_and_skips_non_matches() {
+        let ids = extract(&["url", r"/users/(\d+)"]).unwrap();
+        assert_eq!(ids, [(1, "42".into()), (3, "7".into()), (5, "42".into())]);
+
+        let ids = extract(&["path", r"/users/(\d+)", "--filter", "method == GET"]).unwrap();
+        assert_eq!(ids, [(1, "42".into()), (5, "42".into())]);
+
+        let segments = extract(&["path", r"^/(USERS|ABOUT)", "-i"]).unwrap();
+        assert_eq!(segments.len(), 5);
+        assert_eq!(segments[3], (4, "about".into()));
+    }
+
+    #[test]
+    fn test_regex_needs_one_capture_group() {
+        assert!(extract(&["url", r"/users/\d+"]).is_err());
+        assert!(extract(&["url", r"/(users)/(\d+)"]).is_err());
+        assert!(extract(&["url", r"/(?:users|people)/(\d+)"]).is_ok());
+        assert!(extract(&["url", r"/users/(\d+"]).is_err());
+        assert!(extract(&["nope", r"(x)"]).is_err());
+    }
+}
@@ -1,8 +1,12 @@
-use crate::har::{Har, Entry};
+use crate::commands::page::PageArgs;
+use crate::har::{Har, Entry, Header};
 use crate::filter::eval::FilterExpr;
-use crate::output::json::create_filtered_har;
-use anyhow::Result;
+use crate::output::extract_path;
+use crate::output::json::{write_entries_json, write_filtered_har};
+use anyhow::{Context, Result};
 use clap::Args;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
 
 #[derive(Debug, Args)]
 #[command(after_long_help = FILTER_HELP)]
@@ -18,40 +22,170 @@ pub struct FilterCmd {
     /// Output as valid HAR (default), otherwise output JSON array of entries
     #[arg(long)]
     pub entries_only: bool,
+
+    /// Write the result to a file instead of stdout
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Drop request and response body text, keeping the recorded sizes
+    #[arg(long)]
+    pub strip_bodies: bool,
+
+    /// Keep only these request and response headers (comma-separated, case-insensitive)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',', conflicts_with = "drop_headers")]
+    pub keep_headers: Vec<String>,
+
+    /// Remove these request and response headers (comma-separated, case-insensitive)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub drop_headers: Vec<String>,
+
+    /// Recompute headersSize from the headers left; otherwise the recorded size is kept
+    #[arg(long)]
+    pub recompute_sizes: bool,
+
+    /// Emit compact single-line JSON (the default when stdout is not a terminal)
+    #[arg(long, visible_alias = "compact")]
+    pub minify: bool,
+
+    /// Pretty-print JSON even when stdout is not a terminal
+    #[arg(long, visible_alias = "no-compact", conflicts_with = "minify")]
+    pub pretty: bool,
+
+    /// Print how the expression was parsed, fully parenthesized, and exit
+    /// without reading the HAR
+    #[arg(long)]
+    pub explain: bool,
+
+    #[command(flatten)]
+    pub page: PageArgs,
 }
 
 impl FilterCmd {
+    pub fn explain(&self) -> Result<()> {
+        println!("{}", FilterExpr::parse(&self.expr)?);
+        Ok(())
+    }
+
     pub fn run(&self, har: &Har) -> Result<()> {
         let filter = FilterExpr::parse(&self.expr)?;
+        let page = self.page.resolve(har)?;
 
         let matching_entries: Vec<(usize, &Entry)> = har.log.entries
             .iter()
             .enumerate()
-            .filter(|(_, e)| filter.matches(e))
+            .filter(|(_, e)| page.as_ref().is_none_or(|p| p.contains(e)) && filter.matches(e))
             .map(|(i, e)| (i + 1, e))
             .collect();
 
+        let rewritten: Vec<(usize, Entry)>;
+        let entries = if self.rewrites_entries() {
+            rewritten = matching_entries
+                .iter()
+                .map(|(i, e)| (*i, self.rewrite((*e).clone())))
+                .collect();
+            rewritten.iter().map(|(i, e)| (*i, e)).collect()
+        } else {
+            matching_entries
+        };
+
+        match self.output_file {
+            Some(ref path) => {
+                let file = File::create(path)
+                    .with_context(|| format!("Failed to create file: {}", path))?;
+                self.write(BufWriter::new(file), har, &entries, self.is_pretty(true))
+            }
+            None => {
+                let pretty = self.is_pretty(atty::is(atty::Stream::Stdout));
+                self.write(BufWriter::new(io::stdout().lock()), har, &entries, pretty)
+            }
+        }
+    }
+
+    fn rewrites_entries(&self) -> bool {
+        self.strip_bodies || !self.keep_headers.is_empty() || !self.drop_headers.is_empty() || self.recompute_sizes
+    }
+
+    /// Apply `--strip-bodies` and the header options to a matching entry
+    fn rewrite(&self, mut entry: Entry) -> Entry {
+        if self.strip_bodies {
+            entry = strip_bodies(entry);
+        }
+        let listed = |names: &[String], name: &str| names.iter().any(|n| n.trim().eq_ignore_ascii_case(name));
+        let removed = |name: &str| {
+            (!self.keep_headers.is_empty() && !listed(&self.keep_headers, name)) || listed(&self.drop_headers, name)
+        };
+        for headers in [&mut entry.request.headers, &mut entry.response.headers] {
+            headers.retain(|h| !removed(&h.name));
+        }
+        // The parsed cookie lists repeat the Cookie and Set-Cookie values
+        if removed("Cookie") {
+            entry.request.cookies.clear();
+        }
+        if removed("Set-Cookie") {
+            entry.response.cookies.clear();
+        }
+        if self.recompute_sizes {
+            let request = &mut entry.request;
+            let request_line = format!("{} {} {}", request.method, extract_path(&request.url), request.http_version);
+            request.headers_size = headers_size(request.headers_size, &request_line, &request.headers);
+            let response = &mut entry.response;
+            let status_line = format!("{} {} {}", response.http_version, response.status, response.status_text);
+            response.headers_size = headers_size(response.headers_size, &status_line, &response.headers);
+        }
+        entry
+    }
+
+    /// Whether to pretty-print; `by_default` applies when neither
+    /// `--minify` nor `--pretty` is given
+    fn is_pretty(&self, by_default: bool) -> bool {
+        !self.minify && (self.pretty || by_default)
+    }
+
+    fn write<W: Write>(&self, mut out: W, har: &Har, entries: &[(usize, &Entry)], pretty: bool) -> Result<()> {
         if self.entries_only {
-            let entries: Vec<&Entry> = matching_entries.iter().map(|(_, e)| *e).collect();
-            println!("{}", serde_json::to_string_pretty(&entries)?);
+            write_entries_json(&mut out, entries, pretty)?;
         } else {
             // Output as valid HAR
-            let filtered = create_filtered_har(har, &matching_entries);
-            println!("{}", serde_json::to_string_pretty(&filtered)?);
+            write_filtered_har(&mut out, har, entries, pretty)?;
         }
-
+        writeln!(out)?;
+        out.flush()?;
         Ok(())
     }
 }
 
+/// HTTP/1.x size of the start line and headers up to and including the blank
+/// line before the body; a size recorded as unknown (-1) stays unknown
+fn headers_size(recorded: i64, start_line: &str, headers: &[Header]) -> i64 {
+    if recorded < 0 {
+        return recorded;
+    }
+    let lines: usize = headers.iter().map(|h| h.name.len() + 2 + h.value.len() + 2).sum();
+    (start_line.len() + 2 + lines + 2) as i64
+}
+
+/// Clear body text from an entry; sizes and mime types are left as recorded
+fn strip_bodies(mut entry: Entry) -> Entry {
+    if let Some(ref mut post_data) = entry.request.post_data {
+        post_data.text = None;
+    }
+    entry.response.content.text = None;
+    entry.response.content.encoding = None;
+    entry
+}
+
 const FILTER_HELP: &str = r#"AVAILABLE FIELDS:
   Request:
     method              HTTP method (GET, POST, etc.)
     url                 Full request URL
     host, domain        Hostname from URL
     path                URL path (without query string)
+    path.segment(n)     nth non-empty path segment, from 1 ("users" is 2
+                        in /v1/users/123)
+    path.depth          Number of non-empty path segments
     scheme, protocol    URL scheme (http, https)
     query               Query string
+    query.param("name") Query parameter value (first if repeated, decoded)
 
   Response:
     status              HTTP status code (e.g., 200, 404, 503)
@@ -59,43 +193,250 @@ const FILTER_HELP: &str = r#"AVAILABLE FIELDS:
     contentType         Response content type
     contentSize         Response content size in bytes
     bodySize            Response body size
+    isFailed            Boolean: status 0 (no response) or >= 400
+    resourceType        Resource type (document, stylesheet, script, image,
+                        font, media, xhr, fetch, ...) from _resourceType,
+                        else guessed from the content type
+    isXhr               Boolean: resourceType is xhr or fetch
+    setCookieCount      Number of Set-Cookie response headers
+    maxAge              Cache lifetime in seconds from Cache-Control
+                        (max-age, else s-maxage; 0 for no-store/no-cache)
+    startedDateTime     Start time; compared chronologically with
+                        "2024-01-01T12:00:00Z" or "2024-01-01"
 
   Timing:
     time                Total request time in milliseconds
     blocked, dns, connect, ssl, send, wait, receive
+                        (all accept ms/s/m suffixes: time > 1.5s)
 
   GraphQL:
     isGraphQL           Boolean: is this a GraphQL request?
     operationName       GraphQL operation name
     operationType       GraphQL type (query/mutation/subscription)
     gql.query           Raw GraphQL query string
+    gql.variable("name")  GraphQL variable (dotted paths like "input.id")
 
   Headers:
     request.header("Name")   Request header value
-    response.header("Name")  Response header value
+    response.header("Name")  Response header value (repeated headers such as
+                             Set-Cookie match if any value matches)
+    request.headerMatch(/re/)   Boolean: any request header name matches
+    response.headerMatch(/re/)  Boolean: any response header name matches
 
 OPERATORS:
   ==, !=              Equality
   >, >=, <, <=        Comparison
+  in [a, b, ...]      Set membership; (a, b, ...) works too
+  not in [a, b, ...]  Not any of the values
+  field > otherField  Compare two fields (quote literals that are field names)
+  has(field)          Field is present (same as field != null)
   &&, ||              Logical AND/OR
   !                   Logical NOT
 
 STRING METHODS:
   .contains("str")    Contains substring
+  .icontains("str")   Contains substring, ignoring ASCII case
+  .iequals("str")     Equals, ignoring ASCII case
   .startsWith("str")  Starts with prefix
   .endsWith("str")    Ends with suffix
   .matches(/regex/)   Matches regular expression
+  .inDomain("a.com")  Host is a.com or a subdomain of it (not nota.com)
+  field ~= /regex/    Same as field.matches(/regex/)
+  field *= "str"      Same as field.contains("str")
 
 EXAMPLES:
   status == 200                           Successful requests
   status >= 400                           Error responses
   status != 200                           Non-200 responses
   method == "POST"                        POST requests only
+  status in [200, 201, 204]               Any of several statuses
+  status not in (301, 302)                Anything but these redirects
   host == "api.example.com"               Specific host
   url.contains("/api/")                   URLs containing /api/
-  time > 1000                             Slow requests (>1s)
+  time > 1s                               Slow requests (same as time > 1000)
+  bodySize > 1mb                          Large responses (b/kb/mb/gb on size fields)
+  responseHeadersSize > requestHeadersSize  Field against field
+  has(timings.dns)                        Entries with a DNS timing
+  isFailed                                No response or error status
+  resourceType == "script"                JavaScript loads
   isGraphQL && status >= 400              Failed GraphQL requests
   operationName.contains("User")          GraphQL ops with "User"
   request.header("Authorization") != ""   Authenticated requests
 
-NOTE: Use double quotes for expressions with != (shell escaping)"#;
+NOTE: Use double quotes for expressions with != (shell escaping). Use
+--explain to see how an expression groups."#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{parse_args, try_parse_args};
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_strip_bodies_keeps_sizes() {
+        let json = fixtures::entry_with("POST", "https://example.com/", 200, |e| {
+            e["request"]["postData"] = serde_json::json!({"mimeType": "application/json", "text": "{}"});
+            e["request"]["bodySize"] = serde_json::json!(2);
+            e["response"]["content"] = serde_json::json!({"size": 5, "mimeType": "text/plain", "text": "aGVsbG8=", "encoding": "base64"});
+            e["response"]["bodySize"] = serde_json::json!(5);
+        });
+        let entry = strip_bodies(serde_json::from_value(json).unwrap());
+
+        assert!(entry.request.post_data.as_ref().unwrap().text.is_none());
+        assert_eq!(entry.request.body_size, 2);
+        assert!(entry.response.content.text.is_none());
+        assert_eq!(entry.response.content.size, 5);
+        assert_eq!(entry.response.body_size, 5);
+        assert_eq!(entry.response.content.mime_type.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn test_minified_har_written_to_writer() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/ok", 200),
+            fixtures::entry_json("GET", "https://example.com/missing", 404),
+        ]);
+        let cmd = FilterCmd {
+            expr: "status >= 400".to_string(),
+            file: "-".to_string(),
+            entries_only: false,
+            output_file: None,
+            strip_bodies: false,
+            keep_headers: Vec::new(),
+            drop_headers: Vec::new(),
+            recompute_sizes: false,
+            minify: true,
+            pretty: false,
+            explain: false,
+            page: Default::default(),
+        };
+        let entries = [(2, &har.log.entries[1])];
+
+        let mut out = Vec::new();
+        cmd.write(&mut out, &har, &entries, cmd.is_pretty(true)).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert_eq!(text.lines().count(), 1);
+        let written: Har = serde_json::from_str(&text).unwrap();
+        assert_eq!(written.log.entries.len(), 1);
+        assert_eq!(written.log.entries[0].response.status, 404);
+    }
+
+    #[test]
+    fn test_explain_shows_grouping() {
+        let explain = |expr: &str| FilterExpr::parse(expr).unwrap().to_string();
+
+        assert_eq!(explain("isFailed && isXhr || isGraphQL"), "((isFailed && isXhr) || isGraphQL)");
+        assert_eq!(explain("isFailed || isXhr && isGraphQL"), "(isFailed || (isXhr && isGraphQL))");
+        assert_eq!(explain("isFailed && (isXhr || isGraphQL)"), "(isFailed && (isXhr || isGraphQL))");
+        assert_eq!(explain("!isFailed && status >= 400"), "(!isFailed && status >= 400)");
+        assert_eq!(
+            explain(r#"method == POST && url ~= /api/i || status not in (301, 302)"#),
+            r#"((method == "POST" && url.matches(/api/i)) || !(status in [301, 302]))"#
+        );
+        assert_eq!(
+            explain(r#"has(timings.dns) && response.header("Set-Cookie") *= "secure" && time > 1.5s"#),
+            r#"((has(timings.dns) && response.header("Set-Cookie").contains("secure")) && time > 1500)"#
+        );
+        assert_eq!(explain("response.bodySize > request.bodySize"), "response.bodySize > request.bodySize");
+
+        // The rendered form parses back to the same thing
+        for expr in [
+            "isFailed && isXhr || isGraphQL",
+            r#"host.inDomain("example.com") || !(status == 200)"#,
+            r#"request.header("x") == 'say "hi"' && url.contains('q="a"')"#,
+        ] {
+            assert_eq!(explain(&explain(expr)), explain(expr));
+        }
+
+        // Strings holding a double quote are shown in single quotes
+        assert_eq!(
+            explain(r#"request.header("x") == 'say "hi"'"#),
+            r#"request.header("x") == 'say "hi"'"#
+        );
+        assert_eq!(explain(r#"url.endsWith('"')"#), r#"url.endsWith('"')"#);
+    }
+
+    #[test]
+    fn test_keep_and_drop_headers() {
+        let json = fixtures::entry_with("GET", "https://example.com/a?b=1", 200, |e| {
+            e["request"]["headers"] = serde_json::json!([
+                {"name": "Accept", "value": "*/*"},
+                {"name": "Cookie", "value": "session=secret"},
+                {"name": "User-Agent", "value": "test"}
+            ]);
+            e["request"]["headersSize"] = serde_json::json!(500);
+            e["response"]["headers"] = serde_json::json!([
+                {"name": "content-type", "value": "text/html"},
+                {"name": "Set-Cookie", "value": "session=secret"}
+            ]);
+            e["response"]["headersSize"] = serde_json::json!(-1);
+            e["request"]["cookies"] = serde_json::json!([{"name": "session", "value": "secret"}]);
+            e["response"]["cookies"] = serde_json::json!([{"name": "session", "value": "secret"}]);
+        });
+        let entry = fixtures::to_entry(json);
+
+        let rewrite = |args: &[&str]| {
+            let cmd = parse_args::<FilterCmd>(&[&["status == 200"], args].concat());
+            cmd.rewrite(entry.clone())
+        };
+        let names = |headers: &[Header]| headers.iter().map(|h| h.name.clone()).collect::<Vec<_>>();
+
+        let kept = rewrite(&["--keep-headers", "accept,Content-Type"]);
+        assert_eq!(names(&kept.request.headers), ["Accept"]);
+        assert_eq!(names(&kept.response.headers), ["content-type"]);
+        assert_eq!(kept.request.headers_size, 500, "sizes are kept unless asked");
+        assert!(kept.request.cookies.is_empty() && kept.response.cookies.is_empty());
+
+        let dropped = rewrite(&["--drop-headers", "cookie", "--drop-headers", "set-cookie"]);
+        assert_eq!(names(&dropped.request.headers), ["Accept", "User-Agent"]);
+        assert_eq!(names(&dropped.response.headers), ["content-type"]);
+        assert!(dropped.request.cookies.is_empty() && dropped.response.cookies.is_empty());
+        assert!(!serde_json::to_string(&dropped).unwrap().contains("secret"));
+
+        let only_request = rewrite(&["--drop-headers", "Cookie"]);
+        assert!(only_request.request.cookies.is_empty());
+        assert_eq!(only_request.response.cookies.len(), 1);
+
+        let with_cookies = rewrite(&["--keep-headers", "cookie,set-cookie"]);
+        assert_eq!((with_cookies.request.cookies.len(), with_cookies.response.cookies.len()), (1, 1));
+
+        let recomputed = rewrite(&["--keep-headers", "Accept", "--recompute-sizes"]);
+        // "GET /a?b=1 HTTP/1.1\r\n" + "Accept: */*\r\n" + "\r\n"
+        assert_eq!(recomputed.request.headers_size, 21 + 13 + 2);
+        assert_eq!(recomputed.response.headers_size, -1);
+
+        assert!(try_parse_args::<FilterCmd>(&["x", "--keep-headers", "a", "--drop-headers", "b"]).is_err());
+    }
+
+    #[test]
+    fn test_compact_is_smaller_than_pretty() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/a", 200),
+            fixtures::entry_json("GET", "https://example.com/b", 200),
+        ]);
+        let entries: Vec<(usize, &Entry)> = har.log.entries.iter().enumerate().map(|(i, e)| (i + 1, e)).collect();
+
+        for entries_only in [false, true] {
+            let extra: &[&str] = if entries_only { &["--entries-only"] } else { &[] };
+            let output = |args: &[&str], to_terminal: bool| {
+                let cmd = parse_args::<FilterCmd>(&[&["status == 200"], extra, args].concat());
+                let mut out = Vec::new();
+                cmd.write(&mut out, &har, &entries, cmd.is_pretty(to_terminal)).unwrap();
+                out
+            };
+
+            let pretty = output(&[], true);
+            let compact = output(&["--compact"], true);
+            assert!(compact.len() < pretty.len(), "{} >= {}", compact.len(), pretty.len());
+            assert_eq!(compact.iter().filter(|&&b| b == b'\n').count(), 1);
+            let parse = |bytes: &[u8]| serde_json::from_slice::<serde_json::Value>(bytes).unwrap();
+            assert_eq!(parse(&compact), parse(&pretty));
+
+            // Piped output is compact unless --pretty asks otherwise
+            assert_eq!(output(&[], false), compact);
+            assert_eq!(output(&["--pretty"], false), pretty);
+        }
+        assert!(try_parse_args::<FilterCmd>(&["x", "--compact", "--pretty"]).is_err());
+    }
+}
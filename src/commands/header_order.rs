@@ -0,0 +1,87 @@
+use crate::har::Header;
+use clap::Args;
+
+/// `--no-pseudo`/`--sort` options for commands that list headers
+#[derive(Debug, Clone, Default, Args)]
+pub struct HeaderOrderArgs {
+    /// Hide HTTP/2 and HTTP/3 pseudo-headers (`:method`, `:path`, `:authority`, ...)
+    #[arg(long)]
+    pub no_pseudo: bool,
+
+    /// List pseudo-headers first, then the rest sorted by name (case-insensitive)
+    #[arg(long)]
+    pub sort: bool,
+}
+
+impl HeaderOrderArgs {
+    /// Headers to show, in original order unless `--sort` is given
+    pub fn arrange<'a>(&self, headers: &'a [Header]) -> Vec<&'a Header> {
+        let mut shown: Vec<&Header> = headers
+            .iter()
+            .filter(|h| !(self.no_pseudo && h.is_pseudo()))
+            .collect();
+        if self.sort {
+            // Stable, so pseudo-headers and same-named headers keep their order
+            shown.sort_by(|a, b| match (a.is_pseudo(), b.is_pseudo()) {
+                (true, true) => std::cmp::Ordering::Equal,
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => a.name.to_ascii_lowercase().cmp(&b.name.to_ascii_lowercase()),
+            });
+        }
+        shown
+    }
+
+    /// Owned copy of `arrange`, for rewriting an entry before display
+    pub fn arranged(&self, headers: &[Header]) -> Vec<Header> {
+        self.arrange(headers).into_iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> Vec<Header> {
+        [
+            ("user-agent", "test"),
+            (":method", "GET"),
+            ("Accept", "*/*"),
+            (":path", "/a"),
+            ("cookie", "a=1"),
+            (":authority", "example.com"),
+            ("Cookie", "b=2"),
+            (":scheme", "https"),
+        ]
+        .into_iter()
+        .map(|(name, value)| Header { name: name.into(), value: value.into(), comment: None })
+        .collect()
+    }
+
+    fn names(args: HeaderOrderArgs) -> Vec<String> {
+        args.arrange(&headers()).into_iter().map(|h| format!("{}={}", h.name, h.value)).collect()
+    }
+
+    #[test]
+    fn test_default_keeps_original_order() {
+        let all: Vec<String> = headers().iter().map(|h| format!("{}={}", h.name, h.value)).collect();
+        assert_eq!(names(HeaderOrderArgs::default()), all);
+    }
+
+    #[test]
+    fn test_sort_groups_pseudo_headers_first() {
+        let args = HeaderOrderArgs { sort: true, ..Default::default() };
+        assert_eq!(names(args), [
+            ":method=GET", ":path=/a", ":authority=example.com", ":scheme=https",
+            "Accept=*/*", "cookie=a=1", "Cookie=b=2", "user-agent=test",
+        ]);
+    }
+
+    #[test]
+    fn test_no_pseudo_hides_them() {
+        let args = HeaderOrderArgs { no_pseudo: true, ..Default::default() };
+        assert_eq!(names(args), ["user-agent=test", "Accept=*/*", "cookie=a=1", "Cookie=b=2"]);
+        let args = HeaderOrderArgs { no_pseudo: true, sort: true };
+        assert_eq!(names(args), ["Accept=*/*", "cookie=a=1", "Cookie=b=2", "user-agent=test"]);
+    }
+}
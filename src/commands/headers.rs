@@ -1,8 +1,13 @@
-use crate::har::Har;
+use crate::commands::header_order::HeaderOrderArgs;
+use crate::har::{duplicate_headers, Entry, Har, Header};
 use crate::output::OutputFormat;
+use crate::output::json::{print_json, print_ndjson, write_json};
+use crate::output::yaml::print_yaml;
 use anyhow::{Result, bail};
 use clap::Args;
 use colored::Colorize;
+use serde::Serialize;
+use std::io::{self, BufWriter, Write};
 
 #[derive(Debug, Args)]
 pub struct HeadersCmd {
@@ -29,6 +34,34 @@ pub struct HeadersCmd {
     /// Filter headers by name (case-insensitive contains)
     #[arg(short = 'f', long)]
     pub filter: Option<String>,
+
+    /// Only show headers that should appear once but are repeated (e.g.
+    /// Content-Length); with "all", skip entries without any
+    #[arg(long)]
+    pub duplicates: bool,
+
+    /// Print bare `Name: Value` lines, request headers first, with no banner
+    /// or blank lines; with "all", each line starts with the entry index and a tab
+    #[arg(long, visible_aliases = ["raw", "no-table"], conflicts_with = "output")]
+    pub plain: bool,
+
+    #[command(flatten)]
+    pub order: HeaderOrderArgs,
+}
+
+#[derive(Serialize)]
+struct EntryHeaders<'a> {
+    index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request: Option<Vec<HeaderPair<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response: Option<Vec<HeaderPair<'a>>>,
+}
+
+#[derive(Serialize)]
+struct HeaderPair<'a> {
+    name: &'a str,
+    value: &'a str,
 }
 
 impl HeadersCmd {
@@ -49,62 +82,24 @@ impl HeadersCmd {
         }
 
         let entry = &har.log.entries[idx - 1];
+        if self.plain {
+            let mut out = BufWriter::new(io::stdout().lock());
+            self.write_plain(&mut out, None, entry)?;
+            out.flush()?;
+            return Ok(());
+        }
         self.show_entry_headers(idx, entry, color)
     }
 
-    fn show_entry_headers(&self, index: usize, entry: &crate::har::Entry, color: bool) -> Result<()> {
-        let show_request = self.request || (!self.request && !self.response);
-        let show_response = self.response || (!self.request && !self.response);
+    fn show_entry_headers(&self, index: usize, entry: &Entry, color: bool) -> Result<()> {
+        let (show_request, show_response) = self.sections();
 
         match self.output {
             OutputFormat::Json => {
-                #[derive(serde::Serialize)]
-                struct Headers {
-                    #[serde(skip_serializing_if = "Option::is_none")]
-                    request: Option<Vec<HeaderPair>>,
-                    #[serde(skip_serializing_if = "Option::is_none")]
-                    response: Option<Vec<HeaderPair>>,
-                }
-
-                #[derive(serde::Serialize)]
-                struct HeaderPair {
-                    name: String,
-                    value: String,
-                }
-
-                let filter_fn = |h: &crate::har::Header| -> bool {
-                    self.filter.as_ref().map_or(true, |f| {
-                        h.name.to_lowercase().contains(&f.to_lowercase())
-                    })
-                };
-
-                let headers = Headers {
-                    request: if show_request {
-                        Some(entry.request.headers.iter()
-                            .filter(|h| filter_fn(h))
-                            .map(|h| HeaderPair {
-                                name: h.name.clone(),
-                                value: h.value.clone(),
-                            })
-                            .collect())
-                    } else {
-                        None
-                    },
-                    response: if show_response {
-                        Some(entry.response.headers.iter()
-                            .filter(|h| filter_fn(h))
-                            .map(|h| HeaderPair {
-                                name: h.name.clone(),
-                                value: h.value.clone(),
-                            })
-                            .collect())
-                    } else {
-                        None
-                    },
-                };
-
-                println!("{}", serde_json::to_string_pretty(&headers)?);
+                print_json(&self.entry_headers(index, entry), true)?;
             }
+            OutputFormat::Ndjson => print_ndjson([self.entry_headers(index, entry)])?,
+            OutputFormat::Yaml => print_yaml(&self.entry_headers(index, entry))?,
             _ => {
                 let label = |s: &str| {
                     if color {
@@ -120,26 +115,22 @@ impl HeadersCmd {
 
                 if show_request {
                     println!("{}", label("Request Headers:"));
-                    for h in &entry.request.headers {
-                        if self.matches_filter(&h.name) {
-                            println!("  {}: {}",
-                                if color { h.name.cyan().to_string() } else { h.name.clone() },
-                                h.value
-                            );
-                        }
+                    for h in self.shown(&entry.request.headers) {
+                        println!("  {}: {}",
+                            if color { h.name.cyan().to_string() } else { h.name.clone() },
+                            h.value
+                        );
                     }
                     println!();
                 }
 
                 if show_response {
                     println!("{}", label("Response Headers:"));
-                    for h in &entry.response.headers {
-                        if self.matches_filter(&h.name) {
-                            println!("  {}: {}",
-                                if color { h.name.cyan().to_string() } else { h.name.clone() },
-                                h.value
-                            );
-                        }
+                    for h in self.shown(&entry.response.headers) {
+                        println!("  {}: {}",
+                            if color { h.name.cyan().to_string() } else { h.name.clone() },
+                            h.value
+                        );
                     }
                 }
             }
@@ -149,16 +140,200 @@ impl HeadersCmd {
     }
 
     fn show_all_headers(&self, har: &Har, color: bool) -> Result<()> {
-        for (i, entry) in har.log.entries.iter().enumerate() {
-            self.show_entry_headers(i + 1, entry, color)?;
+        if self.plain {
+            let mut out = BufWriter::new(io::stdout().lock());
+            for (i, entry) in self.entries(har) {
+                self.write_plain(&mut out, Some(i), entry)?;
+            }
+            out.flush()?;
+            return Ok(());
+        }
+
+        match self.output {
+            OutputFormat::Json => {
+                let mut out = BufWriter::new(io::stdout().lock());
+                self.write_all_json(&mut out, har)?;
+                writeln!(out)?;
+                out.flush()?;
+                return Ok(());
+            }
+            OutputFormat::Ndjson => {
+                return print_ndjson(self.entries(har).map(|(i, e)| self.entry_headers(i, e)));
+            }
+            OutputFormat::Yaml => return print_yaml(&self.all_headers(har)),
+            _ => {}
+        }
+
+        for (i, entry) in self.entries(har) {
+            self.show_entry_headers(i, entry, color)?;
             println!();
         }
         Ok(())
     }
 
+    /// `--plain` lines for one entry, prefixed with `index` and a tab if given
+    fn write_plain<W: Write>(&self, out: &mut W, index: Option<usize>, entry: &Entry) -> io::Result<()> {
+        let (show_request, show_response) = self.sections();
+        let sections = [(show_request, &entry.request.headers), (show_response, &entry.response.headers)];
+        for (_, headers) in sections.into_iter().filter(|(show, _)| *show) {
+            for h in self.shown(headers) {
+                if let Some(i) = index {
+                    write!(out, "{}\t", i)?;
+                }
+                writeln!(out, "{}: {}", h.name, h.value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries shown by "all", with their 1-based index
+    fn entries<'s, 'a: 's>(&'s self, har: &'a Har) -> impl Iterator<Item = (usize, &'a Entry)> + 's {
+        let (show_request, show_response) = self.sections();
+        har.log.entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (i + 1, e))
+            .filter(move |(_, e)| {
+                !self.duplicates
+                    || (show_request && !self.shown(&e.request.headers).is_empty())
+                    || (show_response && !self.shown(&e.response.headers).is_empty())
+            })
+    }
+
+    /// Write every entry's headers as a single JSON array
+    fn write_all_json<W: Write>(&self, writer: W, har: &Har) -> Result<()> {
+        write_json(writer, &self.all_headers(har), true)
+    }
+
+    fn all_headers<'a>(&self, har: &'a Har) -> Vec<EntryHeaders<'a>> {
+        self.entries(har)
+            .map(|(i, e)| self.entry_headers(i, e))
+            .collect()
+    }
+
+    fn entry_headers<'a>(&self, index: usize, entry: &'a Entry) -> EntryHeaders<'a> {
+        let (show_request, show_response) = self.sections();
+        let pairs = |headers: &'a [Header]| -> Vec<HeaderPair<'a>> {
+            self.shown(headers)
+                .into_iter()
+                .map(|h| HeaderPair { name: &h.name, value: &h.value })
+                .collect()
+        };
+
+        EntryHeaders {
+            index,
+            request: show_request.then(|| pairs(&entry.request.headers)),
+            response: show_response.then(|| pairs(&entry.response.headers)),
+        }
+    }
+
+    /// Which of (request, response) headers to show; both unless one is asked for
+    fn sections(&self) -> (bool, bool) {
+        (self.request || !self.response, self.response || !self.request)
+    }
+
+    /// Headers passing `--filter` and, if given, `--duplicates`, ordered by
+    /// `--no-pseudo`/`--sort`
+    fn shown<'a>(&self, headers: &'a [Header]) -> Vec<&'a Header> {
+        let duplicates = if self.duplicates { duplicate_headers(headers) } else { Vec::new() };
+        self.order
+            .arrange(headers)
+            .into_iter()
+            .filter(|h| self.matches_filter(&h.name))
+            .filter(|h| !self.duplicates || duplicates.contains(&h.name.to_ascii_lowercase()))
+            .collect()
+    }
+
     fn matches_filter(&self, name: &str) -> bool {
-        self.filter.as_ref().map_or(true, |f| {
+        self.filter.as_ref().is_none_or(|f| {
             name.to_lowercase().contains(&f.to_lowercase())
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{parse_args, try_parse_args};
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_all_json_is_single_array() {
+        let first = fixtures::entry_with("GET", "https://example.com/a", 200, |e| {
+            e["request"]["headers"] = serde_json::json!([{"name": "Accept", "value": "*/*"}]);
+        });
+        let second = fixtures::entry_json("POST", "https://example.com/b", 201);
+        let har = fixtures::har(vec![first, second]);
+
+        let cmd = parse_args::<HeadersCmd>(&["all", "-o", "json", "--request"]);
+        let mut out = Vec::new();
+        cmd.write_all_json(&mut out, &har).unwrap();
+
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let entries = value.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["index"], 1);
+        assert_eq!(entries[0]["request"][0]["name"], "Accept");
+        assert!(entries[0].get("response").is_none());
+        assert_eq!(entries[1]["index"], 2);
+    }
+
+    #[test]
+    fn test_duplicates_lists_only_repeated_singletons() {
+        let dup = fixtures::entry_with("GET", "https://example.com/a", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                {"name": "Content-Length", "value": "10"},
+                {"name": "Content-Type", "value": "text/plain"},
+                {"name": "CONTENT-LENGTH", "value": "10"}
+            ]);
+        });
+        let cookies = fixtures::entry_with("GET", "https://example.com/b", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                {"name": "Set-Cookie", "value": "a=1"},
+                {"name": "Set-Cookie", "value": "b=2"}
+            ]);
+        });
+        let har = fixtures::har(vec![cookies, dup]);
+
+        let cmd = parse_args::<HeadersCmd>(&["all", "--duplicates"]);
+        let all = serde_json::to_value(cmd.all_headers(&har)).unwrap();
+        let entries = all.as_array().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["index"], 2);
+        assert_eq!(entries[0]["request"], serde_json::json!([]));
+        let names: Vec<&str> = entries[0]["response"].as_array().unwrap().iter().map(|h| h["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["Content-Length", "CONTENT-LENGTH"]);
+
+        // Request headers alone have no duplicates here
+        let cmd = parse_args::<HeadersCmd>(&["all", "--duplicates", "--request"]);
+        assert!(cmd.all_headers(&har).is_empty());
+    }
+
+    #[test]
+    fn test_plain_output_shape() {
+        let first = fixtures::entry_with("GET", "https://example.com/a", 200, |e| {
+            e["request"]["headers"] = serde_json::json!([
+                {"name": "Accept", "value": "*/*"},
+                {"name": "User-Agent", "value": "test"}
+            ]);
+            e["response"]["headers"] = serde_json::json!([{"name": "Content-Type", "value": "text/html"}]);
+        });
+        let second = fixtures::entry_with("GET", "https://example.com/b", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([{"name": "Content-Type", "value": "application/json"}]);
+        });
+        let har = fixtures::har(vec![first, second]);
+
+        let plain = |args: &[&str], index: Option<usize>, entry: &Entry| {
+            let cmd = parse_args::<HeadersCmd>(&[&["1", "--plain"], args].concat());
+            let mut out = Vec::new();
+            cmd.write_plain(&mut out, index, entry).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let first = &har.log.entries[0];
+        assert_eq!(plain(&[], None, first), "Accept: */*\nUser-Agent: test\nContent-Type: text/html\n");
+        assert_eq!(plain(&["--response"], None, first), "Content-Type: text/html\n");
+        assert_eq!(plain(&["-f", "type"], Some(2), &har.log.entries[1]), "2\tContent-Type: application/json\n");
+        assert!(try_parse_args::<HeadersCmd>(&["1", "--plain", "-o", "json"]).is_err());
+    }
+}
@@ -1,8 +1,13 @@
-use crate::har::Har;
-use crate::output::OutputFormat;
+use crate::har::{normalize_http_version, Har};
+use crate::output::{format_bytes, format_time, separator, OutputFormat};
+use crate::output::json::print_json;
+use crate::output::yaml::print_yaml;
+use crate::output::table::{colorize_status, status_label};
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Args)]
 pub struct InfoCmd {
@@ -18,7 +23,8 @@ pub struct InfoCmd {
 impl InfoCmd {
     pub fn run(&self, har: &Har, color: bool) -> Result<()> {
         match self.output {
-            OutputFormat::Json => self.print_json(har),
+            OutputFormat::Json => print_json(&collect_info(har), true),
+            OutputFormat::Yaml => print_yaml(&collect_info(har)),
             _ => self.print_table(har, color),
         }
     }
@@ -33,7 +39,7 @@ impl InfoCmd {
         };
 
         println!("{}", label("HAR File Information"));
-        println!("{}", "─".repeat(40));
+        println!("{}", separator(40));
 
         // Version
         println!("{}: {}", label("Version"), har.log.version);
@@ -67,6 +73,7 @@ impl InfoCmd {
 
         // Entries summary
         println!("{}: {}", label("Entries"), har.log.entries.len());
+        println!("{}: {}", label("Failed"), har.log.entries.iter().filter(|e| e.is_failed()).count());
 
         // Method breakdown
         let mut methods: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
@@ -77,7 +84,7 @@ impl InfoCmd {
         if !methods.is_empty() {
             println!("{}: ", label("Methods"));
             let mut methods: Vec<_> = methods.into_iter().collect();
-            methods.sort_by(|a, b| b.1.cmp(&a.1));
+            methods.sort_by_key(|m| std::cmp::Reverse(m.1));
             for (method, count) in methods {
                 println!("  {}: {}", method, count);
             }
@@ -92,84 +99,224 @@ impl InfoCmd {
         if !statuses.is_empty() {
             println!("{}: ", label("Status Codes"));
             let mut statuses: Vec<_> = statuses.into_iter().collect();
-            statuses.sort_by(|a, b| a.0.cmp(&b.0));
+            statuses.sort_by_key(|a| a.0);
             for (status, count) in statuses {
                 let status_str = if color {
-                    match status {
-                        200..=299 => status.to_string().green().to_string(),
-                        300..=399 => status.to_string().yellow().to_string(),
-                        400..=599 => status.to_string().red().to_string(),
-                        _ => status.to_string(),
-                    }
+                    colorize_status(status)
                 } else {
-                    status.to_string()
+                    status_label(status)
                 };
                 println!("  {}: {}", status_str, count);
             }
         }
 
-        // Timing summary
+        // HTTP version breakdown
+        let versions = http_versions(har);
         if !har.log.entries.is_empty() {
-            let times: Vec<f64> = har.log.entries.iter().map(|e| e.time).collect();
-            let total: f64 = times.iter().sum();
-            let avg = total / times.len() as f64;
-            let max = times.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-            let min = times.iter().cloned().fold(f64::INFINITY, f64::min);
+            println!("{}: ", label("HTTP Versions"));
+            for (side, counts) in [("Request", &versions.request), ("Response", &versions.response)] {
+                println!("  {}:", side);
+                let mut counts: Vec<_> = counts.iter().collect();
+                counts.sort_by_key(|c| std::cmp::Reverse(*c.1));
+                for (version, count) in counts {
+                    println!("    {}: {}", version, count);
+                }
+            }
+        }
 
+        // Timing summary
+        if let Some(timing) = timing_summary(har) {
             println!("{}: ", label("Timing"));
-            println!("  Total: {:.0}ms", total);
-            println!("  Average: {:.0}ms", avg);
-            println!("  Min: {:.0}ms, Max: {:.0}ms", min, max);
+            println!("  Total: {}", format_time(timing.total));
+            println!("  Average: {}", format_time(timing.average));
+            println!("  Min: {}, Max: {}", format_time(timing.min), format_time(timing.max));
+        }
+
+        // Size totals
+        if !har.log.entries.is_empty() {
+            let (transfer, decoded) = size_totals(har);
+            println!("{}: ", label("Size"));
+            println!("  Transferred: {}", format_bytes(transfer));
+            println!("  Decoded: {}", format_bytes(decoded));
         }
 
         Ok(())
     }
+}
 
-    fn print_json(&self, har: &Har) -> Result<()> {
-        #[derive(serde::Serialize)]
-        struct Info {
-            version: String,
-            creator: CreatorInfo,
-            browser: Option<CreatorInfo>,
-            pages_count: usize,
-            entries_count: usize,
-            methods: std::collections::HashMap<String, usize>,
-            status_codes: std::collections::HashMap<i32, usize>,
-        }
+/// Summary printed by `info` in structured formats
+#[derive(Debug, Serialize)]
+pub struct Info {
+    pub version: String,
+    pub creator: CreatorInfo,
+    pub browser: Option<CreatorInfo>,
+    pub pages_count: usize,
+    pub entries_count: usize,
+    pub failed_count: usize,
+    // BTreeMaps keep the keys sorted so output is deterministic
+    pub methods: BTreeMap<String, usize>,
+    pub status_codes: BTreeMap<i32, usize>,
+    pub http_versions: HttpVersions,
+    pub transfer_bytes: i64,
+    pub decoded_bytes: i64,
+}
 
-        #[derive(serde::Serialize)]
-        struct CreatorInfo {
-            name: String,
-            version: String,
-        }
+/// Entries per normalized `httpVersion`, for requests and responses
+#[derive(Debug, Default, Serialize)]
+pub struct HttpVersions {
+    pub request: BTreeMap<String, usize>,
+    pub response: BTreeMap<String, usize>,
+}
 
-        let mut methods: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-        for entry in &har.log.entries {
-            *methods.entry(entry.request.method.clone()).or_insert(0) += 1;
-        }
+#[derive(Debug, Serialize)]
+pub struct CreatorInfo {
+    pub name: String,
+    pub version: String,
+}
 
-        let mut status_codes: std::collections::HashMap<i32, usize> = std::collections::HashMap::new();
-        for entry in &har.log.entries {
-            *status_codes.entry(entry.response.status).or_insert(0) += 1;
-        }
+pub fn collect_info(har: &Har) -> Info {
+    let mut methods: BTreeMap<String, usize> = BTreeMap::new();
+    let mut status_codes: BTreeMap<i32, usize> = BTreeMap::new();
+    for entry in &har.log.entries {
+        *methods.entry(entry.request.method.clone()).or_insert(0) += 1;
+        *status_codes.entry(entry.response.status).or_insert(0) += 1;
+    }
 
-        let info = Info {
-            version: har.log.version.clone(),
-            creator: CreatorInfo {
-                name: har.log.creator.name.clone(),
-                version: har.log.creator.version.clone(),
-            },
-            browser: har.log.browser.as_ref().map(|b| CreatorInfo {
-                name: b.name.clone(),
-                version: b.version.clone(),
-            }),
-            pages_count: har.log.pages.as_ref().map(|p| p.len()).unwrap_or(0),
-            entries_count: har.log.entries.len(),
-            methods,
-            status_codes,
-        };
+    let (transfer_bytes, decoded_bytes) = size_totals(har);
+    Info {
+        version: har.log.version.clone(),
+        creator: CreatorInfo {
+            name: har.log.creator.name.clone(),
+            version: har.log.creator.version.clone(),
+        },
+        browser: har.log.browser.as_ref().map(|b| CreatorInfo {
+            name: b.name.clone(),
+            version: b.version.clone(),
+        }),
+        pages_count: har.log.pages.as_ref().map(|p| p.len()).unwrap_or(0),
+        entries_count: har.log.entries.len(),
+        failed_count: har.log.entries.iter().filter(|e| e.is_failed()).count(),
+        methods,
+        status_codes,
+        http_versions: http_versions(har),
+        transfer_bytes,
+        decoded_bytes,
+    }
+}
 
-        println!("{}", serde_json::to_string_pretty(&info)?);
-        Ok(())
+fn http_versions(har: &Har) -> HttpVersions {
+    let mut versions = HttpVersions::default();
+    for entry in &har.log.entries {
+        *versions.request.entry(normalize_http_version(&entry.request.http_version)).or_insert(0) += 1;
+        *versions.response.entry(normalize_http_version(&entry.response.http_version)).or_insert(0) += 1;
+    }
+    versions
+}
+
+/// Total, average and range of entry times in milliseconds
+#[derive(Debug, PartialEq)]
+struct TimingSummary {
+    total: f64,
+    average: f64,
+    min: f64,
+    max: f64,
+}
+
+/// Summarize entry times, skipping negative (unknown) ones; None when none are left
+fn timing_summary(har: &Har) -> Option<TimingSummary> {
+    let times: Vec<f64> = har.log.entries.iter().map(|e| e.time).filter(|&t| t >= 0.0).collect();
+    if times.is_empty() {
+        return None;
+    }
+
+    let total: f64 = times.iter().sum();
+    Some(TimingSummary {
+        total,
+        average: total / times.len() as f64,
+        min: times.iter().copied().fold(f64::INFINITY, f64::min),
+        max: times.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    })
+}
+
+/// Response bytes on the wire and after decompression, skipping unknown sizes
+fn size_totals(har: &Har) -> (i64, i64) {
+    har.log.entries.iter().fold((0, 0), |(transfer, decoded), e| {
+        (
+            transfer + e.transfer_size().unwrap_or(0),
+            decoded + e.content_decoded_size().unwrap_or(0),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn timed(times: &[f64]) -> Har {
+        fixtures::har(times
+            .iter()
+            .map(|&t| {
+                fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+                    e["time"] = serde_json::json!(t);
+                })
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_timing_summary_empty_and_single() {
+        assert_eq!(timing_summary(&timed(&[])), None);
+        assert_eq!(timing_summary(&timed(&[-1.0])), None);
+
+        let single = timing_summary(&timed(&[1500.0])).unwrap();
+        assert_eq!(single, TimingSummary { total: 1500.0, average: 1500.0, min: 1500.0, max: 1500.0 });
+        assert_eq!(format_time(single.total), "1.50s");
+    }
+
+    #[test]
+    fn test_timing_summary_skips_negative_times() {
+        let summary = timing_summary(&timed(&[0.0, -1.0, 100.0, 2500.0])).unwrap();
+        assert_eq!(summary.total, 2600.0);
+        assert!((summary.average - 2600.0 / 3.0).abs() < 1e-9);
+        assert_eq!((summary.min, summary.max), (0.0, 2500.0));
+    }
+
+    #[test]
+    fn test_http_versions_normalized() {
+        let entries = [("HTTP/1.1", "http/1.1"), ("h2", "HTTP/2"), ("http/2.0", "h2"), ("h3", "h3-29"), ("", "spdy/3.1")]
+            .iter()
+            .map(|(request, response)| {
+                fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+                    e["request"]["httpVersion"] = serde_json::json!(request);
+                    e["response"]["httpVersion"] = serde_json::json!(response);
+                })
+            })
+            .collect();
+        let info = collect_info(&fixtures::har(entries));
+
+        let json = serde_json::to_value(&info.http_versions).unwrap();
+        assert_eq!(json["request"], serde_json::json!({"HTTP/1.1": 1, "HTTP/2": 2, "HTTP/3": 1, "unknown": 1}));
+        assert_eq!(json["response"], serde_json::json!({"HTTP/1.1": 1, "HTTP/2": 2, "HTTP/3": 1, "spdy/3.1": 1}));
+    }
+
+    #[test]
+    fn test_info_yaml_round_trip() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("POST", "https://example.com/a", 500),
+            fixtures::entry_json("GET", "https://example.com/b", 200),
+            fixtures::entry_json("GET", "https://example.com/c", 404),
+        ]);
+        let info = collect_info(&har);
+
+        let yaml = serde_yaml_ng::to_string(&info).unwrap();
+        let parsed: serde_json::Value = serde_yaml_ng::from_str(&yaml).unwrap();
+        assert_eq!(parsed, serde_json::to_value(&info).unwrap());
+        assert_eq!(parsed["methods"]["GET"], 2);
+        assert_eq!(parsed["status_codes"]["404"], 1);
+
+        // Keys come out sorted, so the document is the same on every run
+        let codes: Vec<&str> = yaml.lines().skip_while(|l| *l != "status_codes:").skip(1).take(3).collect();
+        assert_eq!(codes, ["  200: 1", "  404: 1", "  500: 1"]);
     }
 }
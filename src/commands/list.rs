@@ -1,9 +1,21 @@
-use crate::har::Har;
-use crate::output::OutputFormat;
-use crate::output::table::print_entries_table;
-use crate::output::json::print_summaries_json;
-use anyhow::Result;
-use clap::Args;
+use crate::commands::page::PageArgs;
+use crate::filter::matcher::Matcher;
+use crate::har::{self, Entry, Har};
+use crate::output::template::Template;
+use crate::output::{format_time, OutputFormat};
+use crate::output::table::{colorize_status, print_columns_table, print_entries_table, status_label, style_table, EntryRow, SummaryFooter, DEFAULT_MAX_URL};
+use crate::output::json::{print_json, print_ndjson, print_summaries_json, print_summaries_ndjson};
+use crate::output::yaml::{print_summaries_yaml, print_yaml};
+use crate::har::source::Source;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, FixedOffset};
+use clap::{Args, ValueEnum};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::ops::ControlFlow;
+use std::time::Duration;
 
 #[derive(Debug, Args)]
 pub struct ListCmd {
@@ -27,36 +39,295 @@ pub struct ListCmd {
     #[arg(long)]
     pub tail: Option<usize>,
 
-    /// Maximum URL length before truncation
-    #[arg(long, default_value = "60")]
-    pub max_url: usize,
+    /// Maximum URL length before truncation (default: fit the terminal width;
+    /// 60 with --select or --merge-duplicates)
+    #[arg(long)]
+    pub max_url: Option<usize>,
 
     /// Long format (more columns)
     #[arg(short = 'l', long)]
     pub long: bool,
+
+    /// Sort entries by field (ascending) before applying head/tail/limit
+    #[arg(short, long)]
+    pub sort: Option<SortField>,
+
+    /// Reverse sort order (descending)
+    #[arg(short = 'R', long, requires = "sort")]
+    pub reverse: bool,
+
+    /// Columns to show in table output (e.g., method,status,host,time)
+    #[arg(long, value_delimiter = ',')]
+    pub select: Option<Vec<String>>,
+
+    /// Only show entries whose URL contains this text
+    #[arg(long, alias = "grep", value_name = "PATTERN")]
+    pub r#match: Option<String>,
+
+    /// Only show entries whose URL matches this regex
+    #[arg(long, value_name = "REGEX", conflicts_with = "match")]
+    pub match_regex: Option<String>,
+
+    /// Show repeated requests (same method and URL) as one row with a count
+    #[arg(long, conflicts_with = "select")]
+    pub merge_duplicates: bool,
+
+    /// Only show entries started at or after this time (RFC 3339 or YYYY-MM-DD)
+    #[arg(long, value_name = "TIME", value_parser = parse_time_arg)]
+    pub since: Option<DateTime<FixedOffset>>,
+
+    /// Only show entries started before this time (RFC 3339 or YYYY-MM-DD)
+    #[arg(long, value_name = "TIME", value_parser = parse_time_arg)]
+    pub until: Option<DateTime<FixedOffset>>,
+
+    /// Print a footer with shown/matched counts and total bytes and time (table output)
+    #[arg(long, conflicts_with = "merge_duplicates")]
+    pub summary: bool,
+
+    /// Print one line per entry from a template, e.g. "{method} {status} {url}";
+    /// placeholders are the --select column names, {{ and }} are literal braces
+    #[arg(long, value_name = "TEMPLATE", value_parser = Template::parse, conflicts_with_all = ["output", "select", "merge_duplicates", "summary"])]
+    pub format: Option<Template>,
+
+    #[command(flatten)]
+    pub page: PageArgs,
+
+    /// Read newline-delimited entries (one JSON entry per line); implied by
+    /// a .jsonl or .ndjson extension
+    #[arg(long)]
+    pub jsonl: bool,
+
+    /// Keep reading JSONL input as lines are appended, printing each new
+    /// match as it arrives, like `tail -f`
+    #[arg(short, long, conflicts_with_all = ["sort", "tail", "merge_duplicates", "summary", "page", "page_title"])]
+    pub follow: bool,
+}
+
+fn parse_time_arg(s: &str) -> std::result::Result<DateTime<FixedOffset>, String> {
+    har::parse_timestamp(s)
+        .ok_or_else(|| format!("expected a timestamp like 2024-01-01T12:00:00Z or 2024-01-01, got '{}'", s))
+}
+
+/// Fields `list --sort` can order by
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortField {
+    Time,
+    Size,
+    Status,
+    Method,
+    Url,
+    Started,
+}
+
+impl SortField {
+    fn compare(&self, a: &crate::har::Entry, b: &crate::har::Entry) -> Ordering {
+        match self {
+            SortField::Time => a.time.partial_cmp(&b.time).unwrap_or(Ordering::Equal),
+            SortField::Size => a.response.body_size.cmp(&b.response.body_size),
+            SortField::Status => a.response.status.cmp(&b.response.status),
+            SortField::Method => a.request.method.cmp(&b.request.method),
+            SortField::Url => a.request.url.cmp(&b.request.url),
+            // Chronological when both parse, so mixed offsets order correctly
+            SortField::Started => compare_started(&a.started_date_time, &b.started_date_time),
+        }
+    }
 }
 
 impl ListCmd {
     pub fn run(&self, har: &Har, color: bool) -> Result<()> {
-        let entries: Vec<(usize, &crate::har::Entry)> = har.log.entries
+        let entries = self.select(har)?;
+
+        if self.merge_duplicates {
+            let groups = merge_duplicates(&entries);
+            let groups = self.apply_limits(groups);
+            return self.print_groups(&groups, color);
+        }
+
+        // Apply head/tail/limit
+        let matched = entries.len();
+        let entries = self.apply_limits(entries);
+
+        self.print(&entries, color)?;
+        if self.summary && matches!(self.output, OutputFormat::Table) {
+            println!("{}", SummaryFooter::new(&entries, matched).render(color));
+        }
+        Ok(())
+    }
+
+    /// Entries matching `--page`, `--since`/`--until` and `--match`/`--match-regex`,
+    /// sorted by `--sort`
+    fn select<'a>(&self, har: &'a Har) -> Result<Vec<(usize, &'a Entry)>> {
+        let matcher = self.matcher()?;
+        let page = self.page.resolve(har)?;
+        let mut entries: Vec<(usize, &Entry)> = har.log.entries
             .iter()
             .enumerate()
             .map(|(i, e)| (i + 1, e))
+            .filter(|(_, e)| page.as_ref().is_none_or(|p| p.contains(e)))
+            .filter(|(_, e)| self.in_time_range(e))
+            .filter(|(_, e)| matcher.as_ref().is_none_or(|m| m.matches(&e.request.url)))
             .collect();
 
-        // Apply head/tail/limit
-        let entries = self.apply_limits(entries);
+        // Sort keeps the original index so `#` still points into the file
+        if let Some(field) = self.sort {
+            entries.sort_by(|a, b| {
+                let cmp = field.compare(a.1, b.1);
+                if self.reverse { cmp.reverse() } else { cmp }
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Whether the entry started within `--since`/`--until`; entries whose
+    /// start can't be parsed are left out once either is given
+    fn in_time_range(&self, entry: &Entry) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        entry.started_at().is_some_and(|t| {
+            self.since.is_none_or(|since| t >= since) && self.until.is_none_or(|until| t < until)
+        })
+    }
+
+    fn matcher(&self) -> Result<Option<Matcher>> {
+        if let Some(ref pattern) = self.r#match {
+            return Ok(Some(Matcher::text(pattern, false, false)));
+        }
+        self.match_regex
+            .as_deref()
+            .map(|pattern| Matcher::regex(pattern, false))
+            .transpose()
+    }
+
+    /// Whether the input is one entry per line rather than a HAR document
+    pub fn jsonl_input(&self) -> bool {
+        self.jsonl || har::jsonl::is_jsonl_path(&self.file)
+    }
+
+    /// List JSONL input, following it with `--follow`
+    pub fn run_jsonl(&self, color: bool) -> Result<()> {
+        if !self.jsonl_input() {
+            bail!("--follow needs JSONL input: a .jsonl file or --jsonl");
+        }
+        if !self.follow {
+            let har = har::jsonl::parse_reader(har::open(&self.file)?)?;
+            return self.run(&har, color);
+        }
+
+        // A file is polled for appended lines; stdin is read until it closes
+        match Source::parse(&self.file) {
+            Source::File(path) => {
+                let file = File::open(path).with_context(|| format!("Failed to open file: {}", path))?;
+                self.follow(BufReader::new(file), Some(har::jsonl::POLL_INTERVAL), color)
+            }
+            _ => self.follow(har::open(&self.file)?, None, color),
+        }
+    }
+
+    /// Print each matching entry as soon as it is read, stopping after head/limit matches
+    fn follow<R: BufRead>(&self, reader: R, poll: Option<Duration>, color: bool) -> Result<()> {
+        if matches!(self.output, OutputFormat::Yaml) && self.format.is_none() {
+            bail!("--follow prints one line per entry; use --output compact, json or ndjson");
+        }
+        let max = self.head.or(self.limit).unwrap_or(usize::MAX);
+        let matcher = self.matcher()?;
+        let mut shown = 0usize;
+        let mut index = 0usize;
+        let mut failed = None;
+
+        har::jsonl::read_entries(reader, poll, |entry| {
+            index += 1;
+            let matches = self.in_time_range(&entry) && matcher.as_ref().is_none_or(|m| m.matches(&entry.request.url));
+            if !matches {
+                return ControlFlow::Continue(());
+            }
+            if let Err(err) = self.print_line(index, &entry, color) {
+                failed = Some(err);
+                return ControlFlow::Break(());
+            }
+            shown += 1;
+            if shown >= max {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })?;
+
+        failed.map_or(Ok(()), Err)
+    }
+
+    /// One `--follow` line: the template, an NDJSON summary, or the compact row
+    fn print_line(&self, index: usize, entry: &Entry, color: bool) -> Result<()> {
+        let entries = [(index, entry)];
+        if let Some(ref template) = self.format {
+            println!("{}", template.render(index, entry, color));
+            return Ok(());
+        }
+        match self.output {
+            OutputFormat::Json | OutputFormat::Ndjson => print_summaries_ndjson(&entries),
+            _ => self.print_compact(&entries),
+        }
+    }
+
+    /// Whether only the first entries are shown, so the file can be streamed
+    pub fn can_stream(&self) -> bool {
+        // Pages may come after the entries in the file, so --page needs the whole HAR
+        // --summary counts every match, so it needs the whole file too
+        self.sort.is_none() && !self.merge_duplicates && !self.summary && !self.page.is_set() && (self.head.is_some() || (self.tail.is_none() && self.limit.is_some()))
+    }
+
+    /// List the first head/limit entries, reading no further into the file
+    pub fn run_streaming<R: Read>(&self, reader: R, color: bool) -> Result<()> {
+        let max = self.head.or(self.limit).unwrap_or(usize::MAX);
+        let matcher = self.matcher()?;
+        let mut owned = Vec::new();
+        let mut index = 0usize;
+
+        har::stream_entries(reader, |entry| {
+            index += 1;
+            let matches = self.in_time_range(&entry) && matcher.as_ref().is_none_or(|m| m.matches(&entry.request.url));
+            if matches && owned.len() < max {
+                owned.push((index, entry));
+            }
+            if owned.len() >= max {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })?;
+
+        let entries: Vec<(usize, &crate::har::Entry)> = owned
+            .iter()
+            .map(|(i, e)| (*i, e))
+            .collect();
+
+        self.print(&entries, color)
+    }
+
+    fn print(&self, entries: &[(usize, &crate::har::Entry)], color: bool) -> Result<()> {
+        if let Some(ref template) = self.format {
+            for (i, entry) in entries {
+                println!("{}", template.render(*i, entry, color));
+            }
+            return Ok(());
+        }
 
         match self.output {
-            OutputFormat::Json => print_summaries_json(&entries, true)?,
-            OutputFormat::Compact => self.print_compact(&entries)?,
-            OutputFormat::Table => print_entries_table(&entries, color, self.max_url),
+            OutputFormat::Json => print_summaries_json(entries, true)?,
+            OutputFormat::Ndjson => print_summaries_ndjson(entries)?,
+            OutputFormat::Yaml => print_summaries_yaml(entries)?,
+            OutputFormat::Compact => self.print_compact(entries)?,
+            OutputFormat::Table => match self.select {
+                Some(ref columns) => print_columns_table(entries, columns, color, self.max_url.unwrap_or(DEFAULT_MAX_URL))?,
+                None => print_entries_table(entries, color, self.max_url),
+            },
         }
 
         Ok(())
     }
 
-    fn apply_limits<'a>(&self, entries: Vec<(usize, &'a crate::har::Entry)>) -> Vec<(usize, &'a crate::har::Entry)> {
+    fn apply_limits<T>(&self, entries: Vec<T>) -> Vec<T> {
         let len = entries.len();
 
         if let Some(head) = self.head {
@@ -74,16 +345,310 @@ impl ListCmd {
         entries
     }
 
+    fn print_groups(&self, groups: &[DuplicateGroup], color: bool) -> Result<()> {
+        match self.output {
+            OutputFormat::Json => print_json(&groups, true)?,
+            OutputFormat::Ndjson => print_ndjson(groups)?,
+            OutputFormat::Yaml => print_yaml(&groups)?,
+            OutputFormat::Compact => {
+                for g in groups {
+                    println!("{}\t{}\t{}\t{}\t{:.0}ms\t{}",
+                        g.index,
+                        g.method,
+                        format_statuses(&g.statuses),
+                        g.count,
+                        g.total_time_ms,
+                        g.url
+                    );
+                }
+            }
+            OutputFormat::Table => {
+                if groups.is_empty() {
+                    println!("No entries found.");
+                    return Ok(());
+                }
+
+                let rows: Vec<MergedRow> = groups
+                    .iter()
+                    .map(|g| MergedRow::from_group(g, color, self.max_url.unwrap_or(DEFAULT_MAX_URL)))
+                    .collect();
+                let mut table = tabled::Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+        Ok(())
+    }
+
     fn print_compact(&self, entries: &[(usize, &crate::har::Entry)]) -> Result<()> {
         for (i, entry) in entries {
-            println!("{}\t{}\t{}\t{:.0}ms\t{}",
-                i,
-                entry.request.method,
-                entry.response.status,
-                entry.time,
-                entry.request.url
-            );
+            println!("{}", compact_row(*i, entry));
         }
         Ok(())
     }
 }
+
+/// One `list -o compact` row: index, method, status, time and URL, tab-separated
+pub fn compact_row(index: usize, entry: &Entry) -> String {
+    format!("{}\t{}\t{}\t{:.0}ms\t{}",
+        index,
+        entry.request.method,
+        entry.response.status,
+        entry.time,
+        entry.request.url
+    )
+}
+
+/// Requests sharing a method and URL, shown as one `--merge-duplicates` row
+#[derive(Debug, serde::Serialize)]
+pub struct DuplicateGroup<'a> {
+    /// Position of the first occurrence in the file
+    pub index: usize,
+    pub method: &'a str,
+    pub url: &'a str,
+    pub count: usize,
+    /// Distinct statuses, in order of first appearance
+    pub statuses: Vec<i32>,
+    pub first_started: &'a str,
+    pub last_started: &'a str,
+    pub total_time_ms: f64,
+    #[serde(skip)]
+    first: &'a Entry,
+}
+
+/// Group entries with the same method and URL anywhere in the list, keeping
+/// groups in order of first occurrence
+pub fn merge_duplicates<'a>(entries: &[(usize, &'a Entry)]) -> Vec<DuplicateGroup<'a>> {
+    let mut groups: Vec<DuplicateGroup<'a>> = Vec::new();
+    let mut positions: HashMap<(&str, &str), usize> = HashMap::new();
+
+    for (i, entry) in entries {
+        let key = (entry.request.method.as_str(), entry.request.url.as_str());
+        let started = entry.started_date_time.as_str();
+
+        match positions.get(&key) {
+            Some(&pos) => {
+                let group = &mut groups[pos];
+                group.count += 1;
+                group.total_time_ms += entry.time.max(0.0);
+                if !group.statuses.contains(&entry.response.status) {
+                    group.statuses.push(entry.response.status);
+                }
+                if compare_started(started, group.first_started).is_lt() {
+                    group.first_started = started;
+                }
+                if compare_started(started, group.last_started).is_gt() {
+                    group.last_started = started;
+                }
+            }
+            None => {
+                positions.insert(key, groups.len());
+                groups.push(DuplicateGroup {
+                    index: *i,
+                    method: key.0,
+                    url: key.1,
+                    count: 1,
+                    statuses: vec![entry.response.status],
+                    first_started: started,
+                    last_started: started,
+                    total_time_ms: entry.time.max(0.0),
+                    first: entry,
+                });
+            }
+        }
+    }
+
+    groups
+}
+
+/// Order two `startedDateTime` values as instants, so offsets and fractional
+/// precision don't matter; as text when either can't be parsed
+fn compare_started(a: &str, b: &str) -> Ordering {
+    match (har::parse_timestamp(a), har::parse_timestamp(b)) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        _ => a.cmp(b),
+    }
+}
+
+#[derive(tabled::Tabled)]
+struct MergedRow {
+    #[tabled(rename = "#")]
+    index: usize,
+    #[tabled(rename = "Method")]
+    method: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Count")]
+    count: usize,
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(rename = "Span")]
+    span: String,
+    #[tabled(rename = "URL")]
+    url: String,
+}
+
+impl MergedRow {
+    fn from_group(group: &DuplicateGroup, color: bool, max_url_len: usize) -> Self {
+        let row = EntryRow::from_entry(group.index, group.first, color, max_url_len);
+        let status = group
+            .statuses
+            .iter()
+            .map(|s| if color { colorize_status(*s) } else { status_label(*s) })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let span = if group.count == 1 {
+            time_of_day(group.first_started).to_string()
+        } else {
+            format!("{} - {}", time_of_day(group.first_started), time_of_day(group.last_started))
+        };
+
+        Self {
+            index: row.index,
+            method: row.method,
+            status,
+            count: group.count,
+            time: format_time(group.total_time_ms),
+            span,
+            url: row.url,
+        }
+    }
+}
+
+/// `HH:MM:SS` from an ISO 8601 timestamp, or the timestamp unchanged
+fn time_of_day(started: &str) -> &str {
+    started
+        .split_once('T')
+        .and_then(|(_, time)| time.get(..8))
+        .unwrap_or(started)
+}
+
+fn format_statuses(statuses: &[i32]) -> String {
+    statuses.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{parse_args, try_parse_args};
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_merge_five_identical_requests() {
+        let mut jsons = Vec::new();
+        for second in 0..5 {
+            let json = fixtures::entry_with("GET", "https://example.com/poll", if second == 3 { 503 } else { 200 }, |e| {
+                e["startedDateTime"] = serde_json::json!(format!("2024-01-01T10:00:0{}.000Z", second));
+            });
+            jsons.push(json);
+        }
+        jsons.insert(2, fixtures::entry_json("POST", "https://example.com/poll", 201));
+        let har = fixtures::har(jsons);
+        let entries: Vec<(usize, &Entry)> = har.log.entries.iter().enumerate().map(|(i, e)| (i + 1, e)).collect();
+
+        let groups = merge_duplicates(&entries);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].index, 1);
+        assert_eq!(groups[0].count, 5);
+        assert_eq!(groups[0].statuses, [200, 503]);
+        assert_eq!(groups[0].total_time_ms, 500.0);
+        assert_eq!(groups[1].method, "POST");
+
+        let row = MergedRow::from_group(&groups[0], false, 60);
+        assert_eq!(row.span, "10:00:00 - 10:00:04");
+        assert_eq!(row.status, "200, 503");
+    }
+
+    #[test]
+    fn test_compact_row() {
+        let entry = fixtures::entry("POST", "https://example.com/login", 302);
+        assert_eq!(compact_row(7, &entry), "7\tPOST\t302\t100ms\thttps://example.com/login");
+    }
+
+    #[test]
+    fn test_merge_span_compares_instants() {
+        let started = ["2024-01-01T10:00:00.000+02:00", "2024-01-01T09:00:00Z", "2024-01-01T09:00:00.5Z", "2024-01-01T07:59:59Z"];
+        let jsons = started
+            .iter()
+            .map(|s| {
+                fixtures::entry_with("GET", "https://example.com/poll", 200, |e| {
+                    e["startedDateTime"] = serde_json::json!(s);
+                })
+            })
+            .collect();
+        let har = fixtures::har(jsons);
+        let entries: Vec<(usize, &Entry)> = har.log.entries.iter().enumerate().map(|(i, e)| (i + 1, e)).collect();
+
+        let groups = merge_duplicates(&entries);
+        assert_eq!(groups[0].first_started, "2024-01-01T07:59:59Z");
+        assert_eq!(groups[0].last_started, "2024-01-01T09:00:00.5Z");
+    }
+
+    #[test]
+    fn test_match_filters_before_sort_and_head() {
+        let mut jsons = Vec::new();
+        for (path, time) in [("/api/a", 300.0), ("/static/x.js", 900.0), ("/api/b", 100.0), ("/api/c", 200.0)] {
+            let json = fixtures::entry_with("GET", &format!("https://example.com{}", path), 200, |e| {
+                e["time"] = serde_json::json!(time);
+            });
+            jsons.push(json);
+        }
+        let har = fixtures::har(jsons);
+
+        let cmd = parse_args::<ListCmd>(&["--match", "/api/", "--sort", "time", "--reverse", "--head", "2"]);
+
+        let entries = cmd.apply_limits(cmd.select(&har).unwrap());
+        let indexes: Vec<usize> = entries.iter().map(|(i, _)| *i).collect();
+        // The slow /static/ entry is filtered out before sorting, so it can't take a head slot
+        assert_eq!(indexes, [1, 4]);
+
+        // --summary needs every match, so it turns off streaming
+        let cmd = parse_args::<ListCmd>(&["--head", "2", "--summary"]);
+        assert!(!cmd.can_stream());
+        let matched = cmd.select(&har).unwrap();
+        let shown = cmd.apply_limits(matched.clone());
+        let footer = SummaryFooter::new(&shown, matched.len());
+        assert_eq!((footer.shown, footer.matched, footer.time_ms), (2, 4, 1200.0));
+    }
+
+    #[test]
+    fn test_since_until_and_sort_are_chronological() {
+        // As strings these sort 1, 2, 3; in time the +02:00 entry is first
+        let started = ["2024-01-01T09:30:00.000Z", "2024-01-01T10:00:00Z", "2024-01-01T11:00:00.000+02:00", "not a date"];
+        let jsons = started
+            .iter()
+            .map(|s| {
+                fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+                    e["startedDateTime"] = serde_json::json!(s);
+                })
+            })
+            .collect();
+        let har = fixtures::har(jsons);
+
+        let indexes = |args: &[&str]| -> Vec<usize> {
+            let cmd = parse_args::<ListCmd>(args);
+            cmd.select(&har).unwrap().iter().map(|(i, _)| *i).collect()
+        };
+
+        assert_eq!(indexes(&["--sort", "started"])[..3], [3, 1, 2]);
+        assert_eq!(indexes(&["--since", "2024-01-01T09:30:00+00:00"]), [1, 2]);
+        assert_eq!(indexes(&["--until", "2024-01-01T10:00:00Z"]), [1, 3]);
+        assert_eq!(indexes(&["--since", "2024-01-01", "--until", "2024-01-02"]), [1, 2, 3]);
+        assert!(try_parse_args::<ListCmd>(&["--since", "yesterday"]).is_err());
+    }
+
+    #[test]
+    fn test_follow_needs_jsonl_and_conflicts_with_whole_file_options() {
+        let parse = |args: &[&str]| try_parse_args::<ListCmd>(args);
+
+        assert!(parse(&["live.jsonl", "--follow"]).unwrap().jsonl_input());
+        assert!(parse(&["-", "--jsonl", "-f"]).unwrap().jsonl_input());
+        assert!(!parse(&["capture.har"]).unwrap().jsonl_input());
+        assert!(parse(&["live.jsonl", "--follow", "--sort", "time"]).is_err());
+        assert!(parse(&["live.jsonl", "--follow", "--tail", "5"]).is_err());
+
+        let err = parse(&["capture.har", "--follow"]).unwrap().run_jsonl(false).unwrap_err();
+        assert_eq!(err.to_string(), "--follow needs JSONL input: a .jsonl file or --jsonl");
+    }
+}
@@ -0,0 +1,45 @@
+use crate::har;
+use anyhow::{Context, Result};
+use clap::Args;
+use std::io::{self, BufWriter, Write};
+
+#[derive(Debug, Args)]
+pub struct MergeCmd {
+    /// HAR files to merge, in order (use - for stdin)
+    #[arg(required = true, num_args = 1..)]
+    pub files: Vec<String>,
+
+    /// Order entries by startedDateTime instead of file order
+    #[arg(long)]
+    pub sort: bool,
+
+    /// Emit compact JSON instead of pretty-printed
+    #[arg(long)]
+    pub compact: bool,
+}
+
+impl MergeCmd {
+    pub fn run(&self) -> Result<()> {
+        let hars = self
+            .files
+            .iter()
+            .map(|path| {
+                let har = har::parse_reader(har::open(path)?)
+                    .with_context(|| format!("Failed to read {}", path))?;
+                Ok((path.clone(), har))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let merged = har::merge(hars, self.sort).expect("at least one file is required");
+
+        let mut out = BufWriter::new(io::stdout().lock());
+        if self.compact {
+            serde_json::to_writer(&mut out, &merged)?;
+        } else {
+            serde_json::to_writer_pretty(&mut out, &merged)?;
+        }
+        writeln!(out)?;
+        out.flush()?;
+        Ok(())
+    }
+}
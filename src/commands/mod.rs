@@ -7,6 +7,27 @@ pub mod filter;
 pub mod body;
 pub mod timing;
 pub mod headers;
+pub mod endpoints;
+pub mod merge;
+pub mod domains;
+pub mod top;
+pub mod agg;
+pub mod validate;
+pub mod openapi;
+pub mod page;
+pub mod header_order;
+pub mod redirects;
+pub mod content_types;
+pub mod redact;
+pub mod params;
+pub mod savings;
+pub mod split;
+pub mod assert;
+pub mod normalize;
+pub mod extract;
+pub mod throughput;
+#[cfg(feature = "tui")]
+pub mod browse;
 
 pub use info::InfoCmd;
 pub use list::ListCmd;
@@ -17,3 +38,37 @@ pub use filter::FilterCmd;
 pub use body::BodyCmd;
 pub use timing::TimingCmd;
 pub use headers::HeadersCmd;
+pub use endpoints::EndpointsCmd;
+pub use merge::MergeCmd;
+pub use domains::DomainsCmd;
+pub use top::TopCmd;
+pub use agg::AggCmd;
+pub use validate::ValidateCmd;
+pub use openapi::OpenApiCmd;
+pub use redirects::RedirectsCmd;
+pub use content_types::ContentTypesCmd;
+pub use redact::RedactCmd;
+pub use params::ParamsCmd;
+pub use savings::SavingsCmd;
+pub use split::SplitCmd;
+pub use assert::AssertCmd;
+pub use normalize::NormalizeCmd;
+pub use extract::ExtractCmd;
+pub use throughput::ThroughputCmd;
+#[cfg(feature = "tui")]
+pub use browse::BrowseCmd;
+
+/// Parse `args`, without the program or command name, into a command's
+/// arguments as the CLI would
+#[cfg(test)]
+pub fn try_parse_args<T: clap::Args>(args: &[&str]) -> Result<T, clap::Error> {
+    let command = T::augment_args(clap::Command::new("harq"));
+    let matches = command.try_get_matches_from(std::iter::once("harq").chain(args.iter().copied()))?;
+    T::from_arg_matches(&matches)
+}
+
+/// `try_parse_args`, panicking on invalid arguments
+#[cfg(test)]
+pub fn parse_args<T: clap::Args>(args: &[&str]) -> T {
+    try_parse_args(args).unwrap()
+}
@@ -0,0 +1,235 @@
+use crate::har::{parse_timestamp, Cookie, Entry, Har, Header};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+#[derive(Debug, Args)]
+pub struct NormalizeCmd {
+    /// HAR file to normalize (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Order entries (and pages) by startedDateTime
+    #[arg(long)]
+    pub sort_entries: bool,
+
+    /// Order headers by name (case-insensitive), then by value
+    #[arg(long)]
+    pub sort_headers: bool,
+
+    /// Remove every `comment` field
+    #[arg(long)]
+    pub drop_comments: bool,
+
+    /// Shorthand for --sort-entries --sort-headers --drop-comments
+    #[arg(long)]
+    pub all: bool,
+
+    /// Write the result to a file instead of stdout
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Emit compact JSON instead of pretty-printed
+    #[arg(long)]
+    pub minify: bool,
+}
+
+impl NormalizeCmd {
+    pub fn run(&self, har: Har) -> Result<()> {
+        let har = self.normalize(har);
+        match self.output_file {
+            Some(ref path) => {
+                let file = File::create(path)
+                    .with_context(|| format!("Failed to create file: {}", path))?;
+                self.write(BufWriter::new(file), &har)
+            }
+            None => self.write(BufWriter::new(io::stdout().lock()), &har),
+        }
+    }
+
+    fn normalize(&self, mut har: Har) -> Har {
+        if self.sort_headers || self.all {
+            for entry in &mut har.log.entries {
+                sort_headers(&mut entry.request.headers);
+                sort_headers(&mut entry.response.headers);
+            }
+        }
+        if self.drop_comments || self.all {
+            drop_comments(&mut har);
+        }
+        // Sorted last so ties are broken on the entries as written
+        if self.sort_entries || self.all {
+            har.log.entries.sort_by(compare_entries);
+            if let Some(ref mut pages) = har.log.pages {
+                pages.sort_by(|a, b| {
+                    parse_timestamp(&a.started_date_time)
+                        .cmp(&parse_timestamp(&b.started_date_time))
+                        .then_with(|| a.started_date_time.cmp(&b.started_date_time))
+                        .then_with(|| a.id.cmp(&b.id))
+                });
+            }
+        }
+        har
+    }
+
+    fn write<W: Write>(&self, mut out: W, har: &Har) -> Result<()> {
+        // Going through Value sorts object keys, including custom `_` fields
+        // that are otherwise written in hash order
+        let value = serde_json::to_value(har)?;
+        if self.minify {
+            serde_json::to_writer(&mut out, &value)?;
+        } else {
+            serde_json::to_writer_pretty(&mut out, &value)?;
+        }
+        writeln!(out)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Start time, then URL and method; identical keys fall back to the whole
+/// entry so the order never depends on the input order
+fn compare_entries(a: &Entry, b: &Entry) -> Ordering {
+    a.started_at()
+        .cmp(&b.started_at())
+        .then_with(|| a.started_date_time.cmp(&b.started_date_time))
+        .then_with(|| a.request.url.cmp(&b.request.url))
+        .then_with(|| a.request.method.cmp(&b.request.method))
+        .then_with(|| {
+            let json = |e: &Entry| serde_json::to_value(e).map(|v| v.to_string()).unwrap_or_default();
+            json(a).cmp(&json(b))
+        })
+}
+
+fn sort_headers(headers: &mut [Header]) {
+    headers.sort_by(|a, b| {
+        a.name
+            .to_ascii_lowercase()
+            .cmp(&b.name.to_ascii_lowercase())
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.value.cmp(&b.value))
+    });
+}
+
+fn drop_comments(har: &mut Har) {
+    let log = &mut har.log;
+    log.comment = None;
+    log.creator.comment = None;
+    if let Some(ref mut browser) = log.browser {
+        browser.comment = None;
+    }
+    for page in log.pages.iter_mut().flatten() {
+        page.comment = None;
+        if let Some(ref mut timings) = page.page_timings {
+            timings.comment = None;
+        }
+    }
+
+    let cookies = |cookies: &mut [Cookie]| cookies.iter_mut().for_each(|c| c.comment = None);
+    let headers = |headers: &mut [Header]| headers.iter_mut().for_each(|h| h.comment = None);
+    for entry in &mut log.entries {
+        entry.comment = None;
+
+        let request = &mut entry.request;
+        request.comment = None;
+        cookies(&mut request.cookies);
+        headers(&mut request.headers);
+        request.query_string.iter_mut().for_each(|q| q.comment = None);
+        if let Some(ref mut post_data) = request.post_data {
+            post_data.comment = None;
+            post_data.params.iter_mut().flatten().for_each(|p| p.comment = None);
+        }
+
+        let response = &mut entry.response;
+        response.comment = None;
+        cookies(&mut response.cookies);
+        headers(&mut response.headers);
+        response.content.comment = None;
+
+        let cache = &mut entry.cache;
+        cache.comment = None;
+        for cached in [&mut cache.before_request, &mut cache.after_request].into_iter().flatten() {
+            cached.comment = None;
+        }
+        entry.timings.comment = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::parse_args;
+    use crate::har::fixtures;
+    use serde_json::json;
+
+    fn entries() -> Vec<serde_json::Value> {
+        let mut entries = Vec::new();
+        for (i, (time, url)) in [
+            ("2024-01-01T00:00:01.000Z", "https://example.com/b"),
+            ("2024-01-01T00:00:00.000Z", "https://example.com/a"),
+            // Same instant as /a, written with an offset
+            ("2024-01-01T01:00:00.000+01:00", "https://example.com/c"),
+            ("2024-01-01T00:00:01.000Z", "https://example.com/b"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let entry = fixtures::entry_with("GET", url, 200, |e| {
+                e["startedDateTime"] = json!(time);
+                e["comment"] = json!("captured by hand");
+                e["_index"] = json!(i);
+                e["_priority"] = json!("High");
+                e["request"]["headers"] = json!([
+                    {"name": "user-agent", "value": "test"},
+                    {"name": "Accept", "value": "*/*", "comment": "note"},
+                    {"name": "accept", "value": "text/html"}
+                ]);
+            });
+            entries.push(entry);
+        }
+        entries
+    }
+
+    fn normalized(args: &[&str], entries: Vec<serde_json::Value>) -> String {
+        let cmd = parse_args::<NormalizeCmd>(args);
+        let mut out = Vec::new();
+        cmd.write(&mut out, &cmd.normalize(fixtures::har(entries))).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_shuffled_input_gives_identical_output() {
+        let original = entries();
+        let expected = normalized(&["--all"], original.clone());
+
+        for order in [[3, 2, 1, 0], [1, 3, 0, 2], [2, 0, 3, 1]] {
+            let shuffled = order.iter().map(|&i| original[i].clone()).collect();
+            assert_eq!(normalized(&["--all"], shuffled), expected);
+        }
+
+        let har: Har = serde_json::from_str(&expected).unwrap();
+        let urls: Vec<&str> = har.log.entries.iter().map(|e| e.request.url.as_str()).collect();
+        assert_eq!(urls, [
+            "https://example.com/a", "https://example.com/c", "https://example.com/b", "https://example.com/b",
+        ]);
+        // The tie between the two /b entries is broken on their content
+        assert_eq!(har.log.entries[2].custom["_index"], 0);
+    }
+
+    #[test]
+    fn test_headers_and_comments() {
+        let har: Har = serde_json::from_str(&normalized(&["--sort-headers", "--drop-comments"], entries())).unwrap();
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.request.url, "https://example.com/b", "entries keep their order");
+        let headers: Vec<(&str, &str)> = entry.request.headers.iter().map(|h| (h.name.as_str(), h.value.as_str())).collect();
+        assert_eq!(headers, [("Accept", "*/*"), ("accept", "text/html"), ("user-agent", "test")]);
+        assert!(entry.comment.is_none());
+        assert!(entry.request.headers.iter().all(|h| h.comment.is_none()));
+
+        let har: Har = serde_json::from_str(&normalized(&[], entries())).unwrap();
+        assert_eq!(har.log.entries[0].comment.as_deref(), Some("captured by hand"));
+        assert_eq!(har.log.entries[0].request.headers[0].name, "user-agent");
+    }
+}
@@ -0,0 +1,290 @@
+use crate::filter::eval::is_graphql_request;
+use crate::har::{Entry, Har};
+use crate::output::extract_path;
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufWriter, Write};
+
+#[derive(Debug, Args)]
+pub struct OpenApiCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Document format
+    #[arg(short, long, alias = "format", default_value = "yaml")]
+    pub output: SpecFormat,
+
+    /// Title for the info section
+    #[arg(long, default_value = "Captured API")]
+    pub title: String,
+}
+
+/// Serialization of the OpenAPI document
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SpecFormat {
+    Yaml,
+    Json,
+}
+
+/// Methods OpenAPI 3.0 has operation slots for
+const OPERATION_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+#[derive(Debug, Serialize)]
+pub struct OpenApi {
+    openapi: &'static str,
+    info: Info,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    servers: Vec<Server>,
+    paths: BTreeMap<String, PathItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct Info {
+    title: String,
+    version: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Server {
+    url: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct PathItem {
+    /// Only set when the path was seen on some, but not all, servers
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    servers: Vec<Server>,
+    #[serde(flatten)]
+    operations: BTreeMap<String, Operation>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Operation {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    parameters: Vec<Parameter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_body: Option<Body>,
+    responses: BTreeMap<String, Response>,
+    #[serde(skip)]
+    calls: usize,
+    #[serde(skip)]
+    query_counts: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct Parameter {
+    name: String,
+    #[serde(rename = "in")]
+    location: &'static str,
+    required: bool,
+    schema: Schema,
+}
+
+#[derive(Debug, Serialize)]
+struct Schema {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Body {
+    content: BTreeMap<String, MediaType>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Response {
+    description: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    content: BTreeMap<String, MediaType>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct MediaType {}
+
+impl OpenApiCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let spec = build_spec(har, &self.title);
+
+        let mut out = BufWriter::new(io::stdout().lock());
+        match self.output {
+            SpecFormat::Yaml => serde_yaml_ng::to_writer(&mut out, &spec)?,
+            SpecFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, &spec)?;
+                writeln!(out)?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Build an OpenAPI 3.0 skeleton from the non-GraphQL entries of a HAR.
+///
+/// Operations are keyed by literal path and method. Responses list each
+/// observed status with its content types; query parameters seen on every
+/// call are marked required.
+pub fn build_spec(har: &Har, title: &str) -> OpenApi {
+    let mut paths: BTreeMap<String, PathItem> = BTreeMap::new();
+    let mut path_servers: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut servers = BTreeSet::new();
+
+    for entry in har.log.entries.iter().filter(|e| !is_graphql_request(e)) {
+        let method = entry.request.method.to_lowercase();
+        if !OPERATION_METHODS.contains(&method.as_str()) {
+            continue;
+        }
+
+        let url = &entry.request.url;
+        let path = extract_path(url).split(['?', '#']).next().unwrap_or("/").to_string();
+        let origin = origin(url).to_string();
+        servers.insert(origin.clone());
+        path_servers.entry(path.clone()).or_default().insert(origin);
+
+        let operation = paths.entry(path).or_default().operations.entry(method).or_default();
+        record(operation, entry);
+    }
+
+    for (path, item) in paths.iter_mut() {
+        let seen_on = &path_servers[path];
+        if servers.len() > 1 && seen_on.len() < servers.len() {
+            item.servers = seen_on.iter().map(|url| Server { url: url.clone() }).collect();
+        }
+
+        for operation in item.operations.values_mut() {
+            operation.parameters = operation
+                .query_counts
+                .iter()
+                .map(|(name, count)| Parameter {
+                    name: name.clone(),
+                    location: "query",
+                    required: *count == operation.calls,
+                    schema: Schema { kind: "string" },
+                })
+                .collect();
+            if operation.responses.is_empty() {
+                operation.responses.insert(
+                    "default".to_string(),
+                    Response { description: "No response recorded".to_string(), ..Default::default() },
+                );
+            }
+        }
+    }
+
+    OpenApi {
+        openapi: "3.0.3",
+        info: Info { title: title.to_string(), version: "1.0.0" },
+        servers: servers.into_iter().map(|url| Server { url }).collect(),
+        paths,
+    }
+}
+
+/// Fold one call into its operation
+fn record(operation: &mut Operation, entry: &Entry) {
+    operation.calls += 1;
+
+    let names: BTreeSet<&str> = entry.request.query_string.iter().map(|q| q.name.as_str()).collect();
+    for name in names {
+        *operation.query_counts.entry(name.to_string()).or_default() += 1;
+    }
+
+    if let Some(mime) = entry.request.post_data.as_ref().and_then(|pd| media_type(&pd.mime_type)) {
+        operation
+            .request_body
+            .get_or_insert_with(Body::default)
+            .content
+            .insert(mime, MediaType {});
+    }
+
+    // Status 0 means no response was received
+    let status = entry.response.status;
+    if status == 0 {
+        return;
+    }
+    let response = operation.responses.entry(status.to_string()).or_insert_with(|| Response {
+        description: match entry.response.status_text.trim() {
+            "" => format!("Status {}", status),
+            text => text.to_string(),
+        },
+        ..Default::default()
+    });
+    if let Some(mime) = entry.content_type().and_then(media_type) {
+        response.content.insert(mime, MediaType {});
+    }
+}
+
+/// Mime type without parameters, e.g. "application/json; charset=utf-8" -> "application/json"
+fn media_type(mime: &str) -> Option<String> {
+    let mime = mime.split(';').next().unwrap_or(mime).trim().to_lowercase();
+    (!mime.is_empty()).then_some(mime)
+}
+
+/// Scheme and authority of a URL (e.g. "https://api.example.com:8443")
+fn origin(url: &str) -> &str {
+    let end = url
+        .find("://")
+        .and_then(|i| url[i + 3..].find(['/', '?', '#']).map(|p| i + 3 + p))
+        .unwrap_or(url.len());
+    &url[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+    use serde_json::json;
+
+    #[test]
+    fn test_groups_operations_by_path_and_method() {
+        let get = fixtures::entry_with("GET", "https://api.example.com/users?page=1", 200, |e| {
+            e["request"]["queryString"] = json!([{"name": "page", "value": "1"}]);
+            e["response"]["content"] = json!({"size": 2, "mimeType": "application/json; charset=utf-8"});
+        });
+        let post = fixtures::entry_with("POST", "https://api.example.com/users", 201, |e| {
+            e["request"]["postData"] = json!({"mimeType": "application/json", "text": "{\"name\":\"a\"}"});
+        });
+        let graphql = fixtures::entry_with("POST", "https://api.example.com/graphql", 200, |e| {
+            e["request"]["postData"] = json!({"mimeType": "application/json", "text": "{\"query\":\"{ me }\"}"});
+        });
+        let har = fixtures::har(vec![
+            get,
+            fixtures::entry_json("GET", "https://api.example.com/users", 500),
+            post,
+            graphql,
+        ]);
+
+        let spec = serde_json::to_value(build_spec(&har, "Test")).unwrap();
+
+        assert_eq!(spec["openapi"], "3.0.3");
+        assert_eq!(spec["servers"], json!([{"url": "https://api.example.com"}]));
+        assert!(spec["paths"].get("/graphql").is_none());
+
+        let get = &spec["paths"]["/users"]["get"];
+        assert_eq!(get["responses"]["200"]["content"], json!({"application/json": {}}));
+        assert!(get["responses"].get("500").is_some());
+        assert_eq!(get["parameters"][0]["name"], "page");
+        assert_eq!(get["parameters"][0]["required"], false);
+
+        let post = &spec["paths"]["/users"]["post"];
+        assert_eq!(post["requestBody"]["content"], json!({"application/json": {}}));
+        assert!(post["responses"].get("201").is_some());
+    }
+
+    #[test]
+    fn test_path_servers_only_when_hosts_differ() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://a.example.com/shared", 200),
+            fixtures::entry_json("GET", "https://b.example.com/shared", 200),
+            fixtures::entry_json("GET", "https://b.example.com:8443/only-b", 0),
+        ]);
+
+        let spec = serde_json::to_value(build_spec(&har, "Test")).unwrap();
+        assert_eq!(spec["servers"].as_array().unwrap().len(), 3);
+        assert_eq!(spec["paths"]["/only-b"]["servers"], json!([{"url": "https://b.example.com:8443"}]));
+        assert!(spec["paths"]["/only-b"]["get"]["responses"].get("default").is_some());
+        assert_eq!(spec["paths"]["/shared"]["servers"].as_array().unwrap().len(), 2);
+    }
+}
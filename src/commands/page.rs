@@ -0,0 +1,122 @@
+use crate::har::{Entry, Har};
+use anyhow::{Result, bail};
+use clap::Args;
+use std::collections::HashSet;
+
+/// `--page`/`--page-title` options for commands that can scope to one page
+#[derive(Debug, Clone, Default, Args)]
+pub struct PageArgs {
+    /// Only include entries of the page with this id (matched against pageref)
+    #[arg(long, value_name = "ID")]
+    pub page: Option<String>,
+
+    /// Only include entries of pages whose title contains this text (case-insensitive)
+    #[arg(long, value_name = "TEXT", conflicts_with = "page")]
+    pub page_title: Option<String>,
+}
+
+/// Page ids selected with `--page` or `--page-title`
+#[derive(Debug)]
+pub struct PageFilter(HashSet<String>);
+
+impl PageArgs {
+    pub fn is_set(&self) -> bool {
+        self.page.is_some() || self.page_title.is_some()
+    }
+
+    /// Resolve the options against the HAR's pages; None when neither is given
+    pub fn resolve(&self, har: &Har) -> Result<Option<PageFilter>> {
+        if !self.is_set() {
+            return Ok(None);
+        }
+
+        let pages = har.log.pages.as_deref().unwrap_or_default();
+        if pages.is_empty() {
+            bail!("This HAR has no pages, so --page/--page-title cannot be used");
+        }
+
+        let (ids, wanted): (HashSet<String>, String) = match (&self.page, &self.page_title) {
+            (Some(id), _) => (
+                pages.iter().filter(|p| &p.id == id).map(|p| p.id.clone()).collect(),
+                format!("id '{}'", id),
+            ),
+            (None, Some(text)) => {
+                let text = text.to_lowercase();
+                (
+                    pages
+                        .iter()
+                        .filter(|p| p.title.to_lowercase().contains(&text))
+                        .map(|p| p.id.clone())
+                        .collect(),
+                    format!("a title containing '{}'", text),
+                )
+            }
+            (None, None) => unreachable!("checked by is_set"),
+        };
+
+        if ids.is_empty() {
+            let available: Vec<String> = pages.iter().map(|p| format!("{} ({})", p.id, p.title)).collect();
+            bail!("No page with {}. Pages: {}", wanted, available.join(", "));
+        }
+
+        Ok(Some(PageFilter(ids)))
+    }
+}
+
+impl PageFilter {
+    /// Whether the entry's pageref is one of the selected pages
+    pub fn contains(&self, entry: &Entry) -> bool {
+        entry.pageref.as_ref().is_some_and(|id| self.0.contains(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+    use serde_json::json;
+
+    fn paged_har() -> Har {
+        let a = fixtures::entry_with("GET", "https://example.com/a", 200, |e| {
+            e["pageref"] = json!("page_1");
+        });
+        let b = fixtures::entry_with("GET", "https://example.com/b", 200, |e| {
+            e["pageref"] = json!("page_2");
+        });
+        let mut har = fixtures::har(vec![a, b, fixtures::entry_json("GET", "https://example.com/c", 200)]);
+        har.log.pages = Some(serde_json::from_value(json!([
+            {"startedDateTime": "2024-01-01T00:00:00.000Z", "id": "page_1", "title": "Home"},
+            {"startedDateTime": "2024-01-01T00:00:05.000Z", "id": "page_2", "title": "Checkout - Shop"}
+        ])).unwrap());
+        har
+    }
+
+    fn args(page: Option<&str>, title: Option<&str>) -> PageArgs {
+        PageArgs { page: page.map(String::from), page_title: title.map(String::from) }
+    }
+
+    #[test]
+    fn test_page_by_id_and_title() {
+        let har = paged_har();
+
+        let filter = args(Some("page_2"), None).resolve(&har).unwrap().unwrap();
+        let urls: Vec<&str> = har.log.entries.iter().filter(|e| filter.contains(e)).map(|e| e.request.url.as_str()).collect();
+        assert_eq!(urls, ["https://example.com/b"]);
+
+        let filter = args(None, Some("checkout")).resolve(&har).unwrap().unwrap();
+        assert!(filter.contains(&har.log.entries[1]));
+        assert!(!filter.contains(&har.log.entries[2]));
+
+        assert!(args(None, None).resolve(&har).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unknown_page_and_missing_pages_error() {
+        let err = args(Some("page_9"), None).resolve(&paged_har()).unwrap_err().to_string();
+        assert!(err.contains("page_1 (Home)"), "{}", err);
+
+        let no_pages = fixtures::har(vec![fixtures::entry_json("GET", "https://example.com/", 200)]);
+        let err = args(Some("page_1"), None).resolve(&no_pages).unwrap_err().to_string();
+        assert!(err.contains("no pages"), "{}", err);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::har::{Entry, Har};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::table::style_table;
+use crate::output::yaml::print_yaml;
+use crate::output::{url_decode, OutputFormat};
+use anyhow::{Result, bail};
+use clap::Args;
+use serde::Serialize;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct ParamsCmd {
+    /// Entry index (1-based)
+    #[arg()]
+    pub index: usize,
+
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+}
+
+/// One submitted form field
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormParam {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+#[derive(Tabled)]
+struct ParamRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Value")]
+    value: String,
+}
+
+impl ParamsCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        if self.index == 0 || self.index > har.log.entries.len() {
+            bail!(
+                "Entry index {} out of range (1-{})",
+                self.index,
+                har.log.entries.len()
+            );
+        }
+
+        let params = form_params(&har.log.entries[self.index - 1]);
+
+        match self.output {
+            OutputFormat::Json => print_json(&params, true)?,
+            OutputFormat::Ndjson => print_ndjson(&params)?,
+            OutputFormat::Yaml => print_yaml(&params)?,
+            OutputFormat::Compact => {
+                for p in &params {
+                    println!("{}\t{}", p.name, display_value(p));
+                }
+            }
+            OutputFormat::Table => {
+                if params.is_empty() {
+                    println!("No form parameters.");
+                    return Ok(());
+                }
+
+                let rows: Vec<ParamRow> = params
+                    .iter()
+                    .map(|p| ParamRow {
+                        name: p.name.clone(),
+                        value: display_value(p),
+                    })
+                    .collect();
+
+                let mut table = Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Form fields of the request body: `postData.params` when recorded,
+/// otherwise the decoded `application/x-www-form-urlencoded` text
+pub fn form_params(entry: &Entry) -> Vec<FormParam> {
+    let Some(ref post_data) = entry.request.post_data else {
+        return Vec::new();
+    };
+
+    if let Some(ref params) = post_data.params {
+        if !params.is_empty() {
+            return params
+                .iter()
+                .map(|p| FormParam {
+                    name: p.name.clone(),
+                    value: p.value.clone(),
+                    file_name: p.file_name.clone(),
+                    content_type: p.content_type.clone(),
+                })
+                .collect();
+        }
+    }
+
+    let is_urlencoded = post_data.mime_type
+        .to_ascii_lowercase()
+        .starts_with("application/x-www-form-urlencoded");
+    match post_data.text {
+        Some(ref text) if is_urlencoded => parse_urlencoded(text),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_urlencoded(text: &str) -> Vec<FormParam> {
+    text.trim()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            FormParam {
+                name: url_decode(name),
+                value: Some(url_decode(value)),
+                file_name: None,
+                content_type: None,
+            }
+        })
+        .collect()
+}
+
+/// Value column text; file uploads show the file name and type instead
+fn display_value(param: &FormParam) -> String {
+    match (&param.file_name, &param.content_type) {
+        (Some(file), Some(mime)) => format!("[file] {} ({})", file, mime),
+        (Some(file), None) => format!("[file] {}", file),
+        _ => param.value.clone().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn post(post_data: serde_json::Value) -> Entry {
+        let json = fixtures::entry_with("POST", "https://example.com/login", 200, |e| {
+            e["request"]["postData"] = post_data;
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_urlencoded_text_fallback() {
+        let entry = post(serde_json::json!({
+            "mimeType": "application/x-www-form-urlencoded; charset=UTF-8",
+            "text": "user=jane+doe&next=%2Fhome&flag"
+        }));
+
+        let params = form_params(&entry);
+        let pairs: Vec<(&str, Option<&str>)> = params.iter().map(|p| (p.name.as_str(), p.value.as_deref())).collect();
+        assert_eq!(pairs, [("user", Some("jane doe")), ("next", Some("/home")), ("flag", Some(""))]);
+
+        let json = post(serde_json::json!({"mimeType": "application/json", "text": "{\"a\":1}"}));
+        assert!(form_params(&json).is_empty());
+    }
+
+    #[test]
+    fn test_params_array_with_file() {
+        let entry = post(serde_json::json!({
+            "mimeType": "multipart/form-data; boundary=x",
+            "params": [
+                {"name": "title", "value": "Holiday"},
+                {"name": "photo", "fileName": "beach.jpg", "contentType": "image/jpeg"}
+            ],
+            "text": "ignored=1"
+        }));
+
+        let params = form_params(&entry);
+        assert_eq!(params.len(), 2);
+        assert_eq!(display_value(&params[0]), "Holiday");
+        assert_eq!(display_value(&params[1]), "[file] beach.jpg (image/jpeg)");
+
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json[0], serde_json::json!({"name": "title", "value": "Holiday"}));
+        assert_eq!(json[1]["fileName"], "beach.jpg");
+        assert_eq!(json[1]["contentType"], "image/jpeg");
+        assert!(json[1].get("value").is_none());
+    }
+}
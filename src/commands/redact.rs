@@ -0,0 +1,452 @@
+use crate::har::{Content, Cookie, Entry, Har, Header};
+use crate::output::url_decode;
+use anyhow::{Context, Result};
+use clap::Args;
+use regex::{Captures, Regex};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Replacement for every redacted value
+pub const REDACTED: &str = "***REDACTED***";
+
+/// Headers redacted unless `--no-defaults` is given
+const DEFAULT_HEADERS: &[&str] = &["Authorization", "Proxy-Authorization", "Cookie", "Set-Cookie"];
+
+/// Headers whose values are URLs, so their query parameters are redacted like the request URL
+const URL_HEADERS: &[&str] = &["Referer", "Location", "Origin", "Content-Location"];
+
+/// Query and form parameters redacted unless `--no-defaults` is given
+const DEFAULT_QUERY: &[&str] = &[
+    "token", "access_token", "refresh_token", "id_token", "api_key", "apikey", "client_secret", "password",
+];
+
+#[derive(Debug, Args)]
+pub struct RedactCmd {
+    /// HAR file to redact (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Redact the value of this header (repeatable, case-insensitive)
+    #[arg(long = "header", value_name = "NAME")]
+    pub headers: Vec<String>,
+
+    /// Redact this cookie in cookie lists and Cookie/Set-Cookie headers (repeatable)
+    #[arg(long = "cookie", value_name = "NAME")]
+    pub cookies: Vec<String>,
+
+    /// Redact this query or form parameter (repeatable)
+    #[arg(long = "query", value_name = "NAME")]
+    pub query: Vec<String>,
+
+    /// Redact regex matches in request and response bodies; with a capture
+    /// group only the first group is replaced (repeatable)
+    #[arg(long = "body-regex", value_name = "REGEX")]
+    pub body_regex: Vec<String>,
+
+    /// Only apply the rules given on the command line
+    #[arg(long)]
+    pub no_defaults: bool,
+
+    /// Write the result to a file instead of stdout
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Emit compact JSON instead of pretty-printed
+    #[arg(long)]
+    pub minify: bool,
+}
+
+impl RedactCmd {
+    pub fn run(&self, mut har: Har) -> Result<()> {
+        Redactor::new(self)?.redact_har(&mut har);
+
+        match self.output_file {
+            Some(ref path) => {
+                let file = File::create(path)
+                    .with_context(|| format!("Failed to create file: {}", path))?;
+                self.write(BufWriter::new(file), &har)
+            }
+            None => self.write(BufWriter::new(io::stdout().lock()), &har),
+        }
+    }
+
+    fn write<W: Write>(&self, mut out: W, har: &Har) -> Result<()> {
+        if self.minify {
+            serde_json::to_writer(&mut out, har)?;
+        } else {
+            serde_json::to_writer_pretty(&mut out, har)?;
+        }
+        writeln!(out)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Compiled redaction rules. Names are matched case-insensitively.
+///
+/// Only values are replaced: names, entry order and the recorded
+/// `headersSize`/`bodySize`/`content.size` are kept as captured.
+pub struct Redactor {
+    headers: Vec<String>,
+    cookies: Vec<String>,
+    query: Vec<String>,
+    body: Vec<Regex>,
+}
+
+impl Redactor {
+    pub fn new(cmd: &RedactCmd) -> Result<Self> {
+        let defaults = |names: &[&str]| -> Vec<String> {
+            if cmd.no_defaults {
+                Vec::new()
+            } else {
+                names.iter().map(|n| n.to_string()).collect()
+            }
+        };
+        let lower = |names: Vec<String>, extra: &[String]| -> Vec<String> {
+            names.iter().chain(extra).map(|n| n.to_lowercase()).collect()
+        };
+
+        let body = cmd
+            .body_regex
+            .iter()
+            .map(|re| Regex::new(re).with_context(|| format!("Invalid --body-regex '{}'", re)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            headers: lower(defaults(DEFAULT_HEADERS), &cmd.headers),
+            cookies: lower(Vec::new(), &cmd.cookies),
+            query: lower(defaults(DEFAULT_QUERY), &cmd.query),
+            body,
+        })
+    }
+
+    /// Redact every entry, and page titles, which browsers often set to the page URL
+    pub fn redact_har(&self, har: &mut Har) {
+        for page in har.log.pages.iter_mut().flatten() {
+            page.title = self.redact_url(&page.title);
+        }
+        for entry in &mut har.log.entries {
+            self.redact_entry(entry);
+        }
+    }
+
+    pub fn redact_entry(&self, entry: &mut Entry) {
+        let request = &mut entry.request;
+        self.redact_headers(&mut request.headers);
+        self.redact_cookies(&mut request.cookies, "cookie");
+        request.url = self.redact_url(&request.url);
+        for param in &mut request.query_string {
+            if self.is_query(&param.name) {
+                param.value = REDACTED.to_string();
+            }
+        }
+        if let Some(ref mut post_data) = request.post_data {
+            for param in post_data.params.iter_mut().flatten() {
+                if self.is_query(&param.name) && param.value.is_some() {
+                    param.value = Some(REDACTED.to_string());
+                }
+            }
+            if let Some(ref mut text) = post_data.text {
+                if post_data.mime_type.starts_with("application/x-www-form-urlencoded") {
+                    *text = self.redact_params(text);
+                }
+                *text = self.redact_body(text);
+            }
+        }
+
+        let response = &mut entry.response;
+        self.redact_headers(&mut response.headers);
+        self.redact_cookies(&mut response.cookies, "set-cookie");
+        if let Some(ref mut redirect_url) = response.redirect_url {
+            *redirect_url = self.redact_url(redirect_url);
+        }
+        self.redact_content(&mut response.content);
+    }
+
+    fn is_header(&self, name: &str) -> bool {
+        self.headers.contains(&name.to_lowercase())
+    }
+
+    fn is_cookie(&self, name: &str) -> bool {
+        self.cookies.contains(&name.trim().to_lowercase())
+    }
+
+    fn is_query(&self, name: &str) -> bool {
+        self.query.contains(&url_decode(name).to_lowercase())
+    }
+
+    fn redact_headers(&self, headers: &mut [Header]) {
+        for h in headers {
+            if self.is_header(&h.name) {
+                h.value = REDACTED.to_string();
+            } else if h.name.eq_ignore_ascii_case("cookie") {
+                h.value = self.redact_cookie_header(&h.value);
+            } else if h.name.eq_ignore_ascii_case("set-cookie") {
+                h.value = self.redact_set_cookie(&h.value);
+            } else if URL_HEADERS.iter().any(|name| h.name.eq_ignore_ascii_case(name)) {
+                h.value = self.redact_url(&h.value);
+            }
+        }
+    }
+
+    /// Redact parsed cookies: all of them when their header is redacted,
+    /// otherwise only the named ones
+    fn redact_cookies(&self, cookies: &mut [Cookie], header: &str) {
+        let all = self.is_header(header);
+        for c in cookies {
+            if all || self.is_cookie(&c.name) {
+                c.value = REDACTED.to_string();
+            }
+        }
+    }
+
+    /// `a=1; session=abc` with named cookies redacted
+    fn redact_cookie_header(&self, value: &str) -> String {
+        value
+            .split(';')
+            .map(|pair| match pair.split_once('=') {
+                Some((name, _)) if self.is_cookie(name) => format!("{}={}", name, REDACTED),
+                _ => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// `Set-Cookie` value with the cookie redacted when named; attributes are kept.
+    /// Multiple cookies folded into one header are separated by newlines.
+    fn redact_set_cookie(&self, value: &str) -> String {
+        value
+            .split('\n')
+            .map(|line| {
+                let (pair, attributes) = line.split_once(';').map_or((line, None), |(p, a)| (p, Some(a)));
+                match pair.split_once('=') {
+                    Some((name, _)) if self.is_cookie(name) => {
+                        let mut out = format!("{}={}", name, REDACTED);
+                        if let Some(attributes) = attributes {
+                            out.push(';');
+                            out.push_str(attributes);
+                        }
+                        out
+                    }
+                    _ => line.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// URL with redacted query parameters; the fragment is left alone
+    fn redact_url(&self, url: &str) -> String {
+        let (before_fragment, fragment) = url.split_once('#').map_or((url, None), |(u, f)| (u, Some(f)));
+        let Some((base, query)) = before_fragment.split_once('?') else {
+            return url.to_string();
+        };
+
+        let mut out = format!("{}?{}", base, self.redact_params(query));
+        if let Some(fragment) = fragment {
+            out.push('#');
+            out.push_str(fragment);
+        }
+        out
+    }
+
+    /// `a=1&token=abc` with named parameters redacted
+    fn redact_params(&self, params: &str) -> String {
+        params
+            .split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((name, _)) if self.is_query(name) => format!("{}={}", name, REDACTED),
+                _ => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    fn redact_body(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for re in &self.body {
+            text = re
+                .replace_all(&text, |caps: &Captures| {
+                    let whole = caps.get(0).unwrap();
+                    match caps.get(1) {
+                        Some(group) => format!(
+                            "{}{}{}",
+                            &whole.as_str()[..group.start() - whole.start()],
+                            REDACTED,
+                            &whole.as_str()[group.end() - whole.start()..]
+                        ),
+                        None => REDACTED.to_string(),
+                    }
+                })
+                .into_owned();
+        }
+        text
+    }
+
+    /// Apply body rules to response text, through base64 if it is encoded.
+    /// Bodies that aren't UTF-8 once decoded are left untouched.
+    fn redact_content(&self, content: &mut Content) {
+        if self.body.is_empty() {
+            return;
+        }
+        let Some(text) = content.decoded_text().and_then(|bytes| String::from_utf8(bytes).ok()) else {
+            return;
+        };
+
+        let redacted = self.redact_body(&text);
+        if redacted == text {
+            return;
+        }
+        content.text = Some(if content.encoding.as_deref() == Some("base64") {
+            use base64::{Engine as _, engine::general_purpose::STANDARD};
+            STANDARD.encode(redacted)
+        } else {
+            redacted
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::parse_args;
+    use crate::har::fixtures;
+    use serde_json::json;
+
+    fn redactor(args: &[&str]) -> Redactor {
+        let cmd = parse_args::<RedactCmd>(args);
+        Redactor::new(&cmd).unwrap()
+    }
+
+    fn sensitive_entry() -> serde_json::Value {
+        fixtures::entry_with("POST", "https://api.example.com/login?user=ann&access_token=abc#top", 200, |e| {
+            e["request"]["headers"] = json!([
+                {"name": "authorization", "value": "Bearer secret"},
+                {"name": "Cookie", "value": "theme=dark; session=s3cr3t"},
+                {"name": "Accept", "value": "*/*"}
+            ]);
+            e["request"]["cookies"] = json!([{"name": "session", "value": "s3cr3t"}]);
+            e["request"]["queryString"] = json!([
+                {"name": "user", "value": "ann"},
+                {"name": "access_token", "value": "abc"}
+            ]);
+            e["request"]["headersSize"] = json!(321);
+            e["request"]["postData"] = json!({
+                "mimeType": "application/x-www-form-urlencoded",
+                "text": "user=ann&password=hunter2"
+            });
+            e["response"]["headers"] = json!([
+                {"name": "Set-Cookie", "value": "session=new; Path=/; HttpOnly"}
+            ]);
+            e["response"]["content"] = json!({
+                "size": 42,
+                "mimeType": "application/json",
+                "text": "{\"email\":\"ann@example.com\",\"id\":1}"
+            });
+        })
+    }
+
+    fn redacted(args: &[&str]) -> Entry {
+        let mut entry: Entry = serde_json::from_value(sensitive_entry()).unwrap();
+        redactor(args).redact_entry(&mut entry);
+        entry
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let entry = redacted(&[]);
+
+        assert_eq!(entry.request_header("Authorization"), Some(REDACTED));
+        assert_eq!(entry.request_header("Cookie"), Some(REDACTED));
+        assert_eq!(entry.request_header("Accept"), Some("*/*"));
+        assert_eq!(entry.request.cookies[0].value, REDACTED);
+        assert_eq!(entry.response_header("Set-Cookie"), Some(REDACTED));
+
+        assert_eq!(entry.request.url, "https://api.example.com/login?user=ann&access_token=***REDACTED***#top");
+        assert_eq!(entry.request.query_string[0].value, "ann");
+        assert_eq!(entry.request.query_string[1].value, REDACTED);
+        assert_eq!(entry.request.post_data.unwrap().text.unwrap(), "user=ann&password=***REDACTED***");
+
+        // Sizes stay as recorded
+        assert_eq!(entry.request.headers_size, 321);
+        assert_eq!(entry.response.content.size, 42);
+    }
+
+    #[test]
+    fn test_named_cookie_inside_headers() {
+        let entry = redacted(&["--no-defaults", "--cookie", "Session"]);
+
+        assert_eq!(entry.request_header("Authorization"), Some("Bearer secret"));
+        assert_eq!(entry.request_header("Cookie"), Some("theme=dark; session=***REDACTED***"));
+        assert_eq!(entry.response_header("Set-Cookie"), Some("session=***REDACTED***; Path=/; HttpOnly"));
+        assert_eq!(entry.request.cookies[0].value, REDACTED);
+        assert!(entry.request.url.contains("access_token=abc"));
+    }
+
+    #[test]
+    fn test_body_regex_with_and_without_group() {
+        let entry = redacted(&["--body-regex", r#""email":"([^"]*)""#, "--body-regex", "hunter2"]);
+
+        assert_eq!(entry.response.content.text.as_deref(), Some("{\"email\":\"***REDACTED***\",\"id\":1}"));
+        assert!(!entry.request.post_data.unwrap().text.unwrap().contains("hunter2"));
+
+        let mut e = sensitive_entry();
+        e["response"]["content"]["text"] = json!("c2VjcmV0IGJvZHk=");
+        e["response"]["content"]["encoding"] = json!("base64");
+        let mut entry: Entry = serde_json::from_value(e).unwrap();
+        redactor(&["--body-regex", "secret"]).redact_entry(&mut entry);
+        assert_eq!(entry.response_text().as_deref(), Some("***REDACTED*** body"));
+    }
+
+    #[test]
+    fn test_output_is_valid_har() {
+        let mut har = fixtures::har(vec![sensitive_entry(), fixtures::entry_json("GET", "https://example.com/", 200)]);
+        let redactor = redactor(&["--header", "x-api-key"]);
+        for entry in &mut har.log.entries {
+            redactor.redact_entry(entry);
+        }
+
+        let json = serde_json::to_string(&har).unwrap();
+        assert!(!json.contains("Bearer secret") && !json.contains("s3cr3t"));
+        let parsed: Har = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.log.entries.len(), 2);
+        assert_eq!(parsed.log.entries[1].request.url, "https://example.com/");
+    }
+
+    #[test]
+    fn test_token_is_gone_from_url_headers_and_page_titles() {
+        let e = fixtures::entry_with("GET", "https://example.com/app?token=SECRET1", 302, |e| {
+            e["request"]["headers"] = json!([
+                {"name": "Referer", "value": "https://example.com/start?token=SECRET1&tab=2"},
+                {"name": "origin", "value": "https://example.com"}
+            ]);
+            e["response"]["headers"] = json!([
+                {"name": "Location", "value": "https://example.com/next?api_key=SECRET2#done"},
+                {"name": "Content-Location", "value": "/app?token=SECRET1"}
+            ]);
+        });
+        let mut har = fixtures::har(vec![e]);
+        har.log.pages = Some(vec![serde_json::from_value(json!({
+            "startedDateTime": "2024-01-01T00:00:00.000Z",
+            "id": "page_1",
+            "title": "https://example.com/start?token=SECRET1",
+            "pageTimings": {}
+        }))
+        .unwrap()]);
+
+        redactor(&[]).redact_har(&mut har);
+
+        let json = serde_json::to_string(&har).unwrap();
+        assert!(!json.contains("SECRET1") && !json.contains("SECRET2"), "{}", json);
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.request_header("Referer"), Some("https://example.com/start?token=***REDACTED***&tab=2"));
+        assert_eq!(entry.request_header("Origin"), Some("https://example.com"));
+        assert_eq!(entry.response_header("Location"), Some("https://example.com/next?api_key=***REDACTED***#done"));
+    }
+
+    #[test]
+    fn test_invalid_regex_errors() {
+        let cmd = parse_args::<RedactCmd>(&["--body-regex", "("]);
+        assert!(Redactor::new(&cmd).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use crate::har::{Entry, Har};
+use crate::output::OutputFormat;
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use crate::output::table::{colorize_status, style_table};
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct RedirectsCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Flag chains with more redirects than this
+    #[arg(long, default_value = "5")]
+    pub max_hops: usize,
+
+    /// Only show loops and chains over --max-hops
+    #[arg(long)]
+    pub problems: bool,
+}
+
+/// Entries linked by 3xx responses whose target was requested next
+#[derive(Debug, serde::Serialize)]
+pub struct RedirectChain {
+    /// 1-based indexes of the entries in the chain
+    pub entries: Vec<usize>,
+    pub urls: Vec<String>,
+    pub statuses: Vec<i32>,
+    /// Status of the last captured entry
+    pub final_status: i32,
+    pub hops: usize,
+    /// Redirect target of the last entry when no later entry requested it
+    pub missing_target: Option<String>,
+    pub is_loop: bool,
+    pub too_long: bool,
+}
+
+#[derive(Tabled)]
+struct RedirectRow {
+    #[tabled(rename = "#")]
+    index: usize,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Hops")]
+    hops: usize,
+    #[tabled(rename = "Chain")]
+    chain: String,
+    #[tabled(rename = "Flags")]
+    flags: String,
+}
+
+impl RedirectsCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let chains: Vec<RedirectChain> = find_chains(&har.log.entries, self.max_hops)
+            .into_iter()
+            .filter(|c| !self.problems || c.is_loop || c.too_long)
+            .collect();
+
+        match self.output {
+            OutputFormat::Json => print_json(&chains, true)?,
+            OutputFormat::Ndjson => print_ndjson(&chains)?,
+            OutputFormat::Yaml => print_yaml(&chains)?,
+            OutputFormat::Compact => {
+                for c in &chains {
+                    println!("{}\t{}\t{}\t{}\t{}", c.entries[0], c.final_status, c.hops, format_chain(c), format_flags(c, self.max_hops));
+                }
+            }
+            OutputFormat::Table => {
+                if chains.is_empty() {
+                    println!("No redirects found.");
+                    return Ok(());
+                }
+
+                let rows: Vec<RedirectRow> = chains
+                    .iter()
+                    .map(|c| RedirectRow {
+                        index: c.entries[0],
+                        status: colorize_status(c.final_status),
+                        hops: c.hops,
+                        chain: format_chain(c),
+                        flags: format_flags(c, self.max_hops).red().to_string(),
+                    })
+                    .collect();
+
+                let mut table = Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `A -> B -> C`, ending with the uncaptured target if there is one
+fn format_chain(chain: &RedirectChain) -> String {
+    let mut urls: Vec<&str> = chain.urls.iter().map(String::as_str).collect();
+    if let Some(ref target) = chain.missing_target {
+        urls.push(target);
+    }
+    urls.join(" -> ")
+}
+
+fn format_flags(chain: &RedirectChain, max_hops: usize) -> String {
+    let mut flags = Vec::new();
+    if chain.is_loop {
+        flags.push("loop".to_string());
+    }
+    if chain.too_long {
+        flags.push(format!("over {} hops", max_hops));
+    }
+    if chain.missing_target.is_some() && !chain.is_loop {
+        flags.push("target not captured".to_string());
+    }
+    flags.join(", ")
+}
+
+/// Follow every 3xx response to the next entry that requested its target.
+///
+/// An entry belongs to at most one chain. The target of a redirect is the
+/// first later, unclaimed entry with that exact URL (ignoring fragments), so
+/// a loop still consumes each repeated request once instead of cycling.
+pub fn find_chains(entries: &[Entry], max_hops: usize) -> Vec<RedirectChain> {
+    let mut by_url: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, e) in entries.iter().enumerate() {
+        by_url.entry(strip_fragment(&e.request.url)).or_default().push(i);
+    }
+
+    let mut claimed = vec![false; entries.len()];
+    let mut chains = Vec::new();
+
+    for start in 0..entries.len() {
+        if claimed[start] || redirect_target(&entries[start]).is_none() {
+            continue;
+        }
+        claimed[start] = true;
+
+        let mut chain = vec![start];
+        let mut seen: HashSet<&str> = HashSet::from([strip_fragment(&entries[start].request.url)]);
+        let mut is_loop = false;
+        let mut missing_target = None;
+
+        let mut current = start;
+        while let Some(target) = redirect_target(&entries[current]) {
+            let key = strip_fragment(&target);
+            let next = by_url
+                .get(key)
+                .and_then(|candidates| candidates.iter().copied().find(|&j| j > current && !claimed[j]));
+
+            // Seeing a URL twice means the chain came back to where it was
+            is_loop |= seen.contains(key);
+
+            match next {
+                Some(j) => {
+                    claimed[j] = true;
+                    seen.insert(strip_fragment(&entries[j].request.url));
+                    chain.push(j);
+                    current = j;
+                }
+                None => {
+                    missing_target = Some(target);
+                    break;
+                }
+            }
+        }
+
+        let hops = chain.len() - 1 + usize::from(missing_target.is_some());
+        chains.push(RedirectChain {
+            entries: chain.iter().map(|&i| i + 1).collect(),
+            urls: chain.iter().map(|&i| entries[i].request.url.clone()).collect(),
+            statuses: chain.iter().map(|&i| entries[i].response.status).collect(),
+            final_status: entries[current].response.status,
+            hops,
+            missing_target,
+            is_loop,
+            too_long: hops > max_hops,
+        });
+    }
+
+    chains
+}
+
+/// Absolute URL a 3xx response points to, from `redirectURL` or `Location`
+pub fn redirect_target(entry: &Entry) -> Option<String> {
+    if !(300..400).contains(&entry.response.status) {
+        return None;
+    }
+
+    let location = entry
+        .response
+        .redirect_url
+        .as_deref()
+        .filter(|u| !u.is_empty())
+        .or_else(|| entry.response_header("location"))?;
+
+    Some(resolve_location(&entry.request.url, location.trim()))
+}
+
+/// Resolve a possibly relative `Location` against the request URL
+fn resolve_location(base: &str, location: &str) -> String {
+    if location.contains("://") {
+        return location.to_string();
+    }
+
+    let Some((scheme, rest)) = base.split_once("://") else {
+        return location.to_string();
+    };
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let origin = &base[..scheme.len() + 3 + authority_end];
+
+    if let Some(network_path) = location.strip_prefix("//") {
+        return format!("{}://{}", scheme, network_path);
+    }
+    if location.starts_with('/') {
+        return format!("{}{}", origin, location);
+    }
+
+    let path = rest[authority_end..].split(['?', '#']).next().unwrap_or("");
+    if location.starts_with('?') {
+        return format!("{}{}{}", origin, path, location);
+    }
+
+    let dir = path.rfind('/').map_or("/", |i| &path[..=i]);
+    format!("{}{}{}", origin, dir, location)
+}
+
+fn strip_fragment(url: &str) -> &str {
+    url.split('#').next().unwrap_or(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+    use serde_json::{json, Value};
+
+    fn redirect(url: &str, status: i32, location: &str) -> Value {
+        fixtures::entry_with("GET", url, status, |e| {
+            e["response"]["headers"] = json!([{"name": "Location", "value": location}]);
+        })
+    }
+
+    fn chains(entries: Vec<Value>, max_hops: usize) -> Vec<RedirectChain> {
+        find_chains(&fixtures::har(entries).log.entries, max_hops)
+    }
+
+    #[test]
+    fn test_resolve_location() {
+        let base = "https://example.com/a/b?x=1";
+        assert_eq!(resolve_location(base, "http://other.com/"), "http://other.com/");
+        assert_eq!(resolve_location(base, "//cdn.example.com/c"), "https://cdn.example.com/c");
+        assert_eq!(resolve_location(base, "/login"), "https://example.com/login");
+        assert_eq!(resolve_location(base, "c"), "https://example.com/a/c");
+        assert_eq!(resolve_location(base, "?y=2"), "https://example.com/a/b?y=2");
+        assert_eq!(resolve_location("https://example.com", "next"), "https://example.com/next");
+    }
+
+    #[test]
+    fn test_chain_follows_redirect_url_and_location() {
+        let first = fixtures::entry_with("GET", "http://example.com/", 301, |e| {
+            e["response"]["redirectURL"] = json!("https://example.com/");
+        });
+        let result = chains(vec![
+            first,
+            fixtures::entry_json("GET", "https://example.com/style.css", 200),
+            redirect("https://example.com/", 302, "/home"),
+            fixtures::entry_json("GET", "https://example.com/home", 200),
+        ], 5);
+
+        assert_eq!(result.len(), 1);
+        let chain = &result[0];
+        assert_eq!(chain.entries, [1, 3, 4]);
+        assert_eq!(chain.statuses, [301, 302, 200]);
+        assert_eq!(chain.final_status, 200);
+        assert_eq!(chain.hops, 2);
+        assert!(!chain.is_loop && !chain.too_long && chain.missing_target.is_none());
+        assert_eq!(format_chain(chain), "http://example.com/ -> https://example.com/ -> https://example.com/home");
+    }
+
+    #[test]
+    fn test_loop_and_max_hops() {
+        let result = chains(vec![
+            redirect("https://example.com/a", 302, "/b"),
+            redirect("https://example.com/b", 302, "/a"),
+            redirect("https://example.com/a", 302, "/b"),
+        ], 2);
+
+        assert_eq!(result.len(), 1);
+        let chain = &result[0];
+        assert_eq!(chain.entries, [1, 2, 3]);
+        assert!(chain.is_loop);
+        assert_eq!(chain.hops, 3);
+        assert!(chain.too_long);
+        assert_eq!(format_flags(chain, 2), "loop, over 2 hops");
+    }
+
+    #[test]
+    fn test_uncaptured_target() {
+        let result = chains(vec![
+            fixtures::entry_json("GET", "https://example.com/", 200),
+            redirect("https://example.com/old", 301, "https://example.com/new"),
+            fixtures::entry_json("GET", "https://example.com/", 304),
+        ], 5);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].entries, [2]);
+        assert_eq!(result[0].final_status, 301);
+        assert_eq!(result[0].missing_target.as_deref(), Some("https://example.com/new"));
+        assert!(!result[0].is_loop);
+    }
+}
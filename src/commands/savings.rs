@@ -0,0 +1,219 @@
+use crate::commands::content_types::ContentTypeGrouping;
+use crate::har::{Entry, Har};
+use crate::output::{format_bytes, OutputFormat};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use crate::output::table::style_table;
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use std::collections::HashMap;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct SavingsCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Group by exact content type or by broad category
+    #[arg(short, long, default_value = "exact")]
+    pub group: ContentTypeGrouping,
+}
+
+/// Bytes compression saved for one content type, or overall
+#[derive(Debug, Default, Serialize)]
+pub struct Savings {
+    pub content_type: String,
+    pub count: usize,
+    pub decoded_bytes: i64,
+    pub transfer_bytes: i64,
+    pub saved_bytes: i64,
+    /// Share of the decoded bytes that didn't go over the wire, 0.0-1.0
+    pub saved_ratio: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SavingsReport {
+    pub total: Savings,
+    pub by_type: Vec<Savings>,
+}
+
+#[derive(Tabled)]
+struct SavingsRow {
+    #[tabled(rename = "Type")]
+    content_type: String,
+    #[tabled(rename = "Requests")]
+    count: usize,
+    #[tabled(rename = "Decoded")]
+    decoded: String,
+    #[tabled(rename = "Transferred")]
+    transfer: String,
+    #[tabled(rename = "Saved")]
+    saved: String,
+    #[tabled(rename = "Ratio")]
+    ratio: String,
+}
+
+impl SavingsCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let report = collect_savings(har, self.group);
+
+        match self.output {
+            OutputFormat::Json => print_json(&report, true)?,
+            OutputFormat::Ndjson => print_ndjson(&report.by_type)?,
+            OutputFormat::Yaml => print_yaml(&report)?,
+            OutputFormat::Compact => {
+                for s in report.by_type.iter().chain([&report.total]) {
+                    println!("{}\t{}\t{}\t{}\t{}", s.content_type, s.count, s.decoded_bytes, s.transfer_bytes, s.saved_bytes);
+                }
+            }
+            OutputFormat::Table => {
+                if report.by_type.is_empty() {
+                    println!("No compressed responses found.");
+                    return Ok(());
+                }
+
+                let rows: Vec<SavingsRow> = report.by_type
+                    .iter()
+                    .chain([&report.total])
+                    .map(|s| SavingsRow {
+                        content_type: s.content_type.clone(),
+                        count: s.count,
+                        decoded: format_bytes(s.decoded_bytes),
+                        transfer: format_bytes(s.transfer_bytes),
+                        saved: format_bytes(s.saved_bytes),
+                        ratio: format!("{:.1}%", s.saved_ratio * 100.0),
+                    })
+                    .collect();
+
+                let mut table = Table::new(rows);
+                style_table(&mut table);
+                println!("{}", table);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sum decoded and wire body sizes of compressed responses, per content
+/// type (most bytes saved first) and overall
+pub fn collect_savings(har: &Har, group: ContentTypeGrouping) -> SavingsReport {
+    let mut groups: HashMap<String, Savings> = HashMap::new();
+    let mut total = Savings {
+        content_type: "Total".to_string(),
+        ..Savings::default()
+    };
+
+    for entry in &har.log.entries {
+        let Some((decoded, transfer)) = compressed_sizes(entry) else {
+            continue;
+        };
+        let savings = groups.entry(group.key(entry)).or_insert_with_key(|key| Savings {
+            content_type: key.clone(),
+            ..Savings::default()
+        });
+        for s in [savings, &mut total] {
+            s.count += 1;
+            s.decoded_bytes += decoded;
+            s.transfer_bytes += transfer;
+        }
+    }
+
+    let mut by_type: Vec<Savings> = groups.into_values().collect();
+    for s in by_type.iter_mut().chain([&mut total]) {
+        s.saved_bytes = s.decoded_bytes - s.transfer_bytes;
+        s.saved_ratio = if s.decoded_bytes > 0 {
+            s.saved_bytes as f64 / s.decoded_bytes as f64
+        } else {
+            0.0
+        };
+    }
+    by_type.sort_by(|a, b| b.saved_bytes.cmp(&a.saved_bytes).then_with(|| a.content_type.cmp(&b.content_type)));
+
+    SavingsReport { total, by_type }
+}
+
+/// (decoded, on-the-wire) body sizes of a compressed response.
+///
+/// None when the response wasn't compressed, or when either size is
+/// missing. A zero wire size is a cache hit rather than compression.
+fn compressed_sizes(entry: &Entry) -> Option<(i64, i64)> {
+    let content = &entry.response.content;
+    let encoded = entry
+        .response_header("content-encoding")
+        .is_some_and(|e| !e.trim().is_empty() && !e.trim().eq_ignore_ascii_case("identity"));
+    if !encoded && content.compression.is_none_or(|c| c <= 0) {
+        return None;
+    }
+
+    let decoded = entry.content_decoded_size()?;
+    let transfer = if entry.response.body_size >= 0 {
+        entry.response.body_size
+    } else {
+        content.size - content.compression.filter(|c| *c > 0)?
+    };
+    (decoded >= 0 && transfer > 0).then_some((decoded, transfer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn entry(mime: &str, encoding: Option<&str>, size: i64, body_size: i64) -> serde_json::Value {
+        let mut json = fixtures::entry_json("GET", "https://example.com/", 200);
+        json["response"]["content"] = serde_json::json!({"size": size, "mimeType": mime});
+        json["response"]["bodySize"] = serde_json::json!(body_size);
+        if let Some(encoding) = encoding {
+            json["response"]["headers"] = serde_json::json!([{"name": "Content-Encoding", "value": encoding}]);
+        }
+        json
+    }
+
+    #[test]
+    fn test_only_compressed_entries_count() {
+        let mut compression_field = entry("text/css", None, 500, -1);
+        compression_field["response"]["content"]["compression"] = serde_json::json!(300);
+        let har = fixtures::har(vec![
+            entry("application/javascript", Some("gzip"), 1000, 250),
+            entry("application/javascript", Some("br"), 3000, 750),
+            entry("text/html; charset=utf-8", Some("gzip"), 2000, 1500),
+            entry("image/png", None, 4000, 4000),
+            entry("text/html", Some("identity"), 900, 900),
+            entry("application/json", Some("gzip"), -1, -1),
+            entry("application/json", Some("gzip"), 800, 0),
+            compression_field,
+        ]);
+
+        let report = collect_savings(&har, ContentTypeGrouping::Exact);
+
+        let rows: Vec<(&str, usize, i64, i64, i64)> = report.by_type
+            .iter()
+            .map(|s| (s.content_type.as_str(), s.count, s.decoded_bytes, s.transfer_bytes, s.saved_bytes))
+            .collect();
+        assert_eq!(rows, [
+            ("application/javascript", 2, 4000, 1000, 3000),
+            ("text/html", 1, 2000, 1500, 500),
+            ("text/css", 1, 500, 200, 300),
+        ]);
+        assert!((report.by_type[0].saved_ratio - 0.75).abs() < 1e-9);
+
+        assert_eq!(report.total.count, 4);
+        assert_eq!((report.total.decoded_bytes, report.total.transfer_bytes, report.total.saved_bytes), (6500, 2700, 3800));
+    }
+
+    #[test]
+    fn test_uncompressed_har_is_empty() {
+        let har = fixtures::har(vec![entry("image/png", None, 4000, 4000)]);
+        let report = collect_savings(&har, ContentTypeGrouping::Coarse);
+        assert!(report.by_type.is_empty());
+        assert_eq!(report.total.count, 0);
+        assert_eq!(report.total.saved_ratio, 0.0);
+    }
+}
@@ -1,10 +1,17 @@
-use crate::har::{Har, Entry};
+use crate::commands::page::PageArgs;
+use crate::filter::matcher::Matcher;
+use crate::har::{self, Entry};
 use crate::output::OutputFormat;
-use crate::output::table::print_entries_table;
-use crate::output::json::print_summaries_json;
+use crate::output::highlight::highlight_spans;
+use crate::output::table::{style_table, EntryRow, SummaryFooter};
+use crate::output::json::{print_summaries_json, print_summaries_ndjson};
+use crate::output::yaml::print_summaries_yaml;
 use anyhow::Result;
 use clap::Args;
-use regex::Regex;
+use std::io::Read;
+use std::ops::{ControlFlow, Range};
+use std::path::Path;
+use tabled::Table;
 
 #[derive(Debug, Args)]
 pub struct SearchCmd {
@@ -28,6 +35,14 @@ pub struct SearchCmd {
     #[arg(short = 'r', long)]
     pub regex: bool,
 
+    /// Use a shell-style glob (`*`, `?`, `[abc]`) that must match the whole text
+    #[arg(short = 'g', long, conflicts_with_all = ["regex", "normalize_ws"])]
+    pub glob: bool,
+
+    /// Use a glob that may match any part of the text
+    #[arg(long, conflicts_with_all = ["regex", "normalize_ws", "glob"])]
+    pub glob_contains: bool,
+
     /// Search in headers
     #[arg(long)]
     pub headers: bool,
@@ -48,61 +63,270 @@ pub struct SearchCmd {
     #[arg(short = 'c', long)]
     pub count: bool,
 
+    /// Ignore whitespace differences (collapse runs, drop spaces around JSON punctuation)
+    #[arg(long, alias = "normalize-whitespace", conflicts_with = "regex")]
+    pub normalize_ws: bool,
+
+    /// Show at most N matches (ignored with --count)
+    #[arg(short = 'n', long)]
+    pub limit: Option<usize>,
+
+    /// Skip the first N matches (ignored with --count)
+    #[arg(long, default_value = "0")]
+    pub offset: usize,
+
     /// Maximum URL length for table output
     #[arg(long, default_value = "60")]
     pub max_url: usize,
+
+    /// Directory holding response bodies referenced by `_file`/`_content`
+    #[arg(long, value_name = "DIR")]
+    pub body_dir: Option<String>,
+
+    /// Show the whole line around each body match instead of a short snippet
+    #[arg(long)]
+    pub show_context: bool,
+
+    /// Print a footer with shown/matched counts and total bytes and time (table output)
+    #[arg(long)]
+    pub summary: bool,
+
+    #[command(flatten)]
+    pub page: PageArgs,
 }
 
-impl SearchCmd {
-    pub fn run(&self, har: &Har, color: bool) -> Result<()> {
-        let matcher = self.create_matcher()?;
+/// Bytes of text shown on each side of a body match
+const SNIPPET_CONTEXT: usize = 30;
 
-        let entries: Vec<(usize, &Entry)> = har.log.entries
-            .iter()
-            .enumerate()
-            .filter(|(_, e)| {
-                let matches = self.entry_matches(e, &matcher);
-                if self.invert { !matches } else { matches }
-            })
-            .map(|(i, e)| (i + 1, e))
-            .collect();
+/// Matching body lines shown per body before the rest are summarized
+const MAX_BODY_LINES: usize = 5;
+
+/// A header or body line that matched, printed under its entry
+#[derive(Debug)]
+struct MatchDetail {
+    location: String,
+    text: String,
+}
+
+impl SearchCmd {
+    pub fn run<R: Read>(&self, reader: R, color: bool) -> Result<()> {
+        let (match_count, matched) = self.search(reader)?;
 
         if self.count {
-            println!("{}", entries.len());
+            println!("{}", match_count);
             return Ok(());
         }
 
+        let entries: Vec<(usize, &Entry)> = matched.iter().map(|(i, e)| (*i, e)).collect();
+        let matcher = self.create_matcher()?;
+
         match self.output {
             OutputFormat::Json => print_summaries_json(&entries, true)?,
+            OutputFormat::Ndjson => print_summaries_ndjson(&entries)?,
+            OutputFormat::Yaml => print_summaries_yaml(&entries)?,
             OutputFormat::Compact => {
                 for (i, entry) in &entries {
-                    println!("{}\t{}\t{}", i, entry.request.method, entry.request.url);
+                    let url = &entry.request.url;
+                    // One row per entry; header and body matches are only listed in the table
+                    println!("{}\t{}\t{}", i, entry.request.method, highlight_spans(url, &matcher.find_spans(url), color));
+                }
+            }
+            OutputFormat::Table => {
+                self.print_table(&entries, &matcher, color);
+                if self.summary {
+                    println!("{}", SummaryFooter::new(&entries, match_count).render(color));
                 }
             }
-            OutputFormat::Table => print_entries_table(&entries, color, self.max_url),
         }
 
         Ok(())
     }
 
-    fn create_matcher(&self) -> Result<Matcher> {
-        if self.regex {
-            let pattern = if self.ignore_case {
-                format!("(?i){}", self.pattern)
+    /// Entry table with matches in the URL highlighted, then any header and
+    /// body matches
+    fn print_table(&self, entries: &[(usize, &Entry)], matcher: &Matcher, color: bool) {
+        if entries.is_empty() {
+            println!("No entries found.");
+            return;
+        }
+
+        let rows: Vec<EntryRow> = entries
+            .iter()
+            .map(|(i, e)| {
+                let mut row = EntryRow::from_entry(*i, e, color, self.max_url);
+                row.url = highlight_spans(&row.url, &matcher.find_spans(&row.url), color);
+                row
+            })
+            .collect();
+
+        let mut table = Table::new(rows);
+        style_table(&mut table);
+        println!("{}", table);
+
+        for (i, entry) in entries {
+            for detail in self.match_details(entry, matcher, color) {
+                println!("#{} {}: {}", i, detail.location, detail.text);
+            }
+        }
+    }
+
+    /// Headers and body lines that matched, for `--headers`/`--body` searches
+    fn match_details(&self, entry: &Entry, matcher: &Matcher, color: bool) -> Vec<MatchDetail> {
+        let mut details = Vec::new();
+        if self.invert {
+            return details;
+        }
+
+        if self.headers {
+            let sides = [("request header", &entry.request.headers), ("response header", &entry.response.headers)];
+            for (location, headers) in sides {
+                for h in headers {
+                    let name_spans = matcher.find_spans(&h.name);
+                    let value_spans = matcher.find_spans(&h.value);
+                    if name_spans.is_empty() && value_spans.is_empty() {
+                        continue;
+                    }
+                    details.push(MatchDetail {
+                        location: location.to_string(),
+                        text: format!(
+                            "{}: {}",
+                            highlight_spans(&h.name, &name_spans, color),
+                            highlight_spans(&h.value, &value_spans, color)
+                        ),
+                    });
+                }
+            }
+        }
+
+        if self.body {
+            if let Some(text) = entry.request.post_data.as_ref().and_then(|p| p.text.as_deref()) {
+                self.body_details("request body", text, matcher, color, &mut details);
+            }
+            if let Some(text) = entry.response_text() {
+                self.body_details("response body", &text, matcher, color, &mut details);
+            }
+        }
+
+        details
+    }
+
+    fn body_details(&self, location: &str, body: &str, matcher: &Matcher, color: bool, details: &mut Vec<MatchDetail>) {
+        let mut shown = 0;
+        let mut more = 0;
+
+        for (n, line) in body.lines().enumerate() {
+            let spans = matcher.find_spans(line);
+            if spans.is_empty() {
+                continue;
+            }
+            if shown == MAX_BODY_LINES {
+                more += 1;
+                continue;
+            }
+            let text = if self.show_context {
+                highlight_spans(line, &spans, color)
             } else {
-                self.pattern.clone()
+                snippet(line, &spans, color)
             };
-            let re = Regex::new(&pattern)?;
-            Ok(Matcher::Regex(re))
+            details.push(MatchDetail { location: format!("{} line {}", location, n + 1), text });
+            shown += 1;
+        }
+
+        // A match spanning lines (e.g. with --normalize-ws) isn't in any single line
+        if shown == 0 {
+            let spans = matcher.find_spans(body);
+            if !spans.is_empty() {
+                details.push(MatchDetail { location: location.to_string(), text: snippet(body, &spans, color) });
+            }
+        }
+
+        if more > 0 {
+            details.push(MatchDetail {
+                location: location.to_string(),
+                text: format!("{} more matching line(s)", more),
+            });
+        }
+    }
+
+    /// Count all matches and collect the page selected by --offset/--limit,
+    /// keeping each entry's original index
+    fn search<R: Read>(&self, reader: R) -> Result<(usize, Vec<(usize, Entry)>)> {
+        let matcher = self.create_matcher()?;
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        // Pages may come after the entries, so --page reads the whole HAR up
+        // front; otherwise stream the file so only matching entries are kept
+        let (full, reader) = if self.page.is_set() {
+            (Some(har::parse_reader(reader)?), None)
         } else {
-            Ok(Matcher::Text {
-                pattern: if self.ignore_case {
-                    self.pattern.to_lowercase()
-                } else {
-                    self.pattern.clone()
-                },
-                ignore_case: self.ignore_case,
-            })
+            (None, Some(reader))
+        };
+        let page = match &full {
+            Some(har) => self.page.resolve(har)?,
+            None => None,
+        };
+
+        let mut matched: Vec<(usize, Entry)> = Vec::new();
+        let mut match_count = 0usize;
+        let mut index = 0usize;
+        let body_dir = self.body_dir.as_deref().filter(|_| self.body).map(Path::new);
+        let mut load_error = None;
+
+        let mut visit = |mut entry: Entry| {
+            index += 1;
+            if page.as_ref().is_some_and(|p| !p.contains(&entry)) {
+                return ControlFlow::Continue(());
+            }
+            if let Some(dir) = body_dir {
+                if let Err(err) = har::load_external_body(&mut entry, dir) {
+                    load_error = Some(err);
+                    return ControlFlow::Break(());
+                }
+            }
+            let matches = self.entry_matches(&entry, &matcher);
+            if matches != self.invert {
+                match_count += 1;
+                if self.count {
+                    return ControlFlow::Continue(());
+                }
+                if match_count > self.offset && matched.len() < limit {
+                    matched.push((index, entry));
+                }
+                // The page is full; no need to read further unless the
+                // summary needs the total
+                if matched.len() >= limit && !self.summary {
+                    return ControlFlow::Break(());
+                }
+            }
+            ControlFlow::Continue(())
+        };
+
+        match (full, reader) {
+            (Some(har), _) => {
+                for entry in har.log.entries {
+                    if visit(entry).is_break() {
+                        break;
+                    }
+                }
+            }
+            (None, Some(reader)) => har::stream_entries(reader, &mut visit)?,
+            (None, None) => unreachable!(),
+        }
+
+        if let Some(err) = load_error {
+            return Err(err);
+        }
+
+        Ok((match_count, matched))
+    }
+
+    fn create_matcher(&self) -> Result<Matcher> {
+        if self.regex {
+            Matcher::regex(&self.pattern, self.ignore_case)
+        } else if self.glob || self.glob_contains {
+            Matcher::glob(&self.pattern, self.ignore_case, !self.glob_contains)
+        } else {
+            Ok(Matcher::text(&self.pattern, self.ignore_case, self.normalize_ws))
         }
     }
 
@@ -140,7 +364,7 @@ impl SearchCmd {
             }
 
             // Search response body (decode if base64)
-            if let Some(text) = entry.response.content.text_content() {
+            if let Some(text) = entry.response_text() {
                 if matcher.matches(&text) {
                     return true;
                 }
@@ -151,22 +375,121 @@ impl SearchCmd {
     }
 }
 
-enum Matcher {
-    Text { pattern: String, ignore_case: bool },
-    Regex(Regex),
+/// The text around the first match on one line, with matches inside it highlighted
+fn snippet(text: &str, spans: &[Range<usize>], color: bool) -> String {
+    let first = &spans[0];
+    let mut start = first.start.saturating_sub(SNIPPET_CONTEXT);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (first.end + SNIPPET_CONTEXT).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+
+    // Single-byte replacements keep the span offsets valid
+    let window = text[start..end].replace(['\n', '\r', '\t'], " ");
+    let inner: Vec<Range<usize>> = spans
+        .iter()
+        .filter(|s| s.start >= start && s.end <= end)
+        .map(|s| s.start - start..s.end - start)
+        .collect();
+
+    format!(
+        "{}{}{}",
+        if start > 0 { "..." } else { "" },
+        highlight_spans(&window, &inner, color),
+        if end < text.len() { "..." } else { "" }
+    )
 }
 
-impl Matcher {
-    fn matches(&self, text: &str) -> bool {
-        match self {
-            Matcher::Text { pattern, ignore_case } => {
-                if *ignore_case {
-                    text.to_lowercase().contains(pattern)
-                } else {
-                    text.contains(pattern)
-                }
-            }
-            Matcher::Regex(re) => re.is_match(text),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{parse_args, try_parse_args};
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_offset_and_limit_keep_original_index() {
+        // Ten /api/ entries with an unrelated entry before each
+        let mut jsons = Vec::new();
+        for i in 0..10 {
+            jsons.push(fixtures::entry_json("GET", &format!("https://cdn.example.com/{}.js", i), 200));
+            jsons.push(fixtures::entry_json("GET", &format!("https://example.com/api/{}", i), 200));
         }
+        let har = fixtures::har_bytes(jsons);
+
+        let cmd = parse_args::<SearchCmd>(&["/api/", "--offset", "3", "--limit", "2"]);
+        let (count, matched) = cmd.search(har.as_slice()).unwrap();
+        let indexes: Vec<usize> = matched.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indexes, [8, 10]);
+        assert!(count < 10, "stops reading once the page is full");
+
+        let cmd = parse_args::<SearchCmd>(&["/api/", "--offset", "3", "--limit", "2", "--count"]);
+        let (count, matched) = cmd.search(har.as_slice()).unwrap();
+        assert_eq!(count, 10);
+        assert!(matched.is_empty());
+
+        // --summary reads on to count every match, but still shows one page
+        let cmd = parse_args::<SearchCmd>(&["/api/", "--offset", "3", "--limit", "2", "--summary"]);
+        let (count, matched) = cmd.search(har.as_slice()).unwrap();
+        let entries: Vec<(usize, &Entry)> = matched.iter().map(|(i, e)| (*i, e)).collect();
+        let footer = SummaryFooter::new(&entries, count);
+        assert_eq!((footer.shown, footer.matched), (2, 10));
+        assert_eq!(footer.time_ms, 200.0);
+    }
+
+    #[test]
+    fn test_glob_matches_whole_url() {
+        let har = fixtures::har_bytes(vec![
+            fixtures::entry_json("GET", "https://example.com/api/users", 200),
+            fixtures::entry_json("GET", "https://cdn.example.com/app.js", 200),
+            fixtures::entry_json("GET", "http://example.com/api/health", 200),
+        ]);
+        let indexes = |args: &[&str]| -> Vec<usize> {
+            let cmd = parse_args::<SearchCmd>(args);
+            cmd.search(har.as_slice()).unwrap().1.iter().map(|(i, _)| *i).collect()
+        };
+
+        assert_eq!(indexes(&["--glob", "*/api/*"]), [1, 3]);
+        assert_eq!(indexes(&["-g", "?ttps://*"]), [1, 2]);
+        assert!(indexes(&["--glob", "api/*"]).is_empty());
+        assert_eq!(indexes(&["--glob-contains", "api/*"]), [1, 3]);
+        assert!(try_parse_args::<SearchCmd>(&["x", "--glob", "--regex"]).is_err());
+    }
+
+    #[test]
+    fn test_match_details_for_headers_and_body() {
+        let mut json = fixtures::entry_json("GET", "https://example.com/", 200);
+        json["response"]["headers"] = serde_json::json!([
+            {"name": "Content-Type", "value": "application/json"},
+            {"name": "X-Token", "value": "secret"}
+        ]);
+        let body = format!("{{\n  \"id\": 1,\n  \"note\": \"{}token{}\"\n}}", "a".repeat(40), "b".repeat(40));
+        json["response"]["content"] = serde_json::json!({"size": body.len(), "text": body});
+        let entry = fixtures::to_entry(json);
+
+        let cmd = parse_args::<SearchCmd>(&["-i", "token", "--headers", "--body"]);
+        let matcher = cmd.create_matcher().unwrap();
+        let details = cmd.match_details(&entry, &matcher, false);
+        let lines: Vec<String> = details.iter().map(|d| format!("{}: {}", d.location, d.text)).collect();
+
+        let snippet = format!("...{}token{}...", "a".repeat(30), "b".repeat(30));
+        assert_eq!(lines, [
+            "response header: X-Token: secret".to_string(),
+            format!("response body line 3: {}", snippet),
+        ]);
+
+        let cmd = parse_args::<SearchCmd>(&["token", "--body", "--show-context"]);
+        let details = cmd.match_details(&entry, &matcher, false);
+        assert!(details[0].text.starts_with("  \"note\": \"aaa"));
+    }
+
+    #[test]
+    fn test_snippet_respects_char_boundaries() {
+        let text = format!("{}needle{}", "é".repeat(20), "ü".repeat(20));
+        let spans = Matcher::text("needle", false, false).find_spans(&text);
+        let out = snippet(&text, &spans, false);
+        assert!(out.starts_with("...") && out.ends_with("...") && out.contains("needle"));
     }
 }
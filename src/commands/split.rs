@@ -0,0 +1,218 @@
+use crate::har::{Entry, Har};
+use crate::output::extract_host;
+use crate::output::json::write_har_part;
+use crate::output::table::style_table;
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct SplitCmd {
+    /// HAR file to split (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// How to group entries into files
+    #[arg(long, default_value = "host")]
+    pub by: SplitBy,
+
+    /// Directory to write the HAR files to (created if missing)
+    #[arg(short = 'd', long, value_name = "DIR")]
+    pub output_dir: String,
+
+    /// Emit compact JSON instead of pretty-printed
+    #[arg(long)]
+    pub minify: bool,
+}
+
+/// Grouping used by `split --by`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SplitBy {
+    /// One file per `pageref`; entries without one go to `no-page`
+    Page,
+    /// One file per request host
+    Host,
+    /// One file per status class: 2xx, 3xx, 4xx, 5xx, or `no-response`
+    StatusClass,
+}
+
+impl SplitBy {
+    fn key(self, entry: &Entry) -> String {
+        match self {
+            SplitBy::Page => entry.pageref.clone().unwrap_or_else(|| "no-page".to_string()),
+            SplitBy::Host => match extract_host(&entry.request.url) {
+                "" => "unknown".to_string(),
+                host => host.to_string(),
+            },
+            SplitBy::StatusClass => match entry.response.status {
+                100..=599 => format!("{}xx", entry.response.status / 100),
+                0 => "no-response".to_string(),
+                _ => "other".to_string(),
+            },
+        }
+    }
+}
+
+/// Entries of one group, with their 1-based indexes in the source HAR
+pub struct SplitGroup<'a> {
+    pub key: String,
+    pub entries: Vec<(usize, &'a Entry)>,
+}
+
+#[derive(Tabled)]
+struct WrittenRow {
+    #[tabled(rename = "File")]
+    file: String,
+    #[tabled(rename = "Group")]
+    key: String,
+    #[tabled(rename = "Entries")]
+    count: usize,
+}
+
+impl SplitCmd {
+    pub fn run(&self, har: &Har) -> Result<()> {
+        let dir = Path::new(&self.output_dir);
+        let written = self.write_groups(har, dir)?;
+
+        if written.is_empty() {
+            println!("No entries found.");
+            return Ok(());
+        }
+
+        let total: usize = written.iter().map(|row| row.count).sum();
+        let count = written.len();
+        let mut table = Table::new(written);
+        style_table(&mut table);
+        println!("{}", table);
+        println!("Wrote {} entries to {} files in {}", total, count, dir.display());
+        Ok(())
+    }
+
+    /// Write one HAR per group into `dir`, returning what was written
+    fn write_groups(&self, har: &Har, dir: &Path) -> Result<Vec<WrittenRow>> {
+        let groups = split_groups(har, self.by);
+        if !groups.is_empty() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+
+        let mut used = HashSet::new();
+        let mut written = Vec::new();
+        for group in groups {
+            let path = unique_path(dir, &sanitize_file_name(&group.key), &mut used);
+            let file = File::create(&path)
+                .with_context(|| format!("Failed to create file: {}", path.display()))?;
+            let mut out = BufWriter::new(file);
+            write_har_part(&mut out, har, &group.entries, !self.minify)?;
+            writeln!(out)?;
+            out.flush()?;
+
+            written.push(WrittenRow {
+                file: path.display().to_string(),
+                key: group.key,
+                count: group.entries.len(),
+            });
+        }
+        Ok(written)
+    }
+}
+
+/// Group entries by `by`, in order of each group's first entry
+pub fn split_groups(har: &Har, by: SplitBy) -> Vec<SplitGroup<'_>> {
+    let mut groups: Vec<SplitGroup> = Vec::new();
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let key = by.key(entry);
+        match groups.iter_mut().find(|g| g.key == key) {
+            Some(group) => group.entries.push((i + 1, entry)),
+            None => groups.push(SplitGroup { key, entries: vec![(i + 1, entry)] }),
+        }
+    }
+    groups
+}
+
+/// File stem safe on any platform: letters, digits, `.`, `-` and `_` are
+/// kept and everything else becomes `_`
+fn sanitize_file_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    let name = name.trim_matches('.');
+    if name.is_empty() { "_".to_string() } else { name.to_string() }
+}
+
+/// `dir/stem.har`, or `dir/stem-2.har` and so on if two keys sanitize alike
+fn unique_path(dir: &Path, stem: &str, used: &mut HashSet<String>) -> PathBuf {
+    let mut name = format!("{}.har", stem);
+    let mut n = 2;
+    while !used.insert(name.to_lowercase()) {
+        name = format!("{}-{}.har", stem, n);
+        n += 1;
+    }
+    dir.join(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::{self, fixtures};
+
+    #[test]
+    fn test_split_by_host_writes_valid_hars() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/", 200),
+            fixtures::entry_json("GET", "https://cdn.example.com/app.js", 200),
+            fixtures::entry_json("POST", "http://api.example.com:8080/login", 401),
+            fixtures::entry_json("GET", "https://example.com/about", 200),
+        ]);
+        let dir = std::env::temp_dir().join(format!("harq-split-{}", std::process::id()));
+        let cmd = SplitCmd {
+            file: "-".to_string(),
+            by: SplitBy::Host,
+            output_dir: dir.display().to_string(),
+            minify: true,
+        };
+
+        let written = cmd.write_groups(&har, &dir).unwrap();
+        let summary: Vec<(&str, usize)> = written.iter().map(|row| (row.key.as_str(), row.count)).collect();
+        assert_eq!(summary, [("example.com", 2), ("cdn.example.com", 1), ("api.example.com", 1)]);
+
+        let part = har::parse_file(dir.join("example.com.har")).unwrap();
+        let urls: Vec<&str> = part.log.entries.iter().map(|e| e.request.url.as_str()).collect();
+        assert_eq!(urls, ["https://example.com/", "https://example.com/about"]);
+        assert_eq!(har::parse_file(dir.join("api.example.com.har")).unwrap().log.entries[0].response.status, 401);
+        assert!(dir.join("cdn.example.com.har").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_status_class_and_page_keys() {
+        let paged = fixtures::entry_with("GET", "https://example.com/", 302, |e| {
+            e["pageref"] = serde_json::json!("page_1");
+        });
+        let har = fixtures::har(vec![
+            paged,
+            fixtures::entry_json("GET", "https://example.com/a", 0),
+            fixtures::entry_json("GET", "https://example.com/b", 304),
+        ]);
+
+        let keys = |by| split_groups(&har, by).into_iter().map(|g| (g.key, g.entries.len())).collect::<Vec<_>>();
+        assert_eq!(keys(SplitBy::StatusClass), [("3xx".to_string(), 2), ("no-response".to_string(), 1)]);
+        assert_eq!(keys(SplitBy::Page), [("page_1".to_string(), 1), ("no-page".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_file_names_are_sanitized_and_unique() {
+        let mut used = HashSet::new();
+        let dir = Path::new("out");
+        assert_eq!(sanitize_file_name("../etc/passwd"), "_etc_passwd");
+        assert_eq!(sanitize_file_name("[::1]"), "___1_");
+        assert_eq!(unique_path(dir, "a_b", &mut used), dir.join("a_b.har"));
+        assert_eq!(unique_path(dir, "a_b", &mut used), dir.join("a_b-2.har"));
+    }
+}
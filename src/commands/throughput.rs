@@ -0,0 +1,241 @@
+use crate::har;
+use crate::output::{format_time, separator, OutputFormat};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::io::Read;
+use std::ops::ControlFlow;
+
+#[derive(Debug, Args)]
+pub struct ThroughputCmd {
+    /// HAR file to analyze (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+}
+
+/// Wall-clock span, request rate and concurrency of a capture.
+///
+/// Each entry is in flight from `startedDateTime` to that plus `time`. Rates
+/// and concurrency are null when there is no span to divide by.
+#[derive(Debug, Serialize)]
+pub struct Throughput {
+    pub requests: usize,
+    /// Entries whose `startedDateTime` couldn't be parsed, left out of the rest
+    pub skipped: usize,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub span_ms: Option<f64>,
+    pub requests_per_sec: Option<f64>,
+    /// Total request time over the span: the average number in flight
+    pub mean_concurrency: Option<f64>,
+    pub peak_concurrency: usize,
+    /// When the peak was first reached
+    pub peak_at: Option<String>,
+}
+
+/// When a request starts or ends, in milliseconds since the epoch
+struct Event {
+    at: f64,
+    starts: bool,
+    /// Order among events at the same instant: ends (0), then starts (1),
+    /// then ends of zero-length requests (2), which must follow their start
+    rank: u8,
+    time: DateTime<FixedOffset>,
+}
+
+impl Throughput {
+    fn from_intervals(intervals: &[(DateTime<FixedOffset>, f64)], skipped: usize) -> Self {
+        let millis = |t: &DateTime<FixedOffset>| t.timestamp_micros() as f64 / 1000.0;
+        let mut events = Vec::with_capacity(intervals.len() * 2);
+        let mut busy = 0.0;
+        for (start, time) in intervals {
+            let time = time.max(0.0);
+            let end = *start + chrono::Duration::microseconds((time * 1000.0) as i64);
+            busy += time;
+            events.push(Event { at: millis(start), starts: true, rank: 1, time: *start });
+            let rank = if time == 0.0 { 2 } else { 0 };
+            events.push(Event { at: millis(start) + time, starts: false, rank, time: end });
+        }
+        // A request ending as another starts doesn't overlap it
+        events.sort_by(|a, b| a.at.total_cmp(&b.at).then(a.rank.cmp(&b.rank)));
+
+        let mut in_flight = 0usize;
+        let mut peak = 0usize;
+        let mut peak_at = None;
+        for event in &events {
+            if event.starts {
+                in_flight += 1;
+                if in_flight > peak {
+                    peak = in_flight;
+                    peak_at = Some(event.time);
+                }
+            } else {
+                in_flight -= 1;
+            }
+        }
+
+        let first = events.iter().find(|e| e.starts);
+        let last = events.iter().rev().find(|e| !e.starts);
+        let span_ms = first.zip(last).map(|(first, last)| last.at - first.at);
+        let per_span = |value: f64, scale: f64| span_ms.filter(|&ms| ms > 0.0).map(|ms| value / ms * scale);
+        let format = |t: &DateTime<FixedOffset>| t.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+
+        Self {
+            requests: intervals.len(),
+            skipped,
+            start: first.map(|e| format(&e.time)),
+            end: last.map(|e| format(&e.time)),
+            span_ms,
+            requests_per_sec: per_span(intervals.len() as f64, 1000.0),
+            mean_concurrency: per_span(busy, 1.0),
+            peak_concurrency: peak,
+            peak_at: peak_at.as_ref().map(format),
+        }
+    }
+}
+
+impl ThroughputCmd {
+    pub fn run<R: Read>(&self, reader: R, color: bool) -> Result<()> {
+        let throughput = self.throughput(reader)?;
+
+        match self.output {
+            OutputFormat::Json => print_json(&throughput, true)?,
+            OutputFormat::Ndjson => print_ndjson([&throughput])?,
+            OutputFormat::Yaml => print_yaml(&throughput)?,
+            OutputFormat::Compact => {
+                let fmt = |v: Option<f64>| v.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string());
+                println!("{}\t{}\t{}\t{}\t{}",
+                    throughput.requests,
+                    fmt(throughput.span_ms),
+                    fmt(throughput.requests_per_sec),
+                    fmt(throughput.mean_concurrency),
+                    throughput.peak_concurrency
+                );
+            }
+            OutputFormat::Table => print_text(&throughput, color),
+        }
+
+        Ok(())
+    }
+
+    fn throughput<R: Read>(&self, reader: R) -> Result<Throughput> {
+        let mut intervals = Vec::new();
+        let mut skipped = 0usize;
+        har::stream_entries(reader, |entry| {
+            match entry.started_at() {
+                Some(start) => intervals.push((start, entry.time)),
+                None => skipped += 1,
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(Throughput::from_intervals(&intervals, skipped))
+    }
+}
+
+fn print_text(throughput: &Throughput, color: bool) {
+    let label = |s: &str| {
+        if color {
+            s.bold().to_string()
+        } else {
+            s.to_string()
+        }
+    };
+
+    println!("{}", label("Throughput"));
+    println!("{}", separator(40));
+    println!("{}: {}", label("Requests"), throughput.requests);
+    if throughput.skipped > 0 {
+        println!("{}: {} (no parsable startedDateTime)", label("Skipped"), throughput.skipped);
+    }
+    let (Some(span), Some(start), Some(end)) = (throughput.span_ms, &throughput.start, &throughput.end) else {
+        println!("No timed entries.");
+        return;
+    };
+
+    println!("{}: {} ({} to {})", label("Span"), format_time(span), start, end);
+    let fmt = |v: Option<f64>| v.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string());
+    println!("{}: {}", label("Requests/sec"), fmt(throughput.requests_per_sec));
+    println!("{}: {}", label("Mean concurrency"), fmt(throughput.mean_concurrency));
+    match throughput.peak_at {
+        Some(ref at) => println!("{}: {} (at {})", label("Peak concurrency"), throughput.peak_concurrency, at),
+        None => println!("{}: {}", label("Peak concurrency"), throughput.peak_concurrency),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn throughput(entries: &[(&str, f64)]) -> Throughput {
+        let entries = entries
+            .iter()
+            .map(|(start, time)| {
+                fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+                    e["startedDateTime"] = serde_json::json!(start);
+                    e["time"] = serde_json::json!(time);
+                })
+            })
+            .collect();
+        let har = fixtures::har_bytes(entries);
+        let cmd = ThroughputCmd { file: "-".to_string(), output: OutputFormat::Table };
+        cmd.throughput(har.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_sequential_entries() {
+        // Back to back: each starts as the previous one ends
+        let t = throughput(&[
+            ("2024-01-01T00:00:00.000Z", 500.0),
+            ("2024-01-01T00:00:00.500Z", 500.0),
+            ("2024-01-01T00:00:01.000Z", 1000.0),
+        ]);
+        assert_eq!(t.requests, 3);
+        assert_eq!(t.span_ms, Some(2000.0));
+        assert_eq!(t.requests_per_sec, Some(1.5));
+        assert_eq!(t.mean_concurrency, Some(1.0));
+        assert_eq!(t.peak_concurrency, 1);
+        assert_eq!(t.start.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(t.end.as_deref(), Some("2024-01-01T00:00:02Z"));
+    }
+
+    #[test]
+    fn test_overlapping_entries() {
+        let t = throughput(&[
+            ("2024-01-01T00:00:00.000Z", 1000.0),
+            ("2024-01-01T00:00:00.200Z", 300.0),
+            // Same instant as .300Z, with an offset
+            ("2024-01-01T01:00:00.300+01:00", 100.0),
+            ("2024-01-01T00:00:00.900Z", 1100.0),
+            ("not a date", 50.0),
+        ]);
+        assert_eq!(t.requests, 4);
+        assert_eq!(t.skipped, 1);
+        assert_eq!(t.span_ms, Some(2000.0));
+        assert_eq!(t.requests_per_sec, Some(2.0));
+        assert_eq!(t.mean_concurrency, Some(1.25));
+        assert_eq!(t.peak_concurrency, 3);
+        assert_eq!(t.peak_at.as_deref(), Some("2024-01-01T01:00:00.300+01:00"));
+    }
+
+    #[test]
+    fn test_no_span() {
+        let t = throughput(&[]);
+        assert_eq!((t.requests, t.peak_concurrency), (0, 0));
+        assert!(t.span_ms.is_none() && t.requests_per_sec.is_none());
+
+        let t = throughput(&[("2024-01-01T00:00:00Z", 0.0)]);
+        assert_eq!(t.span_ms, Some(0.0));
+        assert!(t.requests_per_sec.is_none());
+        assert_eq!(t.peak_concurrency, 1);
+    }
+}
@@ -1,9 +1,14 @@
-use crate::har::Har;
-use crate::output::{format_time, extract_host, OutputFormat};
+use crate::commands::page::PageArgs;
+use crate::har::{Entry, Har, Timings};
+use crate::output::{format_time, extract_host, separator, terminal_width, truncate, OutputFormat};
+use crate::output::waterfall::print_waterfall;
+use crate::output::table::{flexible_width, style_table, table_width};
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use tabled::{Table, Tabled, settings::Style};
+use tabled::{Table, Tabled};
 
 #[derive(Debug, Args)]
 pub struct TimingCmd {
@@ -30,6 +35,21 @@ pub struct TimingCmd {
     /// Limit output
     #[arg(short, long)]
     pub limit: Option<usize>,
+
+    /// Maximum host length before truncation (default: fit the terminal width)
+    #[arg(long)]
+    pub max_host: Option<usize>,
+
+    /// Draw a waterfall of entries by start time, split into timing phases
+    #[arg(short, long, conflicts_with_all = ["stats", "sort"])]
+    pub waterfall: bool,
+
+    /// Waterfall width in columns (default: terminal width)
+    #[arg(long, requires = "waterfall")]
+    pub width: Option<usize>,
+
+    #[command(flatten)]
+    pub page: PageArgs,
 }
 
 #[derive(Tabled)]
@@ -58,23 +78,38 @@ struct TimingRow {
 
 impl TimingCmd {
     pub fn run(&self, har: &Har, color: bool) -> Result<()> {
+        let entries = self.entries(har)?;
+
         if self.stats {
-            return self.print_stats(har, color);
+            return self.print_stats(&entries, color);
+        }
+
+        if self.waterfall {
+            let entries = &entries[..entries.len().min(self.limit.unwrap_or(usize::MAX))];
+            let width = self.width.or_else(terminal_width).unwrap_or(100);
+            // The bars get the rest of the width, so labels keep a fixed cap
+            print_waterfall(entries, width, color, self.max_host.unwrap_or(30));
+            return Ok(());
         }
 
         match self.output {
-            OutputFormat::Json => self.print_json(har),
-            _ => self.print_table(har, color),
+            OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Yaml => self.print_json(&entries),
+            _ => self.print_table(entries, color),
         }
     }
 
-    fn print_table(&self, har: &Har, _color: bool) -> Result<()> {
-        let mut entries: Vec<(usize, &crate::har::Entry)> = har.log.entries
+    /// Entries with their 1-based index, limited to the selected page if any
+    fn entries<'a>(&self, har: &'a Har) -> Result<Vec<(usize, &'a Entry)>> {
+        let page = self.page.resolve(har)?;
+        Ok(har.log.entries
             .iter()
             .enumerate()
             .map(|(i, e)| (i + 1, e))
-            .collect();
+            .filter(|(_, e)| page.as_ref().is_none_or(|p| p.contains(e)))
+            .collect())
+    }
 
+    fn print_table(&self, mut entries: Vec<(usize, &Entry)>, _color: bool) -> Result<()> {
         // Sort if requested
         if let Some(ref sort_field) = self.sort {
             entries.sort_by(|a, b| {
@@ -103,22 +138,17 @@ impl TimingCmd {
         }
 
         let fmt = |v: Option<f64>| -> String {
-            v.filter(|&t| t >= 0.0)
+            Timings::applicable(v)
                 .map(format_time)
                 .unwrap_or_else(|| "-".to_string())
         };
 
-        let rows: Vec<TimingRow> = entries
+        let mut rows: Vec<TimingRow> = entries
             .iter()
             .map(|(i, e)| {
-                let host = extract_host(&e.request.url);
                 TimingRow {
                     index: *i,
-                    host: if host.len() > 30 {
-                        format!("{}...", &host[..27])
-                    } else {
-                        host.to_string()
-                    },
+                    host: extract_host(&e.request.url).to_string(),
                     total: format_time(e.time),
                     blocked: fmt(e.timings.blocked),
                     dns: fmt(e.timings.dns),
@@ -131,20 +161,32 @@ impl TimingCmd {
             })
             .collect();
 
+        let max_host = self.max_host.unwrap_or_else(|| flexible_width(&rows, 1, table_width()));
+        for row in &mut rows {
+            row.host = truncate(&row.host, max_host);
+        }
+
         let mut table = Table::new(rows);
-        table.with(Style::rounded());
+        style_table(&mut table);
         println!("{}", table);
 
         Ok(())
     }
 
-    fn print_stats(&self, har: &Har, color: bool) -> Result<()> {
-        let entries = &har.log.entries;
+    fn print_stats(&self, entries: &[(usize, &Entry)], color: bool) -> Result<()> {
+        let stats = TimingStats::from_entries(entries);
 
-        if entries.is_empty() {
+        match self.output {
+            OutputFormat::Json => return print_json(&stats, true),
+            OutputFormat::Ndjson => return print_ndjson([&stats]),
+            OutputFormat::Yaml => return print_yaml(&stats),
+            _ => {}
+        }
+
+        let (Some(avg), Some(min), Some(max), Some(slowest)) = (stats.avg_ms, stats.min_ms, stats.max_ms, stats.slowest.as_ref()) else {
             println!("No entries.");
             return Ok(());
-        }
+        };
 
         let label = |s: &str| {
             if color {
@@ -154,79 +196,45 @@ impl TimingCmd {
             }
         };
 
-        let times: Vec<f64> = entries.iter().map(|e| e.time).collect();
-        let total: f64 = times.iter().sum();
-        let avg = total / times.len() as f64;
-        let max = times.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-        let min = times.iter().cloned().fold(f64::INFINITY, f64::min);
-
-        // Find slowest entry
-        let (slowest_idx, slowest) = entries
-            .iter()
-            .enumerate()
-            .max_by(|a, b| a.1.time.partial_cmp(&b.1.time).unwrap())
-            .unwrap();
-
         println!("{}", label("Timing Statistics"));
-        println!("{}", "─".repeat(40));
-        println!("{}: {}", label("Total requests"), entries.len());
-        println!("{}: {}", label("Total time"), format_time(total));
+        println!("{}", separator(40));
+        println!("{}: {}", label("Total requests"), stats.count);
+        println!("{}: {}", label("Failed requests"), stats.failed);
+        println!("{}: {}", label("Total time"), format_time(stats.total_ms));
         println!("{}: {}", label("Average time"), format_time(avg));
         println!("{}: {}", label("Min time"), format_time(min));
         println!("{}: {}", label("Max time"), format_time(max));
+        if let Some(ref p) = stats.percentiles {
+            println!("{}: {}", label("p50"), format_time(p.p50));
+            println!("{}: {}", label("p90"), format_time(p.p90));
+            println!("{}: {}", label("p95"), format_time(p.p95));
+            println!("{}: {}", label("p99"), format_time(p.p99));
+        }
         println!();
         println!("{}: #{} {} ({})",
             label("Slowest request"),
-            slowest_idx + 1,
-            format_time(slowest.time).yellow(),
-            extract_host(&slowest.request.url)
+            slowest.index,
+            format_time(slowest.time_ms).yellow(),
+            extract_host(&slowest.url)
         );
 
-        // Timing breakdown averages
-        let mut dns_sum = 0.0;
-        let mut dns_count = 0;
-        let mut connect_sum = 0.0;
-        let mut connect_count = 0;
-        let mut wait_sum = 0.0;
-        let mut wait_count = 0;
-
-        for e in entries {
-            if let Some(dns) = e.timings.dns {
-                if dns >= 0.0 {
-                    dns_sum += dns;
-                    dns_count += 1;
-                }
-            }
-            if let Some(connect) = e.timings.connect {
-                if connect >= 0.0 {
-                    connect_sum += connect;
-                    connect_count += 1;
-                }
-            }
-            if let Some(wait) = e.timings.wait {
-                if wait >= 0.0 {
-                    wait_sum += wait;
-                    wait_count += 1;
-                }
-            }
-        }
-
         println!();
         println!("{}", label("Average breakdown"));
-        if dns_count > 0 {
-            println!("  DNS: {}", format_time(dns_sum / dns_count as f64));
+        let phases = &stats.phase_avg_ms;
+        if let Some(dns) = phases.dns {
+            println!("  DNS: {}", format_time(dns));
         }
-        if connect_count > 0 {
-            println!("  Connect: {}", format_time(connect_sum / connect_count as f64));
+        if let Some(connect) = phases.connect {
+            println!("  Connect: {}", format_time(connect));
         }
-        if wait_count > 0 {
-            println!("  Wait: {}", format_time(wait_sum / wait_count as f64));
+        if let Some(wait) = phases.wait {
+            println!("  Wait: {}", format_time(wait));
         }
 
         Ok(())
     }
 
-    fn print_json(&self, har: &Har) -> Result<()> {
+    fn print_json(&self, entries: &[(usize, &Entry)]) -> Result<()> {
         #[derive(serde::Serialize)]
         struct TimingInfo {
             index: usize,
@@ -241,24 +249,193 @@ impl TimingCmd {
             receive_ms: Option<f64>,
         }
 
-        let timings: Vec<TimingInfo> = har.log.entries
+        let timings: Vec<TimingInfo> = entries
             .iter()
-            .enumerate()
             .map(|(i, e)| TimingInfo {
-                index: i + 1,
+                index: *i,
                 url: e.request.url.clone(),
                 total_ms: e.time,
-                blocked_ms: e.timings.blocked,
-                dns_ms: e.timings.dns,
-                connect_ms: e.timings.connect,
-                ssl_ms: e.timings.ssl,
-                send_ms: e.timings.send,
-                wait_ms: e.timings.wait,
-                receive_ms: e.timings.receive,
+                blocked_ms: Timings::applicable(e.timings.blocked),
+                dns_ms: Timings::applicable(e.timings.dns),
+                connect_ms: Timings::applicable(e.timings.connect),
+                ssl_ms: Timings::applicable(e.timings.ssl),
+                send_ms: Timings::applicable(e.timings.send),
+                wait_ms: Timings::applicable(e.timings.wait),
+                receive_ms: Timings::applicable(e.timings.receive),
             })
             .collect();
 
-        println!("{}", serde_json::to_string_pretty(&timings)?);
-        Ok(())
+        match self.output {
+            OutputFormat::Ndjson => print_ndjson(&timings),
+            OutputFormat::Yaml => print_yaml(&timings),
+            _ => print_json(&timings, true),
+        }
+    }
+}
+
+/// Aggregate timing figures printed by `timing --stats`
+#[derive(Debug, serde::Serialize)]
+pub struct TimingStats {
+    pub count: usize,
+    pub failed: usize,
+    pub total_ms: f64,
+    pub avg_ms: Option<f64>,
+    pub min_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub percentiles: Option<Percentiles>,
+    pub slowest: Option<SlowestRequest>,
+    /// Average of each phase over the entries that report it
+    pub phase_avg_ms: PhaseAverages,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct SlowestRequest {
+    pub index: usize,
+    pub url: String,
+    pub time_ms: f64,
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+pub struct PhaseAverages {
+    pub blocked: Option<f64>,
+    pub dns: Option<f64>,
+    pub connect: Option<f64>,
+    pub ssl: Option<f64>,
+    pub send: Option<f64>,
+    pub wait: Option<f64>,
+    pub receive: Option<f64>,
+}
+
+impl TimingStats {
+    pub fn from_entries(entries: &[(usize, &Entry)]) -> Self {
+        let times: Vec<f64> = entries.iter().map(|(_, e)| e.time).collect();
+        let total_ms: f64 = times.iter().sum();
+        let slowest = entries
+            .iter()
+            .max_by(|a, b| a.1.time.total_cmp(&b.1.time))
+            .map(|(i, e)| SlowestRequest { index: *i, url: e.request.url.clone(), time_ms: e.time });
+
+        // Mean of a phase, leaving out entries where it is missing or -1
+        let phase = |get: fn(&Timings) -> Option<f64>| -> Option<f64> {
+            let values: Vec<f64> = entries.iter().filter_map(|(_, e)| Timings::applicable(get(&e.timings))).collect();
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+
+        Self {
+            count: entries.len(),
+            failed: entries.iter().filter(|(_, e)| e.is_failed()).count(),
+            total_ms,
+            avg_ms: (!times.is_empty()).then(|| total_ms / times.len() as f64),
+            min_ms: times.iter().copied().reduce(f64::min),
+            max_ms: times.iter().copied().reduce(f64::max),
+            percentiles: Percentiles::from_times(&times),
+            slowest,
+            phase_avg_ms: PhaseAverages {
+                blocked: phase(|t| t.blocked),
+                dns: phase(|t| t.dns),
+                connect: phase(|t| t.connect),
+                ssl: phase(|t| t.ssl),
+                send: phase(|t| t.send),
+                wait: phase(|t| t.wait),
+                receive: phase(|t| t.receive),
+            },
+        }
+    }
+}
+
+/// Response-time percentiles in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    /// Compute percentiles with linear interpolation, or None when there are no times
+    pub fn from_times(times: &[f64]) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+
+        let mut sorted = times.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        Some(Self {
+            p50: percentile(&sorted, 50.0),
+            p90: percentile(&sorted, 90.0),
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+        })
+    }
+}
+
+/// Interpolated percentile of an ascending, non-empty slice
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_interpolate() {
+        let times: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let p = Percentiles::from_times(&times).unwrap();
+
+        assert!((p.p50 - 50.5).abs() < 1e-9);
+        assert!((p.p90 - 90.1).abs() < 1e-9);
+        assert!((p.p95 - 95.05).abs() < 1e-9);
+        assert!((p.p99 - 99.01).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_multibyte_host_at_truncation_boundary() {
+        // 'ü' spans bytes 26..28, so the old byte slice at 27 panicked
+        let host = "aaaaaaaaaaaaaaaaaaaaaaaaaaübücher.example";
+        assert_eq!(truncate(host, 30), "aaaaaaaaaaaaaaaaaaaaaaaaaaü...");
+        assert_eq!(truncate(host, 30).chars().count(), 30);
+    }
+
+    #[test]
+    fn test_stats_json_keys() {
+        let mut slow = crate::har::fixtures::entry_json("GET", "https://example.com/slow", 500);
+        slow["time"] = serde_json::json!(300.0);
+        slow["timings"] = serde_json::json!({"dns": -1, "wait": 250.0});
+        let mut fast = crate::har::fixtures::entry_json("GET", "https://example.com/fast", 200);
+        fast["time"] = serde_json::json!(100.0);
+        fast["timings"] = serde_json::json!({"dns": 10.0, "wait": 50.0});
+        let har = crate::har::fixtures::har(vec![slow, fast]);
+        let entries: Vec<(usize, &Entry)> = har.log.entries.iter().enumerate().map(|(i, e)| (i + 1, e)).collect();
+
+        let json = serde_json::to_value(TimingStats::from_entries(&entries)).unwrap();
+        for key in ["count", "failed", "total_ms", "avg_ms", "min_ms", "max_ms", "percentiles", "slowest", "phase_avg_ms"] {
+            assert!(json.get(key).is_some(), "missing {}", key);
+        }
+        assert_eq!(json["count"], 2);
+        assert_eq!(json["failed"], 1);
+        assert_eq!(json["avg_ms"], 200.0);
+        assert_eq!(json["percentiles"]["p50"], 200.0);
+        assert_eq!(json["slowest"]["index"], 1);
+        assert_eq!(json["phase_avg_ms"]["dns"], 10.0);
+        assert_eq!(json["phase_avg_ms"]["wait"], 150.0);
+        assert!(json["phase_avg_ms"]["ssl"].is_null());
+
+        let empty = serde_json::to_value(TimingStats::from_entries(&[])).unwrap();
+        assert_eq!(empty["count"], 0);
+        assert!(empty["avg_ms"].is_null() && empty["slowest"].is_null());
+    }
+
+    #[test]
+    fn test_percentiles_empty_and_single() {
+        assert_eq!(Percentiles::from_times(&[]), None);
+
+        let p = Percentiles::from_times(&[42.0]).unwrap();
+        assert_eq!(p, Percentiles { p50: 42.0, p90: 42.0, p95: 42.0, p99: 42.0 });
     }
 }
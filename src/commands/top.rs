@@ -0,0 +1,170 @@
+use crate::commands::domains::{collect_domains, DomainSort, SizeMode};
+use crate::commands::timing::Percentiles;
+use crate::har::{self, Har};
+use crate::output::{format_time, separator, truncate};
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use std::fs;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Clear the screen and move the cursor home
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+#[derive(Debug, Args)]
+pub struct TopCmd {
+    /// HAR file to watch (re-read whenever it changes)
+    #[arg()]
+    pub file: String,
+
+    /// Refresh interval in milliseconds
+    #[arg(long, default_value = "250")]
+    pub interval: u64,
+}
+
+/// Dashboard figures for one snapshot of the HAR
+#[derive(Debug)]
+struct Summary {
+    total: usize,
+    failed: usize,
+    avg_ms: f64,
+    p95_ms: Option<f64>,
+    /// (host, average time) for the slowest hosts
+    slowest_hosts: Vec<(String, f64)>,
+}
+
+impl TopCmd {
+    pub fn run(&self, color: bool) -> Result<()> {
+        let mut last_seen: Option<(u64, Option<SystemTime>)> = None;
+        let mut summary: Option<Summary> = None;
+        let mut note = String::new();
+
+        loop {
+            let meta = fs::metadata(&self.file)
+                .with_context(|| format!("Failed to open file: {}", self.file))?;
+            let stamp = (meta.len(), meta.modified().ok());
+
+            if last_seen != Some(stamp) {
+                last_seen = Some(stamp);
+                // The file may be mid-write; keep the last good snapshot until it parses
+                match har::parse_file(&self.file) {
+                    Ok(har) => {
+                        summary = Some(Summary::from_har(&har));
+                        note.clear();
+                    }
+                    Err(_) => note = "waiting for a complete write...".to_string(),
+                }
+            }
+
+            let mut out = io::stdout().lock();
+            write!(out, "{}", CLEAR)?;
+            writeln!(out, "{}", self.header(color))?;
+            match summary {
+                Some(ref summary) => write!(out, "{}", summary.render(color))?,
+                None => writeln!(out, "No complete HAR read yet.")?,
+            }
+            if !note.is_empty() {
+                writeln!(out, "\n{}", note)?;
+            }
+            out.flush()?;
+            drop(out);
+
+            thread::sleep(Duration::from_millis(self.interval));
+        }
+    }
+
+    fn header(&self, color: bool) -> String {
+        let title = format!("harq top - {} (Ctrl+C to quit)", self.file);
+        if color { title.bold().to_string() } else { title }
+    }
+}
+
+impl Summary {
+    fn from_har(har: &Har) -> Self {
+        let entries = &har.log.entries;
+        let times: Vec<f64> = entries.iter().map(|e| e.time).filter(|t| *t >= 0.0).collect();
+        let avg_ms = if times.is_empty() { 0.0 } else { times.iter().sum::<f64>() / times.len() as f64 };
+
+        let mut hosts: Vec<(String, f64)> = collect_domains(har, DomainSort::Count, SizeMode::Transfer)
+            .into_iter()
+            .map(|d| (d.host, d.time_ms / d.count as f64))
+            .collect();
+        hosts.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        hosts.truncate(5);
+
+        Self {
+            total: entries.len(),
+            failed: entries.iter().filter(|e| e.is_failed()).count(),
+            avg_ms,
+            p95_ms: Percentiles::from_times(&times).map(|p| p.p95),
+            slowest_hosts: hosts,
+        }
+    }
+
+    fn error_rate(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.failed as f64 / self.total as f64 * 100.0 }
+    }
+
+    fn render(&self, color: bool) -> String {
+        let label = |s: &str| if color { s.bold().to_string() } else { s.to_string() };
+        let mut out = String::new();
+
+        out.push_str(&format!("{}\n", separator(40)));
+        out.push_str(&format!("{}: {}\n", label("Requests"), self.total));
+        out.push_str(&format!(
+            "{}: {} ({:.1}%)\n",
+            label("Errors"),
+            self.failed,
+            self.error_rate()
+        ));
+        out.push_str(&format!("{}: {}\n", label("Avg latency"), format_time(self.avg_ms)));
+        out.push_str(&format!(
+            "{}: {}\n",
+            label("p95 latency"),
+            self.p95_ms.map(format_time).unwrap_or_else(|| "-".to_string())
+        ));
+
+        out.push_str(&format!("\n{}\n", label("Slowest hosts (avg)")));
+        for (host, avg) in &self.slowest_hosts {
+            out.push_str(&format!("  {:<40} {}\n", truncate(host, 40), format_time(*avg)));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_summary_from_har() {
+        let mut entries = Vec::new();
+        for (i, host) in ["a", "b", "c", "d", "e", "f"].iter().enumerate() {
+            let json = fixtures::entry_with("GET", &format!("https://{}.example.com/", host), 200, |e| {
+                e["time"] = serde_json::json!((i + 1) as f64 * 100.0);
+            });
+            entries.push(json);
+        }
+        entries.push(fixtures::entry_json("GET", "https://a.example.com/missing", 404));
+        let summary = Summary::from_har(&fixtures::har(entries));
+
+        assert_eq!(summary.total, 7);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.slowest_hosts.len(), 5);
+        assert_eq!(summary.slowest_hosts[0], ("f.example.com".to_string(), 600.0));
+        assert!(summary.slowest_hosts.iter().all(|(h, _)| h != "a.example.com"));
+
+        let rendered = summary.render(false);
+        assert!(rendered.contains("Errors: 1 (14.3%)"));
+    }
+
+    #[test]
+    fn test_summary_of_empty_har() {
+        let summary = Summary::from_har(&fixtures::har(Vec::new()));
+        assert_eq!(summary.error_rate(), 0.0);
+        assert!(summary.render(false).contains("p95 latency: -"));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::har::parse_timestamp;
+use crate::output::OutputFormat;
+use crate::output::json::{print_json, print_ndjson};
+use crate::output::yaml::print_yaml;
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use serde_json::Value;
+
+#[derive(Debug, Args)]
+pub struct ValidateCmd {
+    /// HAR file to check (use - for stdin)
+    #[arg(default_value = "-")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Allowed difference in ms between `time` and the sum of its timings
+    #[arg(long, default_value = "1")]
+    pub tolerance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Lowercase, as in the table and the JSON output
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// A spec violation; `entry` is the 1-based entry index, or None for the log itself
+#[derive(Debug, serde::Serialize)]
+pub struct Issue {
+    pub entry: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+const ENTRY_FIELDS: &[&str] = &["startedDateTime", "time", "request", "response", "cache", "timings"];
+const REQUEST_FIELDS: &[&str] = &[
+    "method", "url", "httpVersion", "cookies", "headers", "queryString", "headersSize", "bodySize",
+];
+const RESPONSE_FIELDS: &[&str] = &[
+    "status", "statusText", "httpVersion", "cookies", "headers", "content", "redirectURL", "headersSize", "bodySize",
+];
+const CONTENT_FIELDS: &[&str] = &["size", "mimeType"];
+/// Phases that add up to `time`; `ssl` is already part of `connect`
+const TIMING_PHASES: &[&str] = &["blocked", "dns", "connect", "send", "wait", "receive"];
+
+impl ValidateCmd {
+    pub fn run<R: std::io::Read>(&self, reader: R, color: bool) -> Result<()> {
+        // Checked as plain JSON: the typed model would reject or default the fields we report on
+        let json: Value = serde_json::from_reader(reader).context("Failed to parse HAR file")?;
+        let issues = validate(&json, self.tolerance);
+
+        match self.output {
+            OutputFormat::Json => print_json(&issues, true)?,
+            OutputFormat::Ndjson => print_ndjson(&issues)?,
+            OutputFormat::Yaml => print_yaml(&issues)?,
+            OutputFormat::Compact => {
+                for issue in &issues {
+                    let entry = issue.entry.map(|i| i.to_string()).unwrap_or_else(|| "-".to_string());
+                    println!("{}\t{}\t{}", entry, issue.severity, issue.message);
+                }
+            }
+            OutputFormat::Table => print_issues(&issues, color),
+        }
+
+        let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+        if errors > 0 {
+            bail!("HAR is invalid: {} error(s)", errors);
+        }
+        Ok(())
+    }
+}
+
+fn print_issues(issues: &[Issue], color: bool) {
+    for issue in issues {
+        let severity = match (issue.severity, color) {
+            (Severity::Error, true) => issue.severity.to_string().red().bold().to_string(),
+            (Severity::Warning, true) => issue.severity.to_string().yellow().to_string(),
+            (_, false) => issue.severity.to_string(),
+        };
+        match issue.entry {
+            Some(i) => println!("entry #{}: {}: {}", i, severity, issue.message),
+            None => println!("log: {}: {}", severity, issue.message),
+        }
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+    let warnings = issues.len() - errors;
+    if issues.is_empty() {
+        println!("No problems found.");
+    } else {
+        println!("{} error(s), {} warning(s)", errors, warnings);
+    }
+}
+
+/// Check a HAR document against the HAR 1.2 spec
+pub fn validate(json: &Value, tolerance: f64) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut log_error = |message: String| {
+        issues.push(Issue { entry: None, severity: Severity::Error, message });
+    };
+
+    let Some(log) = json.get("log").filter(|l| l.is_object()) else {
+        log_error("missing log object".to_string());
+        return issues;
+    };
+
+    for field in ["version", "creator", "entries"] {
+        if log.get(field).is_none() {
+            log_error(format!("missing log.{}", field));
+        }
+    }
+
+    let Some(entries) = log.get("entries").and_then(Value::as_array) else {
+        return issues;
+    };
+
+    for (i, entry) in entries.iter().enumerate() {
+        validate_entry(entry, tolerance, &mut EntryIssues { index: i + 1, issues: &mut issues });
+    }
+
+    issues
+}
+
+/// Collects issues for one entry
+struct EntryIssues<'a> {
+    index: usize,
+    issues: &'a mut Vec<Issue>,
+}
+
+impl EntryIssues<'_> {
+    fn error(&mut self, message: String) {
+        self.push(Severity::Error, message);
+    }
+
+    fn warning(&mut self, message: String) {
+        self.push(Severity::Warning, message);
+    }
+
+    fn push(&mut self, severity: Severity, message: String) {
+        self.issues.push(Issue { entry: Some(self.index), severity, message });
+    }
+}
+
+fn validate_entry(entry: &Value, tolerance: f64, out: &mut EntryIssues) {
+    for (path, fields) in [
+        ("", ENTRY_FIELDS),
+        ("request.", REQUEST_FIELDS),
+        ("response.", RESPONSE_FIELDS),
+        ("response.content.", CONTENT_FIELDS),
+    ] {
+        // A missing parent object is already reported one level up
+        let Some(object) = lookup(entry, path.trim_end_matches('.')) else {
+            continue;
+        };
+        for field in fields {
+            if object.get(field).is_none() {
+                out.error(format!("missing {}{}", path, field));
+            }
+        }
+    }
+
+    if let Some(started) = entry.get("startedDateTime").and_then(Value::as_str) {
+        if parse_timestamp(started).is_none() {
+            out.error(format!("startedDateTime \"{}\" is not an ISO 8601 timestamp", started));
+        }
+    }
+
+    match lookup(entry, "response.status") {
+        Some(status) if status.is_i64() => {
+            let status = status.as_i64().unwrap_or(0);
+            // 0 is what browsers record for aborted or blocked requests
+            if status != 0 && !(100..=599).contains(&status) {
+                out.error(format!("response.status {} is out of range (100-599)", status));
+            }
+        }
+        // Parsing reads these leniently, so they are only worth a warning
+        Some(status) => out.warning(format!("response.status {} is not an integer", status)),
+        None => {}
+    }
+
+    for path in ["request.headersSize", "request.bodySize", "response.headersSize", "response.bodySize"] {
+        if let Some(size) = lookup(entry, path).and_then(Value::as_f64) {
+            if size < -1.0 {
+                out.error(format!("{} is {} (use -1 for unknown)", path, size));
+            }
+        }
+    }
+
+    let Some(timings) = entry.get("timings") else {
+        return;
+    };
+
+    for phase in ["send", "wait", "receive"] {
+        match timings.get(phase).and_then(Value::as_f64) {
+            None => out.error(format!("missing timings.{}", phase)),
+            Some(v) if v < 0.0 => out.error(format!("timings.{} is negative ({})", phase, v)),
+            Some(_) => {}
+        }
+    }
+    for phase in ["blocked", "dns", "connect", "ssl"] {
+        if let Some(v) = timings.get(phase).and_then(Value::as_f64) {
+            if v < -1.0 {
+                out.error(format!("timings.{} is {} (use -1 for not applicable)", phase, v));
+            }
+        }
+    }
+
+    if let Some(time) = entry.get("time").and_then(Value::as_f64) {
+        let sum: f64 = TIMING_PHASES
+            .iter()
+            .filter_map(|phase| timings.get(phase).and_then(Value::as_f64))
+            .filter(|v| *v > 0.0)
+            .fold(0.0, |acc, v| acc + v);
+        if (time - sum).abs() > tolerance {
+            out.warning(format!("time {} does not match the sum of timings {}", time, sum));
+        }
+    }
+}
+
+/// Follow a dotted path of object keys; an empty path is the value itself
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.').try_fold(value, |v, key| v.get(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn valid_entry() -> Value {
+        json!({
+            "startedDateTime": "2024-01-01T10:00:00.000+01:00",
+            "time": 50.5,
+            "request": {
+                "method": "GET",
+                "url": "https://example.com/",
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": [],
+                "queryString": [],
+                "headersSize": -1,
+                "bodySize": 0
+            },
+            "response": {
+                "status": 200,
+                "statusText": "OK",
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": [],
+                "content": { "size": 0, "mimeType": "text/html" },
+                "redirectURL": "",
+                "headersSize": -1,
+                "bodySize": 0
+            },
+            "cache": {},
+            "timings": { "blocked": -1, "dns": 10, "connect": -1, "send": 0.5, "wait": 30, "receive": 10 }
+        })
+    }
+
+    fn har(entries: Vec<Value>) -> Value {
+        json!({ "log": { "version": "1.2", "creator": { "name": "test", "version": "1" }, "entries": entries } })
+    }
+
+    #[test]
+    fn test_valid_har_has_no_issues() {
+        let issues = validate(&har(vec![valid_entry()]), 1.0);
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_accepts_timestamps_harq_reads() {
+        let mut entry = valid_entry();
+        entry["startedDateTime"] = json!("2024-01-01T10:00:00.000+0200");
+        assert!(validate(&har(vec![entry]), 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_reports_errors_with_entry_index() {
+        let mut broken = valid_entry();
+        broken["startedDateTime"] = json!("yesterday");
+        broken["response"]["status"] = json!(999);
+        broken["request"].as_object_mut().unwrap().remove("url");
+        broken["timings"]["wait"] = json!(-5);
+
+        let issues = validate(&har(vec![valid_entry(), broken]), 1.0);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+
+        assert!(issues.iter().all(|i| i.entry == Some(2)));
+        assert!(messages.contains(&"missing request.url"));
+        assert!(messages.iter().any(|m| m.starts_with("startedDateTime")));
+        assert!(messages.iter().any(|m| m.contains("out of range")));
+        assert!(messages.iter().any(|m| m.contains("timings.wait is negative")));
+    }
+
+    #[test]
+    fn test_string_status_is_a_warning() {
+        let mut entry = valid_entry();
+        entry["response"]["status"] = json!("200");
+
+        let issues = validate(&har(vec![entry]), 1.0);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].message, "response.status \"200\" is not an integer");
+        assert_eq!(issues[0].severity.to_string(), "warning");
+        assert_eq!(serde_json::to_value(Severity::Error).unwrap(), json!(Severity::Error.to_string()));
+    }
+
+    #[test]
+    fn test_time_mismatch_is_a_warning() {
+        let mut entry = valid_entry();
+        entry["time"] = json!(500);
+
+        let issues = validate(&har(vec![entry]), 1.0);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert!(validate(&har(vec![valid_entry()]), 0.0).is_empty());
+    }
+}
@@ -1,9 +1,14 @@
-use crate::har::Har;
+use crate::commands::header_order::HeaderOrderArgs;
+use crate::commands::list::compact_row;
+use crate::har::{self, Har};
 use crate::output::OutputFormat;
 use crate::output::table::print_entry_detail;
 use crate::output::json::print_entry_json;
+use crate::output::yaml::print_yaml;
 use anyhow::{Result, bail};
 use clap::Args;
+use colored::Colorize;
+use std::ops::Range;
 
 #[derive(Debug, Args)]
 pub struct ViewCmd {
@@ -30,6 +35,21 @@ pub struct ViewCmd {
     /// Show only headers
     #[arg(long)]
     pub headers_only: bool,
+
+    /// Also list N entries before and after, one line each (table output only)
+    #[arg(short = 'C', long, value_name = "N")]
+    pub context: Option<usize>,
+
+    /// Also show the connection id, cache info and custom `_` fields
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Directory holding response bodies referenced by `_file`/`_content`
+    #[arg(long, value_name = "DIR")]
+    pub body_dir: Option<String>,
+
+    #[command(flatten)]
+    pub order: HeaderOrderArgs,
 }
 
 impl ViewCmd {
@@ -42,16 +62,57 @@ impl ViewCmd {
             );
         }
 
-        let entry = &har.log.entries[self.index - 1];
+        let mut entry = har::with_external_body(&har.log.entries[self.index - 1], self.body_dir.as_deref())?;
+        if self.order.no_pseudo || self.order.sort {
+            let entry = entry.to_mut();
+            entry.request.headers = self.order.arranged(&entry.request.headers);
+            entry.response.headers = self.order.arranged(&entry.response.headers);
+        }
 
         match self.output {
-            OutputFormat::Json => print_entry_json(entry, true)?,
+            OutputFormat::Json => print_entry_json(&entry, true)?,
+            OutputFormat::Yaml => print_yaml(&entry)?,
             _ => {
                 let show_body = self.full && !self.no_body && !self.headers_only;
-                print_entry_detail(self.index, entry, color, show_body);
+                let (before, after) = neighbors(self.index, har.log.entries.len(), self.context.unwrap_or(0));
+                print_summaries(har, before, color);
+                print_entry_detail(self.index, &entry, color, show_body, self.verbose);
+                print_summaries(har, after, color);
             }
         }
 
         Ok(())
     }
 }
+
+/// 1-based indexes of up to `context` entries before and after `index`,
+/// clamped to the file
+fn neighbors(index: usize, len: usize, context: usize) -> (Range<usize>, Range<usize>) {
+    let first = index.saturating_sub(context).max(1);
+    let last = index.saturating_add(context).min(len);
+    (first..index, index + 1..last + 1)
+}
+
+/// One line per entry, laid out like `list -o compact`
+fn print_summaries(har: &Har, indexes: Range<usize>, color: bool) {
+    for i in indexes {
+        let line = compact_row(i, &har.log.entries[i - 1]);
+        println!("{}", if color { line.dimmed().to_string() } else { line });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbors_clamp_at_file_boundaries() {
+        assert_eq!(neighbors(5, 10, 2), (3..5, 6..8));
+        assert_eq!(neighbors(1, 10, 3), (1..1, 2..5));
+        assert_eq!(neighbors(2, 10, 3), (1..2, 3..6));
+        assert_eq!(neighbors(10, 10, 3), (7..10, 11..11));
+        assert_eq!(neighbors(1, 1, 5), (1..1, 2..2));
+        assert_eq!(neighbors(4, 10, 0), (4..4, 5..5));
+        assert_eq!(neighbors(3, 5, usize::MAX), (1..3, 4..6));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::Cli;
+use anyhow::{Context, Result, bail};
+use clap::{Arg, ArgAction, Command, CommandFactory};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Config file name looked up in the current directory
+const CONFIG_FILE: &str = "harq.toml";
+
+/// Default flags loaded from a `harq.toml` file.
+///
+/// Top-level keys apply to global options (e.g. `color`), and a table per
+/// command sets that command's defaults. Keys are long flag names:
+///
+/// ```toml
+/// color = "always"
+///
+/// [list]
+/// max-url = 100
+/// output = "compact"
+///
+/// [redact]
+/// header = ["X-Api-Key", "X-Session"]
+/// ```
+pub struct Config {
+    values: toml::Table,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self> {
+        Ok(Self {
+            values: text.parse::<toml::Table>()?,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Failed to parse config: {}", path.display()))
+    }
+
+    /// Load the explicit config path, or `./harq.toml`, or `~/.harq.toml`
+    pub fn discover(explicit: Option<&str>) -> Result<Option<Self>> {
+        if let Some(path) = explicit {
+            return Self::load(Path::new(path)).map(Some);
+        }
+
+        let mut candidates = vec![PathBuf::from(CONFIG_FILE)];
+        if let Some(home) = std::env::var_os("HOME") {
+            candidates.push(PathBuf::from(home).join(".harq.toml"));
+        }
+
+        match candidates.into_iter().find(|p| p.is_file()) {
+            Some(path) => Self::load(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Make config values the defaults of the matching options of `command`.
+    ///
+    /// Being defaults, they rank below flags given on the command line and
+    /// don't count for `requires` or `conflicts_with`.
+    pub fn apply(&self, mut command: Command) -> Result<Command> {
+        for (key, value) in &self.values {
+            match value {
+                toml::Value::Table(table) => {
+                    // Tables for other builds' commands, e.g. `browse` without the tui feature
+                    let Some(subcommand) = command.find_subcommand(key) else {
+                        continue;
+                    };
+                    let mut defaults = Vec::new();
+                    for (option, value) in table {
+                        let Some(arg) = find_long(subcommand.get_arguments(), option) else {
+                            bail!("Unknown option '{}' for '{}' in config", option, key);
+                        };
+                        defaults.push((arg.get_id().clone(), default_values(arg, option, value)?));
+                    }
+                    command = command.mut_subcommand(key, |mut subcommand| {
+                        for (id, values) in defaults {
+                            subcommand = subcommand.mut_arg(id, |arg| arg.default_values(values));
+                        }
+                        subcommand
+                    });
+                }
+                _ => {
+                    let Some(arg) = find_long(command.get_arguments(), key).filter(|a| a.is_global_set()) else {
+                        bail!("Unknown global option '{}' in config", key);
+                    };
+                    let (id, values) = (arg.get_id().clone(), default_values(arg, key, value)?);
+                    command = command.mut_arg(id, |arg| arg.default_values(values));
+                }
+            }
+        }
+        Ok(command)
+    }
+}
+
+/// The CLI with defaults from the config named by `--config` (or found by
+/// lookup) applied
+pub fn command(args: &[OsString]) -> Result<Command> {
+    let explicit = Cli::command()
+        .ignore_errors(true)
+        .try_get_matches_from(args)
+        .ok()
+        .and_then(|m| m.get_one::<String>("config").cloned());
+
+    match Config::discover(explicit.as_deref())? {
+        Some(config) => config.apply(Cli::command()),
+        None => Ok(Cli::command()),
+    }
+}
+
+/// The option whose long flag is `key`; `_` and `-` are interchangeable
+fn find_long<'a>(mut args: impl Iterator<Item = &'a Arg>, key: &str) -> Option<&'a Arg> {
+    let key = key.replace('_', "-");
+    args.find(|arg| arg.get_long() == Some(key.as_str()))
+}
+
+/// A config value as default values for `arg`, one per array element
+fn default_values(arg: &Arg, key: &str, value: &toml::Value) -> Result<Vec<String>> {
+    let scalar = |value: &toml::Value| match value {
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(n) => Ok(n.to_string()),
+        toml::Value::Float(n) => Ok(n.to_string()),
+        other => bail!("Unsupported config value for '{}': {}", key, other),
+    };
+
+    match value {
+        toml::Value::Array(items) if matches!(arg.get_action(), ArgAction::Append) => items.iter().map(scalar).collect(),
+        toml::Value::Array(_) => bail!("'{}' in config takes a single value, not a list", key),
+        value => Ok(vec![scalar(value)?]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Commands;
+    use clap::FromArgMatches;
+
+    fn parse(config: &str, line: &str) -> Result<Commands> {
+        let command = Config::parse(config)?.apply(Cli::command())?;
+        let matches = command.try_get_matches_from(line.split_whitespace())?;
+        Ok(Cli::from_arg_matches(&matches)?.command)
+    }
+
+    fn parse_list(config: &str, line: &str) -> crate::commands::ListCmd {
+        match parse(config, line).unwrap() {
+            Commands::List(cmd) => cmd,
+            _ => panic!("expected list"),
+        }
+    }
+
+    #[test]
+    fn test_config_sets_default() {
+        let cmd = parse_list("[list]\nmax-url = 100\nlong = true\n", "harq list f.har");
+        assert_eq!(cmd.max_url, Some(100));
+        assert!(cmd.long);
+    }
+
+    #[test]
+    fn test_explicit_flag_overrides_config() {
+        let cmd = parse_list("[list]\nmax-url = 100\n", "harq list --max-url 20 f.har");
+        assert_eq!(cmd.max_url, Some(20));
+    }
+
+    #[test]
+    fn test_other_command_tables_are_ignored() {
+        let cmd = parse_list("[search]\nmax-url = 100\n", "harq ls f.har");
+        assert_eq!(cmd.max_url, None);
+    }
+
+    #[test]
+    fn test_unknown_option_is_rejected() {
+        assert!(parse("[list]\nbogus = 1\n", "harq list f.har").is_err());
+        // Keys are flag names, not field names
+        assert!(parse("[redact]\nheaders = [\"X-Api-Key\"]\n", "harq redact f.har").is_err());
+    }
+
+    #[test]
+    fn test_options_named_by_long_flag() {
+        let Commands::Redact(cmd) = parse("[redact]\nheader = [\"X-Api-Key\", \"X-Session\"]\n", "harq redact f.har").unwrap() else {
+            panic!("expected redact");
+        };
+        assert_eq!(cmd.headers, ["X-Api-Key", "X-Session"]);
+
+        let Commands::Body(cmd) = parse("[body]\nas = \"json\"\n", "harq body 1 f.har").unwrap() else {
+            panic!("expected body");
+        };
+        assert!(cmd.as_format.is_some());
+    }
+
+    #[test]
+    fn test_config_defaults_skip_conflicts_and_requires() {
+        // --format conflicts with --output, and count's --output requires --by
+        let cmd = parse_list("[list]\noutput = \"json\"\n", "harq list --format {url} f.har");
+        assert!(cmd.format.is_some());
+        assert!(matches!(parse("[count]\noutput = \"json\"\n", "harq count f.har").unwrap(), Commands::Count(_)));
+    }
+}
@@ -1,7 +1,14 @@
-use crate::har::Entry;
-use anyhow::{Result, bail};
+use crate::har::{parse_timestamp, Entry, Timings};
+use crate::output::{extract_host, url_decode};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, FixedOffset, SecondsFormat};
 use regex::Regex;
 
+/// Characters that only appear in comparisons, never in a bare field name
+const OPERATOR_CHARS: &[char] = &['=', '!', '<', '>', '~'];
+
+const COMPARISON_HINT: &str = "hint: comparisons look like `status >= 400`, use == for equality";
+
 /// A parsed filter expression
 #[derive(Debug)]
 pub enum FilterExpr {
@@ -12,12 +19,16 @@ pub enum FilterExpr {
     Ge(Field, Value),
     Lt(Field, Value),
     Le(Field, Value),
+    In(Field, Vec<Value>),
 
     // String operations
     Contains(Field, String),
+    IContains(Field, String), // needle stored lowercased
+    IEquals(Field, String),
     StartsWith(Field, String),
     EndsWith(Field, String),
     Matches(Field, Regex),
+    InDomain(Field, String), // domain stored lowercased, without a leading "*." or "."
 
     // Logical operators
     And(Box<FilterExpr>, Box<FilterExpr>),
@@ -26,6 +37,23 @@ pub enum FilterExpr {
 
     // Boolean (for simple field checks)
     Bool(Field),
+
+    // Presence: has(field) or field != null
+    Exists(Field),
+
+    // Field against field: response.bodySize > request.bodySize
+    CompareFields(Field, CompareOp, Field),
+}
+
+/// Comparison operator, used when both sides are fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
 }
 
 /// Field accessor
@@ -37,6 +65,8 @@ pub enum Field {
     Host,
     Domain, // alias for Host
     Path,
+    PathSegment(usize),
+    PathDepth,
     Scheme,
     Query,
     Status,
@@ -44,6 +74,9 @@ pub enum Field {
     Time,
     StartedDateTime,
     ServerIpAddress,
+    IsFailed,
+    ResourceType,
+    IsXhr,
 
     // Request fields
     RequestHttpVersion,
@@ -56,6 +89,8 @@ pub enum Field {
     ResponseBodySize,
     ContentType,
     ContentSize,
+    MaxAge,
+    SetCookieCount,
 
     // Timing fields
     TimingBlocked,
@@ -68,12 +103,16 @@ pub enum Field {
 
     // Header access
     RequestHeader(String),
+    QueryParam(String),
     ResponseHeader(String),
+    RequestHeaderMatch(Regex),
+    ResponseHeaderMatch(Regex),
 
     // GraphQL fields
     GqlOperationName,
     GqlOperationType,
     GqlQuery,
+    GqlVariable(String),
     IsGraphQL,
 }
 
@@ -83,6 +122,8 @@ pub enum Value {
     String(String),
     Number(f64),
     Bool(bool),
+    /// A parsed `startedDateTime`, compared chronologically
+    Time(DateTime<FixedOffset>),
 }
 
 impl FilterExpr {
@@ -100,14 +141,16 @@ impl FilterExpr {
         }
 
         // Try to parse logical operators (lowest precedence)
-        // Look for && and || at the top level (not inside parens)
-        if let Some(pos) = find_top_level(expr, "||") {
+        // Look for && and || at the top level (not inside parens). Splitting
+        // at the last one makes chains left-associative: a || b || c is
+        // (a || b) || c, and && binds tighter than ||
+        if let Some(pos) = find_last_top_level(expr, "||") {
             let left = Self::parse(&expr[..pos])?;
             let right = Self::parse(&expr[pos + 2..])?;
             return Ok(FilterExpr::Or(Box::new(left), Box::new(right)));
         }
 
-        if let Some(pos) = find_top_level(expr, "&&") {
+        if let Some(pos) = find_last_top_level(expr, "&&") {
             let left = Self::parse(&expr[..pos])?;
             let right = Self::parse(&expr[pos + 2..])?;
             return Ok(FilterExpr::And(Box::new(left), Box::new(right)));
@@ -115,10 +158,9 @@ impl FilterExpr {
 
         // Handle NOT
         if expr.starts_with('!') || expr.to_lowercase().starts_with("not ") {
-            let inner = if expr.starts_with('!') {
-                &expr[1..]
-            } else {
-                &expr[4..]
+            let inner = match expr.strip_prefix('!') {
+                Some(rest) => rest,
+                None => &expr[4..],
             };
             return Ok(FilterExpr::Not(Box::new(Self::parse(inner)?)));
         }
@@ -128,96 +170,181 @@ impl FilterExpr {
     }
 
     fn parse_comparison(expr: &str) -> Result<Self> {
-        // Check for method calls: field.method(arg)
-        if let Some(idx) = expr.find('.') {
-            let field_str = &expr[..idx];
-            let rest = &expr[idx + 1..];
+        // Presence check: has(field)
+        if let Some(inner) = expr.strip_prefix("has(").and_then(|r| r.strip_suffix(')')) {
+            return Ok(FilterExpr::Exists(Field::parse(inner.trim())?));
+        }
 
-            if rest.starts_with("contains(") && rest.ends_with(')') {
-                let arg = extract_string_arg(&rest[9..rest.len() - 1])?;
-                let field = Field::parse(field_str)?;
-                return Ok(FilterExpr::Contains(field, arg));
+        // Method calls: field.method(arg). The field may itself be a call,
+        // as in request.header("Accept").icontains("json")
+        if expr.ends_with(')') {
+            for method in ["contains", "icontains", "iequals", "startsWith", "endsWith", "matches", "inDomain"] {
+                let call = format!(".{}(", method);
+                let Some(pos) = find_top_level(expr, &call) else {
+                    continue;
+                };
+                let field = Field::parse(&expr[..pos])?;
+                let arg = &expr[pos + call.len()..expr.len() - 1];
+
+                return Ok(match method {
+                    "contains" => FilterExpr::Contains(field, extract_string_arg(arg)?),
+                    "icontains" => FilterExpr::IContains(field, extract_string_arg(arg)?.to_ascii_lowercase()),
+                    "iequals" => FilterExpr::IEquals(field, extract_string_arg(arg)?),
+                    "startsWith" => FilterExpr::StartsWith(field, extract_string_arg(arg)?),
+                    "endsWith" => FilterExpr::EndsWith(field, extract_string_arg(arg)?),
+                    "inDomain" => {
+                        let domain = extract_string_arg(arg)?.to_ascii_lowercase();
+                        let domain = domain.trim_start_matches("*.").trim_start_matches('.');
+                        if domain.is_empty() {
+                            bail!("inDomain() needs a domain name: {}", expr);
+                        }
+                        FilterExpr::InDomain(field, domain.to_string())
+                    }
+                    _ => FilterExpr::Matches(field, extract_regex_arg(arg)?),
+                });
             }
+        }
 
-            if rest.starts_with("startsWith(") && rest.ends_with(')') {
-                let arg = extract_string_arg(&rest[11..rest.len() - 1])?;
-                let field = Field::parse(field_str)?;
-                return Ok(FilterExpr::StartsWith(field, arg));
+        // Set membership: field in [a, b, c], field not in (a, b, c)
+        for (op, negated) in [(" not in ", true), (" in ", false)] {
+            let Some(pos) = find_top_level(expr, op) else {
+                continue;
+            };
+            let list = expr[pos + op.len()..].trim();
+            let bracketed = (list.starts_with('[') && list.ends_with(']'))
+                || (list.starts_with('(') && list.ends_with(')'));
+            if bracketed {
+                let field = Field::parse(&expr[..pos])?;
+                let values = split_list(&list[1..list.len() - 1])
+                    .into_iter()
+                    .map(|v| Value::parse_for(&field, v))
+                    .collect::<Result<Vec<_>>>()?;
+                let expr = FilterExpr::In(field, values);
+                return Ok(if negated { FilterExpr::Not(Box::new(expr)) } else { expr });
             }
+        }
 
-            if rest.starts_with("endsWith(") && rest.ends_with(')') {
-                let arg = extract_string_arg(&rest[9..rest.len() - 1])?;
-                let field = Field::parse(field_str)?;
-                return Ok(FilterExpr::EndsWith(field, arg));
-            }
+        // Infix shorthands: `url ~= /v\d+/` for matches(), `contentType *= "json"`
+        // for contains(). Scanned before == and friends, which would split `~=` wrongly
+        for op in ["~=", "*="] {
+            if let Some(pos) = find_top_level(expr, op) {
+                let field_str = expr[..pos].trim();
+                let value_str = expr[pos + op.len()..].trim();
+                if field_str.is_empty() || value_str.is_empty() {
+                    bail!("Malformed comparison `{}` ({})", expr, COMPARISON_HINT);
+                }
+                let field = Field::parse(field_str)
+                    .map_err(|err| anyhow!("{} in `{}` (hint: see Available Fields in the README)", err, expr))?;
 
-            if rest.starts_with("matches(") && rest.ends_with(')') {
-                let pattern = extract_regex_arg(&rest[8..rest.len() - 1])?;
-                let field = Field::parse(field_str)?;
-                return Ok(FilterExpr::Matches(field, pattern));
+                return Ok(match op {
+                    "~=" => FilterExpr::Matches(field, extract_regex_arg(value_str)?),
+                    _ => FilterExpr::Contains(field, extract_string_arg(value_str)?),
+                });
             }
         }
 
-        // Binary comparison operators
-        for (op, constructor) in [
-            ("==", FilterExpr::Eq as fn(Field, Value) -> FilterExpr),
-            ("!=", FilterExpr::Ne as fn(Field, Value) -> FilterExpr),
-            (">=", FilterExpr::Ge as fn(Field, Value) -> FilterExpr),
-            ("<=", FilterExpr::Le as fn(Field, Value) -> FilterExpr),
-            (">", FilterExpr::Gt as fn(Field, Value) -> FilterExpr),
-            ("<", FilterExpr::Lt as fn(Field, Value) -> FilterExpr),
+        // Binary comparison operators, skipping any inside quoted values
+        for (op, compare_op, constructor) in [
+            ("==", CompareOp::Eq, FilterExpr::Eq as fn(Field, Value) -> FilterExpr),
+            ("!=", CompareOp::Ne, FilterExpr::Ne as fn(Field, Value) -> FilterExpr),
+            (">=", CompareOp::Ge, FilterExpr::Ge as fn(Field, Value) -> FilterExpr),
+            ("<=", CompareOp::Le, FilterExpr::Le as fn(Field, Value) -> FilterExpr),
+            (">", CompareOp::Gt, FilterExpr::Gt as fn(Field, Value) -> FilterExpr),
+            ("<", CompareOp::Lt, FilterExpr::Lt as fn(Field, Value) -> FilterExpr),
         ] {
-            if let Some(pos) = expr.find(op) {
+            if let Some(pos) = find_top_level(expr, op) {
                 let field_str = expr[..pos].trim();
                 let value_str = expr[pos + op.len()..].trim();
 
-                let field = Field::parse(field_str)?;
-                let value = Value::parse(value_str)?;
+                if field_str.is_empty() || value_str.is_empty() || value_str.starts_with(OPERATOR_CHARS) {
+                    bail!("Malformed comparison `{}` ({})", expr, COMPARISON_HINT);
+                }
+                let field = Field::parse(field_str)
+                    .map_err(|err| anyhow!("{} in `{}` (hint: see Available Fields in the README)", err, expr))?;
+
+                // A right side that is unambiguously a field compares the two
+                // fields; bare words like CONNECT or query stay literals
+                if let Some(other) = parse_field_operand(value_str)? {
+                    return Ok(FilterExpr::CompareFields(field, compare_op, other));
+                }
+
+                // Comparing against null tests presence instead of value
+                if value_str == "null" {
+                    return match op {
+                        "!=" => Ok(FilterExpr::Exists(field)),
+                        "==" => Ok(FilterExpr::Not(Box::new(FilterExpr::Exists(field)))),
+                        _ => bail!("Only == and != can compare with null: {}", expr),
+                    };
+                }
+
+                let value = Value::parse_for(&field, value_str)?;
 
                 return Ok(constructor(field, value));
             }
         }
 
-        // Try to parse as a standalone field name (for boolean checks like `isGraphQL`)
-        if let Ok(field) = Field::parse(expr) {
-            return Ok(FilterExpr::Bool(field));
+        // Anything with operator characters left over is a broken comparison,
+        // not a field name
+        if expr.is_empty() || expr.contains(OPERATOR_CHARS) {
+            bail!("Unable to parse expression `{}` ({})", expr, COMPARISON_HINT);
         }
 
-        bail!("Unable to parse expression: {}", expr);
+        // A standalone field name is a boolean check like `isGraphQL`
+        Field::parse(expr)
+            .map(FilterExpr::Bool)
+            .map_err(|err| anyhow!("{} (hint: a bare name must be a boolean field such as isGraphQL or failed)", err))
     }
 
     /// Evaluate filter against an entry
     pub fn matches(&self, entry: &Entry) -> bool {
         match self {
+            // A repeated header matches if any of its values does; `!=` holds
+            // only when none of them is equal
             FilterExpr::Eq(field, value) => {
-                field.get_value(entry).map_or(false, |v| v.eq_value(value))
+                field.any_value(entry, |v| v.eq_value(value))
             }
             FilterExpr::Ne(field, value) => {
-                field.get_value(entry).map_or(true, |v| !v.eq_value(value))
+                !field.any_value(entry, |v| v.eq_value(value))
             }
             FilterExpr::Gt(field, value) => {
-                field.get_value(entry).map_or(false, |v| v.gt_value(value))
+                field.any_value(entry, |v| v.gt_value(value))
             }
             FilterExpr::Ge(field, value) => {
-                field.get_value(entry).map_or(false, |v| v.ge_value(value))
+                field.any_value(entry, |v| v.ge_value(value))
             }
             FilterExpr::Lt(field, value) => {
-                field.get_value(entry).map_or(false, |v| v.lt_value(value))
+                field.any_value(entry, |v| v.lt_value(value))
             }
             FilterExpr::Le(field, value) => {
-                field.get_value(entry).map_or(false, |v| v.le_value(value))
+                field.any_value(entry, |v| v.le_value(value))
+            }
+            FilterExpr::In(field, values) => {
+                field.any_value(entry, |v| values.iter().any(|x| v.eq_value(x)))
             }
             FilterExpr::Contains(field, s) => {
-                field.get_string(entry).map_or(false, |v| v.contains(s))
+                field.any_string(entry, |v| v.contains(s))
+            }
+            FilterExpr::IContains(field, s) => {
+                field.any_string(entry, |v| v.to_ascii_lowercase().contains(s))
+            }
+            FilterExpr::IEquals(field, s) => {
+                field.any_string(entry, |v| v.eq_ignore_ascii_case(s))
             }
             FilterExpr::StartsWith(field, s) => {
-                field.get_string(entry).map_or(false, |v| v.starts_with(s))
+                field.any_string(entry, |v| v.starts_with(s))
             }
             FilterExpr::EndsWith(field, s) => {
-                field.get_string(entry).map_or(false, |v| v.ends_with(s))
+                field.any_string(entry, |v| v.ends_with(s))
             }
             FilterExpr::Matches(field, re) => {
-                field.get_string(entry).map_or(false, |v| re.is_match(&v))
+                field.any_string(entry, |v| re.is_match(v))
+            }
+            FilterExpr::InDomain(field, domain) => {
+                // url.inDomain(...) checks the URL's host
+                match field {
+                    Field::Url => in_domain(extract_host(&entry.request.url), domain),
+                    _ => field.any_string(entry, |h| in_domain(h, domain)),
+                }
             }
             FilterExpr::And(left, right) => {
                 left.matches(entry) && right.matches(entry)
@@ -227,12 +354,33 @@ impl FilterExpr {
             }
             FilterExpr::Not(inner) => !inner.matches(entry),
             FilterExpr::Bool(field) => {
-                field.get_value(entry).map_or(false, |v| v.is_truthy())
+                field.any_value(entry, |v| v.is_truthy())
+            }
+            FilterExpr::Exists(field) => field.any_value(entry, |_| true),
+            FilterExpr::CompareFields(left, op, right) => {
+                // A missing field equals nothing, as with literals
+                if *op == CompareOp::Ne {
+                    return !left.any_value(entry, |a| right.any_value(entry, |b| a.eq_value(b)));
+                }
+                left.any_value(entry, |a| right.any_value(entry, |b| op.apply(a, b)))
             }
         }
     }
 }
 
+impl CompareOp {
+    fn apply(&self, a: &Value, b: &Value) -> bool {
+        match self {
+            CompareOp::Eq => a.eq_value(b),
+            CompareOp::Ne => !a.eq_value(b),
+            CompareOp::Gt => a.gt_value(b),
+            CompareOp::Ge => a.ge_value(b),
+            CompareOp::Lt => a.lt_value(b),
+            CompareOp::Le => a.le_value(b),
+        }
+    }
+}
+
 impl Field {
     pub fn parse(s: &str) -> Result<Self> {
         let s = s.trim();
@@ -248,12 +396,45 @@ impl Field {
             return Ok(Field::ResponseHeader(name));
         }
 
+        // Query parameter access: query.param("name")
+        if s.starts_with("query.param(") && s.ends_with(')') {
+            let name = extract_string_arg(&s[12..s.len() - 1])?;
+            return Ok(Field::QueryParam(name));
+        }
+
+        // Path segment: path.segment(2) is "users" in /v1/users/123 (1-based)
+        if s.starts_with("path.segment(") && s.ends_with(')') {
+            let arg = s[13..s.len() - 1].trim();
+            return match arg.parse::<usize>() {
+                Ok(n) if n >= 1 => Ok(Field::PathSegment(n)),
+                _ => bail!("path.segment() takes a segment number starting at 1, got: {}", arg),
+            };
+        }
+
+        // GraphQL variable: gql.variable("userId") or a dotted path like "input.id"
+        if s.starts_with("gql.variable(") && s.ends_with(')') {
+            let name = extract_string_arg(&s[13..s.len() - 1])?;
+            return Ok(Field::GqlVariable(name));
+        }
+
+        // Header name pattern: request.headerMatch(/x-.*/) (names are matched case-insensitively)
+        if s.starts_with("request.headerMatch(") && s.ends_with(')') {
+            let pattern = extract_regex_pattern(&s[20..s.len() - 1])?;
+            return Ok(Field::RequestHeaderMatch(Regex::new(&format!("(?i){}", pattern))?));
+        }
+
+        if s.starts_with("response.headerMatch(") && s.ends_with(')') {
+            let pattern = extract_regex_pattern(&s[21..s.len() - 1])?;
+            return Ok(Field::ResponseHeaderMatch(Regex::new(&format!("(?i){}", pattern))?));
+        }
+
         Ok(match s.to_lowercase().as_str() {
             "method" => Field::Method,
             "url" => Field::Url,
             "host" => Field::Host,
             "domain" => Field::Domain,
             "path" => Field::Path,
+            "path.depth" | "pathdepth" | "path_depth" => Field::PathDepth,
             "scheme" | "protocol" => Field::Scheme,
             "query" | "querystring" | "query_string" => Field::Query,
             "status" => Field::Status,
@@ -261,16 +442,23 @@ impl Field {
             "time" => Field::Time,
             "starteddatetime" | "started_date_time" => Field::StartedDateTime,
             "serveripaddress" | "server_ip_address" | "serverip" => Field::ServerIpAddress,
+            "isfailed" | "is_failed" | "failed" => Field::IsFailed,
+            "resourcetype" | "resource_type" | "type" => Field::ResourceType,
+            "isxhr" | "is_xhr" => Field::IsXhr,
 
             "request.httpversion" | "request.http_version" => Field::RequestHttpVersion,
-            "request.headerssize" | "request.headers_size" => Field::RequestHeadersSize,
-            "request.bodysize" | "request.body_size" => Field::RequestBodySize,
+            "request.headerssize" | "request.headers_size" | "requestheaderssize" => Field::RequestHeadersSize,
+            "request.bodysize" | "request.body_size" | "requestbodysize" => Field::RequestBodySize,
 
             "response.httpversion" | "response.http_version" => Field::ResponseHttpVersion,
-            "response.headerssize" | "response.headers_size" => Field::ResponseHeadersSize,
-            "response.bodysize" | "response.body_size" | "bodysize" | "body_size" => Field::ResponseBodySize,
+            "response.headerssize" | "response.headers_size" | "responseheaderssize" => Field::ResponseHeadersSize,
+            "response.bodysize" | "response.body_size" | "responsebodysize" | "bodysize" | "body_size" => {
+                Field::ResponseBodySize
+            }
             "contenttype" | "content_type" | "response.contenttype" => Field::ContentType,
             "contentsize" | "content_size" | "response.content.size" => Field::ContentSize,
+            "maxage" | "max_age" | "cachettl" | "cache_ttl" => Field::MaxAge,
+            "setcookiecount" | "set_cookie_count" | "response.setcookiecount" => Field::SetCookieCount,
 
             "timings.blocked" | "blocked" => Field::TimingBlocked,
             "timings.dns" | "dns" => Field::TimingDns,
@@ -294,15 +482,24 @@ impl Field {
         match self {
             Field::Method => Some(Value::String(entry.request.method.clone())),
             Field::Url => Some(Value::String(entry.request.url.clone())),
-            Field::Host | Field::Domain => Some(Value::String(extract_host(&entry.request.url))),
+            Field::Host | Field::Domain => Some(Value::String(extract_host(&entry.request.url).to_string())),
             Field::Path => Some(Value::String(extract_path(&entry.request.url))),
+            Field::PathSegment(n) => path_segments(&entry.request.url).nth(n - 1).map(|s| Value::String(s.to_string())),
+            Field::PathDepth => Some(Value::Number(path_segments(&entry.request.url).count() as f64)),
             Field::Scheme => Some(Value::String(extract_scheme(&entry.request.url))),
             Field::Query => extract_query(&entry.request.url).map(Value::String),
             Field::Status => Some(Value::Number(entry.response.status as f64)),
             Field::StatusText => Some(Value::String(entry.response.status_text.clone())),
             Field::Time => Some(Value::Number(entry.time)),
-            Field::StartedDateTime => Some(Value::String(entry.started_date_time.clone())),
+            Field::StartedDateTime => Some(
+                entry
+                    .started_at()
+                    .map_or_else(|| Value::String(entry.started_date_time.clone()), Value::Time),
+            ),
             Field::ServerIpAddress => entry.server_ip_address.as_ref().map(|s| Value::String(s.clone())),
+            Field::IsFailed => Some(Value::Bool(entry.is_failed())),
+            Field::ResourceType => Some(Value::String(resource_type(entry))),
+            Field::IsXhr => Some(Value::Bool(matches!(resource_type(entry).as_str(), "xhr" | "fetch"))),
 
             Field::RequestHttpVersion => Some(Value::String(entry.request.http_version.clone())),
             Field::RequestHeadersSize => Some(Value::Number(entry.request.headers_size as f64)),
@@ -313,32 +510,122 @@ impl Field {
             Field::ResponseBodySize => Some(Value::Number(entry.response.body_size as f64)),
             Field::ContentType => entry.content_type().map(|s| Value::String(s.to_string())),
             Field::ContentSize => Some(Value::Number(entry.response.content.size as f64)),
-
-            Field::TimingBlocked => entry.timings.blocked.map(Value::Number),
-            Field::TimingDns => entry.timings.dns.map(Value::Number),
-            Field::TimingConnect => entry.timings.connect.map(Value::Number),
-            Field::TimingSsl => entry.timings.ssl.map(Value::Number),
-            Field::TimingSend => entry.timings.send.map(Value::Number),
-            Field::TimingWait => entry.timings.wait.map(Value::Number),
-            Field::TimingReceive => entry.timings.receive.map(Value::Number),
+            Field::SetCookieCount => Some(Value::Number(set_cookie_count(entry) as f64)),
+            Field::MaxAge => entry
+                .response_header("cache-control")
+                .and_then(parse_max_age)
+                .map(Value::Number),
+
+            Field::TimingBlocked => Timings::applicable(entry.timings.blocked).map(Value::Number),
+            Field::TimingDns => Timings::applicable(entry.timings.dns).map(Value::Number),
+            Field::TimingConnect => Timings::applicable(entry.timings.connect).map(Value::Number),
+            Field::TimingSsl => Timings::applicable(entry.timings.ssl).map(Value::Number),
+            Field::TimingSend => Timings::applicable(entry.timings.send).map(Value::Number),
+            Field::TimingWait => Timings::applicable(entry.timings.wait).map(Value::Number),
+            Field::TimingReceive => Timings::applicable(entry.timings.receive).map(Value::Number),
 
             Field::RequestHeader(name) => entry.request_header(name).map(|s| Value::String(s.to_string())),
             Field::ResponseHeader(name) => entry.response_header(name).map(|s| Value::String(s.to_string())),
+            Field::QueryParam(name) => query_param(entry, name).map(Value::String),
+            Field::RequestHeaderMatch(re) => {
+                Some(Value::Bool(entry.request.headers.iter().any(|h| re.is_match(&h.name))))
+            }
+            Field::ResponseHeaderMatch(re) => {
+                Some(Value::Bool(entry.response.headers.iter().any(|h| re.is_match(&h.name))))
+            }
 
             // GraphQL fields
             Field::GqlOperationName => extract_graphql_field(entry, "operationName"),
             Field::GqlOperationType => extract_graphql_operation_type(entry),
             Field::GqlQuery => extract_graphql_field(entry, "query"),
+            Field::GqlVariable(path) => extract_graphql_variable(entry, path),
             Field::IsGraphQL => Some(Value::Bool(is_graphql_request(entry))),
         }
     }
 
+    /// Whether any value of the field passes `pred`: each value of a repeated
+    /// header, otherwise the single value from `get_value`
+    pub fn any_value(&self, entry: &Entry, mut pred: impl FnMut(&Value) -> bool) -> bool {
+        let values = match self {
+            Field::RequestHeader(name) => entry.request_headers(name),
+            Field::ResponseHeader(name) => entry.response_headers(name),
+            _ => return self.get_value(entry).is_some_and(|v| pred(&v)),
+        };
+        values.into_iter().any(|s| pred(&Value::String(s.to_string())))
+    }
+
+    /// Like `any_value`, for string methods
+    pub fn any_string(&self, entry: &Entry, mut pred: impl FnMut(&str) -> bool) -> bool {
+        match self {
+            Field::RequestHeader(name) => entry.request_headers(name).into_iter().any(pred),
+            Field::ResponseHeader(name) => entry.response_headers(name).into_iter().any(pred),
+            _ => self.get_string(entry).is_some_and(|v| pred(&v)),
+        }
+    }
+
     pub fn get_string(&self, entry: &Entry) -> Option<String> {
-        self.get_value(entry).map(|v| v.to_string())
+        match self {
+            // String methods see the timestamp exactly as recorded
+            Field::StartedDateTime => Some(entry.started_date_time.clone()),
+            _ => self.get_value(entry).map(|v| v.to_string()),
+        }
+    }
+
+    /// Whether the field holds a byte count, so literals may carry kb/mb/gb suffixes
+    fn is_size(&self) -> bool {
+        matches!(
+            self,
+            Field::RequestHeadersSize
+                | Field::RequestBodySize
+                | Field::ResponseHeadersSize
+                | Field::ResponseBodySize
+                | Field::ContentSize
+        )
+    }
+
+    /// Fields measured in milliseconds, which accept `250ms`, `1.5s` and `2m` literals
+    fn is_duration(&self) -> bool {
+        matches!(
+            self,
+            Field::Time
+                | Field::TimingBlocked
+                | Field::TimingDns
+                | Field::TimingConnect
+                | Field::TimingSsl
+                | Field::TimingSend
+                | Field::TimingWait
+                | Field::TimingReceive
+        )
     }
 }
 
 impl Value {
+    /// Parse a literal compared against `field`; size fields accept `500kb`, `1mb`, etc.
+    /// and timing fields accept `250ms`, `1.5s`, `2m`. Bare numbers keep their meaning.
+    pub fn parse_for(field: &Field, s: &str) -> Result<Self> {
+        if field.is_size() {
+            if let Some(bytes) = parse_size(s) {
+                return Ok(Value::Number(bytes));
+            }
+        }
+        if field.is_duration() {
+            if let Some(ms) = parse_duration(s) {
+                return Ok(Value::Number(ms));
+            }
+        }
+        // Anything else would compare as text against a time and never match
+        if matches!(field, Field::StartedDateTime) {
+            return match parse_timestamp(&extract_string_arg(s)?) {
+                Some(t) => Ok(Value::Time(t)),
+                None => bail!(
+                    "startedDateTime compares with a timestamp like \"2024-01-01T12:00:00Z\" or a date like \"2024-01-01\", got {} (hint: use startsWith() to match a partial date)",
+                    s
+                ),
+            };
+        }
+        Self::parse(s)
+    }
+
     pub fn parse(s: &str) -> Result<Self> {
         let s = s.trim();
 
@@ -367,10 +654,11 @@ impl Value {
     fn eq_value(&self, other: &Value) -> bool {
         match (self, other) {
             (Value::String(a), Value::String(b)) => a == b,
-            (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
+            (Value::Number(a), Value::Number(b)) => numbers_equal(*a, *b),
             (Value::Bool(a), Value::Bool(b)) => a == b,
-            (Value::String(a), Value::Number(b)) => a.parse::<f64>().map_or(false, |n| (n - b).abs() < f64::EPSILON),
-            (Value::Number(a), Value::String(b)) => b.parse::<f64>().map_or(false, |n| (n - a).abs() < f64::EPSILON),
+            (Value::Time(a), Value::Time(b)) => a == b,
+            (Value::String(a), Value::Number(b)) => a.parse::<f64>().is_ok_and(|n| numbers_equal(n, *b)),
+            (Value::Number(a), Value::String(b)) => b.parse::<f64>().is_ok_and(|n| numbers_equal(n, *a)),
             _ => false,
         }
     }
@@ -378,8 +666,9 @@ impl Value {
     fn gt_value(&self, other: &Value) -> bool {
         match (self, other) {
             (Value::Number(a), Value::Number(b)) => a > b,
-            (Value::String(a), Value::Number(b)) => a.parse::<f64>().map_or(false, |n| n > *b),
-            (Value::Number(a), Value::String(b)) => b.parse::<f64>().map_or(false, |n| *a > n),
+            (Value::Time(a), Value::Time(b)) => a > b,
+            (Value::String(a), Value::Number(b)) => a.parse::<f64>().is_ok_and(|n| n > *b),
+            (Value::Number(a), Value::String(b)) => b.parse::<f64>().is_ok_and(|n| *a > n),
             (Value::String(a), Value::String(b)) => a > b,
             _ => false,
         }
@@ -392,8 +681,9 @@ impl Value {
     fn lt_value(&self, other: &Value) -> bool {
         match (self, other) {
             (Value::Number(a), Value::Number(b)) => a < b,
-            (Value::String(a), Value::Number(b)) => a.parse::<f64>().map_or(false, |n| n < *b),
-            (Value::Number(a), Value::String(b)) => b.parse::<f64>().map_or(false, |n| *a < n),
+            (Value::Time(a), Value::Time(b)) => a < b,
+            (Value::String(a), Value::Number(b)) => a.parse::<f64>().is_ok_and(|n| n < *b),
+            (Value::Number(a), Value::String(b)) => b.parse::<f64>().is_ok_and(|n| *a < n),
             (Value::String(a), Value::String(b)) => a < b,
             _ => false,
         }
@@ -408,20 +698,210 @@ impl Value {
             Value::Bool(b) => *b,
             Value::Number(n) => *n != 0.0,
             Value::String(s) => !s.is_empty(),
+            Value::Time(_) => true,
         }
     }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", s),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Time(t) => write!(f, "{}", t.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        }
+    }
+}
 
-    fn to_string(&self) -> String {
+/// Filter syntax with every `&&`/`||` group parenthesized, as printed by
+/// `filter --explain`: `a && b || c` shows as `((a && b) || c)`
+impl std::fmt::Display for FilterExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Value::String(s) => s.clone(),
-            Value::Number(n) => n.to_string(),
-            Value::Bool(b) => b.to_string(),
+            FilterExpr::Eq(field, value) => write!(f, "{} == {}", field, Literal(value)),
+            FilterExpr::Ne(field, value) => write!(f, "{} != {}", field, Literal(value)),
+            FilterExpr::Gt(field, value) => write!(f, "{} > {}", field, Literal(value)),
+            FilterExpr::Ge(field, value) => write!(f, "{} >= {}", field, Literal(value)),
+            FilterExpr::Lt(field, value) => write!(f, "{} < {}", field, Literal(value)),
+            FilterExpr::Le(field, value) => write!(f, "{} <= {}", field, Literal(value)),
+            FilterExpr::In(field, values) => {
+                let values: Vec<String> = values.iter().map(|v| Literal(v).to_string()).collect();
+                write!(f, "{} in [{}]", field, values.join(", "))
+            }
+            FilterExpr::Contains(field, s) => write!(f, "{}.contains({})", field, Quoted(s)),
+            FilterExpr::IContains(field, s) => write!(f, "{}.icontains({})", field, Quoted(s)),
+            FilterExpr::IEquals(field, s) => write!(f, "{}.iequals({})", field, Quoted(s)),
+            FilterExpr::StartsWith(field, s) => write!(f, "{}.startsWith({})", field, Quoted(s)),
+            FilterExpr::EndsWith(field, s) => write!(f, "{}.endsWith({})", field, Quoted(s)),
+            FilterExpr::Matches(field, re) => write!(f, "{}.matches({})", field, regex_literal(re.as_str())),
+            FilterExpr::InDomain(field, domain) => write!(f, "{}.inDomain({})", field, Quoted(domain)),
+            FilterExpr::And(left, right) => write!(f, "({} && {})", left, right),
+            FilterExpr::Or(left, right) => write!(f, "({} || {})", left, right),
+            FilterExpr::Not(inner) => match **inner {
+                FilterExpr::And(..) | FilterExpr::Or(..) | FilterExpr::Bool(_) | FilterExpr::Exists(_) => {
+                    write!(f, "!{}", inner)
+                }
+                _ => write!(f, "!({})", inner),
+            },
+            FilterExpr::Bool(field) => write!(f, "{}", field),
+            FilterExpr::Exists(field) => write!(f, "has({})", field),
+            FilterExpr::CompareFields(left, op, right) => {
+                let right = right.to_string();
+                if right.contains('.') {
+                    write!(f, "{} {} {}", left, op, right)
+                } else {
+                    write!(f, "{} {} field({})", left, op, right)
+                }
+            }
+        }
+    }
+}
+
+/// The right side of a comparison as a field: `field(name)`, or a dotted
+/// path such as `request.bodySize`. Anything else is a literal
+fn parse_field_operand(s: &str) -> Result<Option<Field>> {
+    if let Some(name) = s.strip_prefix("field(").and_then(|rest| rest.strip_suffix(')')) {
+        return Field::parse(name).map(Some);
+    }
+    if s.contains('.') && !s.starts_with(['"', '\'', '/']) {
+        return Ok(Field::parse(s).ok());
+    }
+    Ok(None)
+}
+
+impl std::fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+        })
+    }
+}
+
+/// The documented name of a field
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Field::Method => "method",
+            Field::Url => "url",
+            Field::Host => "host",
+            Field::Domain => "domain",
+            Field::Path => "path",
+            Field::PathSegment(n) => return write!(f, "path.segment({})", n),
+            Field::PathDepth => "path.depth",
+            Field::Scheme => "scheme",
+            Field::Query => "query",
+            Field::Status => "status",
+            Field::StatusText => "statusText",
+            Field::Time => "time",
+            Field::StartedDateTime => "startedDateTime",
+            Field::ServerIpAddress => "serverIPAddress",
+            Field::IsFailed => "isFailed",
+            Field::ResourceType => "resourceType",
+            Field::IsXhr => "isXhr",
+            Field::RequestHttpVersion => "request.httpVersion",
+            Field::RequestHeadersSize => "request.headersSize",
+            Field::RequestBodySize => "request.bodySize",
+            Field::ResponseHttpVersion => "response.httpVersion",
+            Field::ResponseHeadersSize => "response.headersSize",
+            Field::ResponseBodySize => "response.bodySize",
+            Field::ContentType => "contentType",
+            Field::ContentSize => "contentSize",
+            Field::MaxAge => "maxAge",
+            Field::SetCookieCount => "setCookieCount",
+            Field::TimingBlocked => "timings.blocked",
+            Field::TimingDns => "timings.dns",
+            Field::TimingConnect => "timings.connect",
+            Field::TimingSsl => "timings.ssl",
+            Field::TimingSend => "timings.send",
+            Field::TimingWait => "timings.wait",
+            Field::TimingReceive => "timings.receive",
+            Field::RequestHeader(name) => return write!(f, "request.header({})", Quoted(name)),
+            Field::QueryParam(name) => return write!(f, "query.param({})", Quoted(name)),
+            Field::ResponseHeader(name) => return write!(f, "response.header({})", Quoted(name)),
+            Field::RequestHeaderMatch(re) => {
+                return write!(f, "request.headerMatch({})", regex_literal(re.as_str().trim_start_matches("(?i)")))
+            }
+            Field::ResponseHeaderMatch(re) => {
+                return write!(f, "response.headerMatch({})", regex_literal(re.as_str().trim_start_matches("(?i)")))
+            }
+            Field::GqlOperationName => "operationName",
+            Field::GqlOperationType => "operationType",
+            Field::GqlQuery => "gql.query",
+            Field::GqlVariable(name) => return write!(f, "gql.variable({})", Quoted(name)),
+            Field::IsGraphQL => "isGraphQL",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A value written as a filter literal: strings and timestamps quoted
+struct Literal<'a>(&'a Value);
+
+impl std::fmt::Display for Literal<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Value::String(s) => write!(f, "{}", Quoted(s)),
+            Value::Time(_) => write!(f, "\"{}\"", self.0),
+            other => write!(f, "{}", other),
         }
     }
 }
 
+/// A string in double quotes, or single quotes if it contains a double quote
+struct Quoted<'a>(&'a str);
+
+impl std::fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quote = if self.0.contains('"') && !self.0.contains('\'') { '\'' } else { '"' };
+        write!(f, "{}{}{}", quote, self.0, quote)
+    }
+}
+
+/// `/pattern/flags`, turning a leading `(?flags)` group back into flags
+fn regex_literal(pattern: &str) -> String {
+    let flags = pattern
+        .strip_prefix("(?")
+        .and_then(|rest| rest.split_once(')'))
+        .filter(|(flags, _)| !flags.is_empty() && flags.chars().all(|c| "imsx".contains(c)));
+    match flags {
+        Some((flags, pattern)) => format!("/{}/{}", pattern, flags),
+        None => format!("/{}/", pattern),
+    }
+}
+
 // Helper functions
 
+/// Relative tolerance for comparing numbers with a fractional part
+const FLOAT_TOLERANCE: f64 = 1e-9;
+
+/// Whole numbers (statuses, byte sizes) compare exactly; others allow a
+/// relative error so `time == 0.3` matches a recorded `0.30000000000000004`
+fn numbers_equal(a: f64, b: f64) -> bool {
+    if a == b {
+        return true;
+    }
+    if a.fract() == 0.0 && b.fract() == 0.0 {
+        return false;
+    }
+    (a - b).abs() <= FLOAT_TOLERANCE * a.abs().max(b.abs())
+}
+
+/// Whether `host` is `domain` itself or one of its subdomains, ignoring
+/// ASCII case and a trailing dot; `notexample.com` is not in `example.com`
+fn in_domain(host: &str, domain: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
 fn extract_string_arg(s: &str) -> Result<String> {
     let s = s.trim();
     if (s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')) {
@@ -432,19 +912,73 @@ fn extract_string_arg(s: &str) -> Result<String> {
 }
 
 fn extract_regex_arg(s: &str) -> Result<Regex> {
+    Ok(Regex::new(&extract_regex_pattern(s)?)?)
+}
+
+/// Turn a `/pattern/flags` literal or quoted string into a regex pattern string
+fn extract_regex_pattern(s: &str) -> Result<String> {
     let s = s.trim();
 
-    if s.starts_with('/') && s.ends_with("/i") {
-        return Ok(Regex::new(&format!("(?i){}", &s[1..s.len() - 2]))?);
+    // Regex literal: /pattern/flags, where flags follow the last unescaped slash
+    if let Some(body) = s.strip_prefix('/') {
+        if let Some(end) = find_last_unescaped_slash(body) {
+            let pattern = &body[..end];
+            let flags = &body[end + 1..];
+
+            if let Some(bad) = flags.chars().find(|c| !"imsx".contains(*c)) {
+                bail!("Unknown regex flag '{}' in {}", bad, s);
+            }
+
+            return Ok(if flags.is_empty() {
+                pattern.to_string()
+            } else {
+                format!("(?{}){}", flags, pattern)
+            });
+        }
     }
 
-    let pattern = if s.starts_with('/') && s.ends_with('/') {
-        s[1..s.len() - 1].to_string()
-    } else {
-        extract_string_arg(s)?
-    };
+    extract_string_arg(s)
+}
+
+/// Find the byte offset of the last `/` not preceded by an odd number of backslashes
+fn find_last_unescaped_slash(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    (0..bytes.len()).rev().find(|&i| {
+        bytes[i] == b'/' && bytes[..i].iter().rev().take_while(|&&b| b == b'\\').count() % 2 == 0
+    })
+}
+
+/// Split a comma-separated list, ignoring commas inside quoted strings
+fn split_list(s: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut prev = '\0';
+
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) => {
+                if c == q && prev != '\\' {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                ',' => {
+                    items.push(s[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+        prev = c;
+    }
 
-    Ok(Regex::new(&pattern)?)
+    let last = s[start..].trim();
+    if !last.is_empty() || !items.is_empty() {
+        items.push(last);
+    }
+    items
 }
 
 fn is_balanced(s: &str) -> bool {
@@ -464,55 +998,59 @@ fn is_balanced(s: &str) -> bool {
     depth == 0
 }
 
+/// Byte offset of the first `pattern` outside quotes and parentheses
 fn find_top_level(s: &str, pattern: &str) -> Option<usize> {
+    top_level_positions(s, pattern).next()
+}
+
+/// Byte offset of the last `pattern` outside quotes and parentheses
+fn find_last_top_level(s: &str, pattern: &str) -> Option<usize> {
+    top_level_positions(s, pattern).last()
+}
+
+/// Byte offsets of `pattern` outside quotes, parentheses and the `/regex/`
+/// after `~=`, in order
+fn top_level_positions<'a>(s: &'a str, pattern: &'a str) -> impl Iterator<Item = usize> + 'a {
     let mut depth = 0;
     let mut in_string = false;
     let mut string_char = '"';
-    let chars: Vec<char> = s.chars().collect();
+    let mut prev = '\0';
 
-    for i in 0..chars.len() {
-        let c = chars[i];
+    s.char_indices().filter(move |&(i, c)| {
+        let escaped = prev == '\\';
+        prev = c;
 
         if in_string {
-            if c == string_char && (i == 0 || chars[i - 1] != '\\') {
+            if c == string_char && !escaped {
                 in_string = false;
             }
-            continue;
+            return false;
         }
 
         match c {
             '"' | '\'' => {
                 in_string = true;
                 string_char = c;
+                false
             }
-            '(' => depth += 1,
-            ')' => depth -= 1,
-            _ => {
-                if depth == 0 && s[i..].starts_with(pattern) {
-                    return Some(i);
-                }
+            // Elsewhere a slash is just part of an unquoted value such as a path
+            '/' if s[..i].trim_end().ends_with("~=") => {
+                in_string = true;
+                string_char = c;
+                false
+            }
+            '(' => {
+                depth += 1;
+                false
+            }
+            ')' => {
+                depth -= 1;
+                false
             }
+            _ => depth == 0 && s[i..].starts_with(pattern),
         }
-    }
-
-    None
-}
-
-/// Extract host/domain from URL (e.g., "https://api.example.com/path" -> "api.example.com")
-fn extract_host(url: &str) -> String {
-    let without_scheme = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))
-        .unwrap_or(url);
-
-    without_scheme
-        .split('/')
-        .next()
-        .unwrap_or(without_scheme)
-        .split(':')
-        .next()
-        .unwrap_or(without_scheme)
-        .to_string()
+    })
+    .map(|(i, _)| i)
 }
 
 /// Extract path from URL (e.g., "https://api.example.com/v1/users?id=1" -> "/v1/users")
@@ -532,6 +1070,14 @@ fn extract_path(url: &str) -> String {
         .unwrap_or_else(|| "/".to_string())
 }
 
+/// Non-empty path segments, so `/a//b/` has the segments "a" and "b"
+fn path_segments(url: &str) -> impl Iterator<Item = &str> {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme.find('/').map_or("", |i| &without_scheme[i..]);
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    path.split('/').filter(|s| !s.is_empty())
+}
+
 /// Extract scheme from URL (e.g., "https://example.com" -> "https")
 fn extract_scheme(url: &str) -> String {
     if url.starts_with("https://") {
@@ -548,12 +1094,125 @@ fn extract_query(url: &str) -> Option<String> {
     url.find('?').map(|i| url[i + 1..].to_string())
 }
 
+/// First value of a query parameter, decoded from the URL (or `queryString`)
+fn query_param(entry: &Entry, name: &str) -> Option<String> {
+    if let Some(query) = extract_query(&entry.request.url) {
+        let query = query.split('#').next().unwrap_or("");
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            if url_decode(key) == name {
+                return Some(url_decode(value));
+            }
+        }
+    }
+
+    entry
+        .request
+        .query_string
+        .iter()
+        .find(|p| p.name == name)
+        .map(|p| p.value.clone())
+}
+
+/// Resource type from Chrome's `_resourceType`, or classified from the response content type
+fn resource_type(entry: &Entry) -> String {
+    if let Some(kind) = entry.custom.get("_resourceType").and_then(|v| v.as_str()) {
+        return kind.to_lowercase();
+    }
+
+    if entry
+        .request_header("x-requested-with")
+        .is_some_and(|v| v.eq_ignore_ascii_case("XMLHttpRequest"))
+    {
+        return "xhr".to_string();
+    }
+
+    let mime = entry.content_type().unwrap_or("").to_lowercase();
+    let kind = if mime.contains("html") {
+        "document"
+    } else if mime.contains("css") {
+        "stylesheet"
+    } else if mime.contains("javascript") || mime.contains("ecmascript") {
+        "script"
+    } else if mime.starts_with("image/") {
+        "image"
+    } else if mime.starts_with("font/") || mime.contains("woff") || mime.contains("opentype") || mime.contains("truetype") {
+        "font"
+    } else if mime.starts_with("audio/") || mime.starts_with("video/") {
+        "media"
+    } else if mime.contains("event-stream") {
+        "eventsource"
+    } else if mime.contains("json") || mime.contains("xml") {
+        "xhr"
+    } else {
+        "other"
+    };
+    kind.to_string()
+}
+
+/// Parse a byte size with an optional b/kb/mb/gb suffix (1kb = 1024 bytes)
+fn parse_size(s: &str) -> Option<f64> {
+    let s = s.trim().to_lowercase();
+    let (number, multiplier) = [("gb", 1024.0 * 1024.0 * 1024.0), ("mb", 1024.0 * 1024.0), ("kb", 1024.0), ("b", 1.0)]
+        .into_iter()
+        .find_map(|(suffix, multiplier)| s.strip_suffix(suffix).map(|n| (n, multiplier)))?;
+
+    number.trim().parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Parse a duration with a ms/s/m suffix into milliseconds
+pub fn parse_duration(s: &str) -> Option<f64> {
+    let s = s.trim().to_lowercase();
+    // "ms" must be tried before "m" and "s"
+    let (number, multiplier) = [("ms", 1.0), ("s", 1000.0), ("m", 60_000.0)]
+        .into_iter()
+        .find_map(|(suffix, multiplier)| s.strip_suffix(suffix).map(|n| (n, multiplier)))?;
+
+    number.trim().parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Number of `Set-Cookie` response headers, or the parsed `cookies` list when
+/// the capture dropped those headers
+fn set_cookie_count(entry: &Entry) -> usize {
+    let headers = entry
+        .response
+        .headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("set-cookie"))
+        .count();
+
+    if headers > 0 { headers } else { entry.response.cookies.len() }
+}
+
+/// Effective cache lifetime in seconds from a Cache-Control header.
+///
+/// `no-store` and `no-cache` yield 0; otherwise `max-age` is used, falling
+/// back to `s-maxage`. Returns None when no lifetime is specified.
+fn parse_max_age(cache_control: &str) -> Option<f64> {
+    let mut max_age = None;
+    let mut s_maxage = None;
+
+    for directive in cache_control.split(',') {
+        let (name, value) = directive.split_once('=').unwrap_or((directive, ""));
+        let value = value.trim().trim_matches('"').parse::<f64>().ok();
+
+        match name.trim().to_lowercase().as_str() {
+            "no-store" | "no-cache" => return Some(0.0),
+            "max-age" => max_age = max_age.or(value),
+            "s-maxage" => s_maxage = s_maxage.or(value),
+            _ => {}
+        }
+    }
+
+    max_age.or(s_maxage)
+}
+
 /// Check if a request is a GraphQL request
 /// A request is considered GraphQL if:
 /// 1. Method is POST
 /// 2. Content-Type contains "json" or "graphql"
 /// 3. Request body is valid JSON with `operationName` or `query` field
-fn is_graphql_request(entry: &Entry) -> bool {
+pub fn is_graphql_request(entry: &Entry) -> bool {
     // Must be POST
     if entry.request.method.to_uppercase() != "POST" {
         return false;
@@ -597,6 +1256,30 @@ fn extract_graphql_field(entry: &Entry, field: &str) -> Option<Value> {
     }
 }
 
+/// Look up a value in the GraphQL `variables` object by dotted path.
+///
+/// Numbers and booleans keep their type; objects and arrays are compared as
+/// their JSON text. Variables sent as a JSON-encoded string are decoded first.
+fn extract_graphql_variable(entry: &Entry, path: &str) -> Option<Value> {
+    let json = get_request_body_json(entry)?;
+    let variables = match json.get("variables")? {
+        serde_json::Value::String(s) => serde_json::from_str(s).ok()?,
+        other => other.clone(),
+    };
+
+    let value = path
+        .split('.')
+        .try_fold(&variables, |current, key| current.get(key))?;
+
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(Value::String(s.clone())),
+        serde_json::Value::Bool(b) => Some(Value::Bool(*b)),
+        serde_json::Value::Number(n) => n.as_f64().map(Value::Number),
+        _ => Some(Value::String(value.to_string())),
+    }
+}
+
 /// Extract operation type (query/mutation/subscription) from GraphQL query string
 fn extract_graphql_operation_type(entry: &Entry) -> Option<Value> {
     let json = get_request_body_json(entry)?;
@@ -606,9 +1289,8 @@ fn extract_graphql_operation_type(entry: &Entry) -> Option<Value> {
     let trimmed = query.trim_start();
 
     for keyword in ["query", "mutation", "subscription"] {
-        if trimmed.starts_with(keyword) {
+        if let Some(rest) = trimmed.strip_prefix(keyword) {
             // Make sure it's followed by whitespace, '(', or '{'
-            let rest = &trimmed[keyword.len()..];
             if rest.is_empty()
                 || rest.starts_with(char::is_whitespace)
                 || rest.starts_with('(')
@@ -630,6 +1312,7 @@ fn extract_graphql_operation_type(entry: &Entry) -> Option<Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::har::fixtures;
 
     #[test]
     fn test_parse_simple_eq() {
@@ -643,9 +1326,722 @@ mod tests {
         assert!(matches!(expr, FilterExpr::Eq(Field::Method, Value::String(_))));
     }
 
+    #[test]
+    fn test_compare_two_fields() {
+        let expr = FilterExpr::parse("response.headersSize > request.headersSize").unwrap();
+        assert!(matches!(
+            expr,
+            FilterExpr::CompareFields(Field::ResponseHeadersSize, CompareOp::Gt, Field::RequestHeadersSize)
+        ));
+
+        let larger = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["request"]["headersSize"] = serde_json::json!(100);
+            e["response"]["headersSize"] = serde_json::json!(300);
+        });
+        let mut smaller = larger.clone();
+        smaller["response"]["headersSize"] = serde_json::json!(50);
+        let (larger, smaller): (Entry, Entry) =
+            (serde_json::from_value(larger).unwrap(), serde_json::from_value(smaller).unwrap());
+
+        assert!(expr.matches(&larger));
+        assert!(!expr.matches(&smaller));
+        assert!(FilterExpr::parse("response.bodySize >= request.bodySize").unwrap().matches(&larger));
+        assert!(FilterExpr::parse("host != field(path)").unwrap().matches(&larger));
+        assert!(FilterExpr::parse("field(nope) == 1").is_err());
+        assert!(FilterExpr::parse("method == field(nope)").is_err());
+    }
+
+    #[test]
+    fn test_literals_still_parse_as_values() {
+        for expr in ["status == 200", r#"method == "time""#, "method == POST", "isFailed == false", "host == example.com"] {
+            let parsed = FilterExpr::parse(expr).unwrap();
+            assert!(!matches!(parsed, FilterExpr::CompareFields(..)), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_bare_words_naming_fields_stay_literals() {
+        let connect = FilterExpr::parse("method == CONNECT").unwrap();
+        assert_eq!(connect.to_string(), "method == \"CONNECT\"");
+        assert!(connect.matches(&fixtures::entry("CONNECT", "https://example.com:443", 200)));
+        assert!(!connect.matches(&fixtures::entry("GET", "https://example.com/", 200)));
+
+        let query = FilterExpr::parse("operationType == query").unwrap();
+        assert!(matches!(&query, FilterExpr::Eq(Field::GqlOperationType, Value::String(s)) if s == "query"));
+
+        for expr in ["resourceType == type", "statusText != wait", "statusText == failed"] {
+            assert!(!matches!(FilterExpr::parse(expr).unwrap(), FilterExpr::CompareFields(..)), "{}", expr);
+        }
+    }
+
     #[test]
     fn test_parse_and() {
         let expr = FilterExpr::parse(r#"status == 200 && method == "GET""#).unwrap();
         assert!(matches!(expr, FilterExpr::And(_, _)));
     }
+
+    #[test]
+    fn test_in_numeric_list() {
+        let expr = FilterExpr::parse("status in [200, 201 ,204]").unwrap();
+        assert!(matches!(&expr, FilterExpr::In(Field::Status, values) if values.len() == 3));
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/", 204)));
+        assert!(!expr.matches(&fixtures::entry("GET", "https://example.com/", 404)));
+    }
+
+    #[test]
+    fn test_in_string_list() {
+        let expr = FilterExpr::parse(r#"method in [ "GET", 'HEAD' ]"#).unwrap();
+        assert!(expr.matches(&fixtures::entry("HEAD", "https://example.com/", 200)));
+        assert!(!expr.matches(&fixtures::entry("POST", "https://example.com/", 200)));
+
+        let expr = FilterExpr::parse(r#"url in ["https://example.com/a,b", "x"]"#).unwrap();
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/a,b", 200)));
+    }
+
+    #[test]
+    fn test_in_domain() {
+        let expr = FilterExpr::parse(r#"host.inDomain("example.com")"#).unwrap();
+        let matches = |url: &str| expr.matches(&fixtures::entry("GET", url, 200));
+
+        assert!(matches("https://example.com/"));
+        assert!(matches("https://api.example.com/v1"));
+        assert!(matches("https://a.b.EXAMPLE.com:8443/"));
+        assert!(!matches("https://notexample.com/"));
+        assert!(!matches("https://example.com.evil.net/"));
+        assert!(!matches("https://example.org/"));
+
+        // Wildcard and leading-dot spellings mean the same; url checks its host
+        for source in [r#"url.inDomain("*.Example.com")"#, r#"domain.inDomain(".example.com")"#] {
+            let expr = FilterExpr::parse(source).unwrap();
+            assert!(expr.matches(&fixtures::entry("GET", "https://cdn.example.com/x?next=notexample.com", 200)));
+            assert!(!expr.matches(&fixtures::entry("GET", "https://notexample.com/example.com", 200)));
+        }
+
+        assert!(FilterExpr::parse(r#"host.inDomain("")"#).is_err());
+    }
+
+    #[test]
+    fn test_paren_list() {
+        let expr = FilterExpr::parse(r#"method in ("GET", "POST")"#).unwrap();
+        assert!(matches!(&expr, FilterExpr::In(Field::Method, values) if values.len() == 2));
+        assert!(expr.matches(&fixtures::entry("POST", "https://example.com/", 200)));
+        assert!(!expr.matches(&fixtures::entry("PUT", "https://example.com/", 200)));
+
+        let expr = FilterExpr::parse(r#"url in ("https://example.com/a,b") && status in (200)"#).unwrap();
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/a,b", 200)));
+    }
+
+    #[test]
+    fn test_not_in() {
+        for source in ["status not in [301, 302]", "status not in (301,302)"] {
+            let expr = FilterExpr::parse(source).unwrap();
+            assert!(matches!(&expr, FilterExpr::Not(inner) if matches!(**inner, FilterExpr::In(Field::Status, ref v) if v.len() == 2)));
+            assert!(expr.matches(&fixtures::entry("GET", "https://example.com/", 200)));
+            assert!(!expr.matches(&fixtures::entry("GET", "https://example.com/", 302)));
+        }
+
+        let expr = FilterExpr::parse(r#"method not in ['GET', "HEAD"] || status == 500"#).unwrap();
+        assert!(expr.matches(&fixtures::entry("POST", "https://example.com/", 200)));
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/", 500)));
+        assert!(!expr.matches(&fixtures::entry("HEAD", "https://example.com/", 200)));
+
+        // A quoted " not in " is part of the value
+        let expr = FilterExpr::parse(r#"statusText in ["was not in cache"]"#).unwrap();
+        assert!(matches!(&expr, FilterExpr::In(Field::StatusText, values) if values.len() == 1));
+    }
+
+    #[test]
+    fn test_response_header_match() {
+        let json = fixtures::entry_with("GET", "https://api.example.com/", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                { "name": "Content-Type", "value": "application/json" },
+                { "name": "X-RateLimit-Remaining", "value": "0" }
+            ]);
+        });
+        let limited = fixtures::to_entry(json);
+        let plain = fixtures::entry("GET", "https://api.example.com/", 200);
+
+        let expr = FilterExpr::parse("response.headerMatch(/x-ratelimit-.*/)").unwrap();
+        assert!(expr.matches(&limited));
+        assert!(!expr.matches(&plain));
+        assert!(!FilterExpr::parse("request.headerMatch(/x-ratelimit-.*/)").unwrap().matches(&limited));
+        assert!(FilterExpr::parse("!response.headerMatch(/x-ratelimit-.*/)").unwrap().matches(&plain));
+    }
+
+    #[test]
+    fn test_repeated_header_matches_any_value() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                { "name": "Set-Cookie", "value": "a=1; Path=/" },
+                { "name": "Content-Length", "value": "10" },
+                { "name": "set-cookie", "value": "b=2; Secure; HttpOnly" },
+                { "name": "Content-Length", "value": "12" }
+            ]);
+        });
+        let entry = fixtures::to_entry(json);
+        assert_eq!(entry.response_headers("SET-COOKIE"), ["a=1; Path=/", "b=2; Secure; HttpOnly"]);
+        assert_eq!(entry.response_header("set-cookie"), Some("a=1; Path=/"));
+        assert!(entry.request_headers("Set-Cookie").is_empty());
+
+        let matches = |expr: &str| FilterExpr::parse(expr).unwrap().matches(&entry);
+        assert!(matches(r#"response.header("Set-Cookie").contains("Secure")"#));
+        assert!(matches(r#"response.header("Set-Cookie").startsWith("b=")"#));
+        assert!(matches(r#"response.header("Set-Cookie") ~= /httponly/i"#));
+        assert!(!matches(r#"response.header("Set-Cookie").contains("SameSite")"#));
+        assert!(matches(r#"response.header("Content-Length") > 11"#));
+        assert!(matches(r#"response.header("Content-Length") in [12, 99]"#));
+        // != only holds when no value is equal
+        assert!(!matches(r#"response.header("Content-Length") != 12"#));
+        assert!(matches(r#"response.header("Content-Length") != 11"#));
+        assert!(matches(r#"!response.header("Set-Cookie").contains("SameSite")"#));
+    }
+
+    #[test]
+    fn test_number_equality() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["bodySize"] = serde_json::json!(5000000);
+            e["time"] = serde_json::json!(0.1 + 0.2);
+            e["timings"]["wait"] = serde_json::json!(12.345);
+        });
+        let entry = fixtures::to_entry(json);
+        let matches = |expr: &str| FilterExpr::parse(expr).unwrap().matches(&entry);
+
+        assert!(matches("bodySize == 5000000"));
+        assert!(!matches("bodySize == 5000001"));
+        assert!(matches("bodySize != 4999999"));
+        assert!(matches("bodySize >= 5000000 && bodySize <= 5000000"));
+
+        assert!(matches("time == 0.3"));
+        assert!(!matches("time == 0.31"));
+        assert!(matches("timings.wait == 12.345"));
+        assert!(!matches("timings.wait == 12.3451"));
+
+        assert!(numbers_equal(1e15 + 0.5, 1e15 + 0.5));
+        assert!(!numbers_equal(1e15, 1e15 + 1.0));
+        assert!(!numbers_equal(0.0, 1e-12));
+    }
+
+    #[test]
+    fn test_parse_max_age() {
+        assert_eq!(parse_max_age("max-age=3600"), Some(3600.0));
+        assert_eq!(parse_max_age("public, max-age=60, must-revalidate"), Some(60.0));
+        assert_eq!(parse_max_age("s-maxage=300"), Some(300.0));
+        assert_eq!(parse_max_age("max-age=10, s-maxage=300"), Some(10.0));
+        assert_eq!(parse_max_age("Max-Age=\"5\""), Some(5.0));
+        assert_eq!(parse_max_age("no-store"), Some(0.0));
+        assert_eq!(parse_max_age("no-cache, max-age=3600"), Some(0.0));
+        assert_eq!(parse_max_age("public"), None);
+        assert_eq!(parse_max_age("max-age=abc"), None);
+    }
+
+    #[test]
+    fn test_max_age_field() {
+        let json = fixtures::entry_with("GET", "https://example.com/app.js", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                { "name": "Cache-Control", "value": "public, max-age=30" }
+            ]);
+        });
+        let short = fixtures::to_entry(json);
+        let uncached = fixtures::entry("GET", "https://example.com/", 200);
+
+        let expr = FilterExpr::parse("maxAge < 60").unwrap();
+        assert!(expr.matches(&short));
+        assert!(!expr.matches(&uncached));
+    }
+
+    fn entry_with_dns(dns: Option<f64>) -> Entry {
+        let mut json = fixtures::entry_json("GET", "https://example.com/", 200);
+        if let Some(dns) = dns {
+            json["timings"]["dns"] = serde_json::json!(dns);
+        }
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_size_suffixes() {
+        assert_eq!(parse_size("1mb"), Some(1048576.0));
+        assert_eq!(parse_size("500KB"), Some(512000.0));
+        assert_eq!(parse_size("1.5 gb"), Some(1.5 * 1073741824.0));
+        assert_eq!(parse_size("42b"), Some(42.0));
+        assert_eq!(parse_size("mb"), None);
+        assert_eq!(parse_size("lots"), None);
+
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["bodySize"] = serde_json::json!(2 * 1024 * 1024);
+            e["request"]["headers"] = serde_json::json!([{"name": "X-Limit", "value": "1mb"}]);
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert!(FilterExpr::parse("bodySize > 1mb").unwrap().matches(&entry));
+        assert!(!FilterExpr::parse("response.bodySize >= 3MB").unwrap().matches(&entry));
+        assert!(FilterExpr::parse("bodySize in [2mb, 4mb]").unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_size_suffix_only_for_size_fields() {
+        assert!(matches!(Value::parse_for(&Field::Status, "1mb").unwrap(), Value::String(_)));
+        assert!(matches!(Value::parse_for(&Field::ContentSize, "1mb").unwrap(), Value::Number(_)));
+
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["request"]["headers"] = serde_json::json!([{"name": "X-Limit", "value": "1mb"}]);
+        });
+        let entry = fixtures::to_entry(json);
+        assert!(FilterExpr::parse(r#"request.header("X-Limit") == 1mb"#).unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_duration_suffixes() {
+        assert_eq!(parse_duration("250ms"), Some(250.0));
+        assert_eq!(parse_duration("1.5s"), Some(1500.0));
+        assert_eq!(parse_duration("2m"), Some(120_000.0));
+        assert_eq!(parse_duration("1 S"), Some(1000.0));
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration("1500"), None);
+
+        assert!(matches!(FilterExpr::parse("time > 1.5s").unwrap(), FilterExpr::Gt(Field::Time, Value::Number(n)) if n == 1500.0));
+        assert!(matches!(FilterExpr::parse("time > 1500").unwrap(), FilterExpr::Gt(Field::Time, Value::Number(n)) if n == 1500.0));
+        assert!(matches!(Value::parse_for(&Field::Status, "2s").unwrap(), Value::String(_)));
+    }
+
+    #[test]
+    fn test_duration_literals_evaluate() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["time"] = serde_json::json!(1600.0);
+            e["timings"]["wait"] = serde_json::json!(300.0);
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert!(FilterExpr::parse("time > 1.5s").unwrap().matches(&entry));
+        assert!(!FilterExpr::parse("time > 2s").unwrap().matches(&entry));
+        assert!(FilterExpr::parse("timings.wait > 250ms && wait < 1m").unwrap().matches(&entry));
+        assert!(FilterExpr::parse("time in [1.6s, 2s]").unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_set_cookie_count() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                {"name": "Set-Cookie", "value": "a=1"},
+                {"name": "Content-Type", "value": "text/html"},
+                {"name": "set-cookie", "value": "b=2"},
+                {"name": "SET-COOKIE", "value": "c=3"}
+            ]);
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert_eq!(set_cookie_count(&entry), 3);
+        assert!(FilterExpr::parse("setCookieCount == 3").unwrap().matches(&entry));
+        assert!(!FilterExpr::parse("setCookieCount > 5").unwrap().matches(&entry));
+
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["cookies"] = serde_json::json!([{"name": "a", "value": "1"}, {"name": "b", "value": "2"}]);
+        });
+        let entry = fixtures::to_entry(json);
+        assert_eq!(set_cookie_count(&entry), 2);
+    }
+
+    #[test]
+    fn test_query_param() {
+        let entry = fixtures::entry("GET", "https://example.com/search?env=prod&q=caf%C3%A9+latte&id=1&id=2&flag#top", 200);
+        let get = |name: &str| query_param(&entry, name);
+
+        assert_eq!(get("env").as_deref(), Some("prod"));
+        assert_eq!(get("q").as_deref(), Some("café latte"));
+        assert_eq!(get("id").as_deref(), Some("1"));
+        assert_eq!(get("flag").as_deref(), Some(""));
+        assert_eq!(get("missing"), None);
+
+        assert!(FilterExpr::parse(r#"query.param("env") == "prod""#).unwrap().matches(&entry));
+        assert!(FilterExpr::parse(r#"query.param("id") in [1, 3]"#).unwrap().matches(&entry));
+        assert!(FilterExpr::parse(r#"query.param("missing") == null"#).unwrap().matches(&entry));
+        assert!(!FilterExpr::parse(r#"query.param("missing") == ''"#).unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_started_date_time_compares_chronologically() {
+        let mut json = fixtures::entry_json("GET", "https://example.com/", 200);
+        // 08:00 UTC, though it sorts after "2024-01-01T09" as a string
+        json["startedDateTime"] = serde_json::json!("2024-01-01T10:00:00.000+02:00");
+        let entry = fixtures::to_entry(json);
+        let matches = |expr: &str| FilterExpr::parse(expr).unwrap().matches(&entry);
+
+        assert!(matches(r#"startedDateTime < "2024-01-01T09:00:00Z""#));
+        assert!(matches(r#"startedDateTime > "2024-01-01T07:59:59.999Z""#));
+        assert!(matches(r#"startedDateTime == "2024-01-01T08:00:00Z""#));
+        assert!(matches(r#"startedDateTime >= "2024-01-01""#));
+        assert!(matches(r#"startedDateTime.startsWith("2024-01-01T10")"#));
+
+        for expr in [r#"startedDateTime > "2024-01""#, "startedDateTime == yesterday", r#"startedDateTime in ["2024-01-01", "2024"]"#] {
+            let err = FilterExpr::parse(expr).unwrap_err().to_string();
+            assert!(err.starts_with("startedDateTime compares with a timestamp"), "{}: {}", expr, err);
+        }
+    }
+
+    #[test]
+    fn test_path_segment_and_depth() {
+        let segments = |url: &'static str| path_segments(url).collect::<Vec<_>>();
+        assert_eq!(segments("https://example.com/v1/users/123?x=/y#a/b"), ["v1", "users", "123"]);
+        assert_eq!(segments("https://example.com/v1/users/"), ["v1", "users"]);
+        assert_eq!(segments("https://example.com//v1//users"), ["v1", "users"]);
+        assert!(segments("https://example.com").is_empty());
+        assert!(segments("https://example.com/?q=1").is_empty());
+
+        let entry = fixtures::entry("GET", "https://example.com/v1/users/123/", 200);
+        let matches = |expr: &str| FilterExpr::parse(expr).unwrap().matches(&entry);
+        assert!(matches(r#"path.segment(2) == "users""#));
+        assert!(matches(r#"path.segment(3) == 123"#));
+        assert!(matches("path.depth == 3"));
+        assert!(matches("path.segment(4) == null"));
+        assert!(!matches("has(path.segment(4))"));
+
+        assert!(FilterExpr::parse("path.segment(0) == 'x'").is_err());
+        assert!(FilterExpr::parse("path.segment(x) == 'x'").is_err());
+    }
+
+    #[test]
+    fn test_icontains_and_iequals() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["content"]["mimeType"] = serde_json::json!("Application/JSON; charset=UTF-8");
+            e["request"]["headers"] = serde_json::json!([{"name": "Accept", "value": "Application/Json"}]);
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert!(FilterExpr::parse(r#"contentType.icontains("json")"#).unwrap().matches(&entry));
+        assert!(FilterExpr::parse(r#"contentType.icontains("JSON")"#).unwrap().matches(&entry));
+        assert!(!FilterExpr::parse(r#"contentType.contains("json")"#).unwrap().matches(&entry));
+
+        assert!(FilterExpr::parse(r#"request.header("Accept").icontains("json")"#).unwrap().matches(&entry));
+        assert!(FilterExpr::parse(r#"request.header("Accept").iequals("application/json")"#).unwrap().matches(&entry));
+        assert!(!FilterExpr::parse(r#"request.header("Accept").iequals("json")"#).unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_method_call_after_multibyte_string() {
+        let entry = fixtures::entry("GET", "https://example.com/café", 200);
+        let filter = FilterExpr::parse(r#"request.header("été").contains("x") || url.endsWith("é")"#).unwrap();
+        assert!(filter.matches(&entry));
+    }
+
+    fn graphql_entry(variables: serde_json::Value) -> Entry {
+        let body = serde_json::json!({
+            "operationName": "GetUser",
+            "query": "query GetUser($userId: ID!) { user(id: $userId) { name } }",
+            "variables": variables
+        });
+        let json = fixtures::entry_with("POST", "https://example.com/graphql", 200, |e| {
+            e["request"]["postData"] = serde_json::json!({
+                "mimeType": "application/json",
+                "text": body.to_string()
+            });
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_gql_variable_string_and_number() {
+        let entry = graphql_entry(serde_json::json!({"userId": "42", "limit": 10, "input": {"id": 7, "tags": ["a"]}}));
+        let matches = |expr: &str| FilterExpr::parse(expr).unwrap().matches(&entry);
+
+        assert!(matches(r#"gql.variable("userId") == "42""#));
+        assert!(matches(r#"gql.variable("userId") == 42"#));
+        assert!(matches(r#"gql.variable("limit") == 10"#));
+        assert!(matches(r#"gql.variable("limit") > 5"#));
+        assert!(matches(r#"gql.variable("limit") == "10""#));
+        assert!(matches(r#"gql.variable("input.id") == 7"#));
+        assert!(matches(r#"gql.variable("input.tags") == '["a"]'"#));
+        assert!(matches(r#"gql.variable("missing") == null"#));
+    }
+
+    #[test]
+    fn test_gql_variables_as_encoded_string() {
+        let entry = graphql_entry(serde_json::json!(r#"{"userId": "42"}"#));
+        assert!(FilterExpr::parse(r#"gql.variable("userId") == "42""#).unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_exists_on_missing_timing() {
+        let missing = entry_with_dns(None);
+        let zero = entry_with_dns(Some(0.0));
+
+        for expr in ["has(timings.dns)", "timings.dns != null"] {
+            let filter = FilterExpr::parse(expr).unwrap();
+            assert!(!filter.matches(&missing), "{}", expr);
+            assert!(filter.matches(&zero), "{}", expr);
+        }
+
+        let absent = FilterExpr::parse("dns == null").unwrap();
+        assert!(absent.matches(&missing));
+        assert!(!absent.matches(&zero));
+
+        // Missing and "not greater" are both false for comparisons
+        let gt = FilterExpr::parse("timings.dns > 0").unwrap();
+        assert!(!gt.matches(&missing));
+        assert!(!gt.matches(&zero));
+    }
+
+    #[test]
+    fn test_not_applicable_timing_is_absent() {
+        let missing = entry_with_dns(None);
+        let not_applicable = entry_with_dns(Some(-1.0));
+        let zero = entry_with_dns(Some(0.0));
+
+        let has = FilterExpr::parse("has(timings.dns)").unwrap();
+        assert!(!has.matches(&missing));
+        assert!(!has.matches(&not_applicable));
+        assert!(has.matches(&zero));
+
+        assert!(FilterExpr::parse("dns == null").unwrap().matches(&not_applicable));
+
+        // -1 no longer sneaks under an upper bound
+        let below = FilterExpr::parse("timings.dns < 10").unwrap();
+        assert!(!below.matches(&missing));
+        assert!(!below.matches(&not_applicable));
+        assert!(below.matches(&zero));
+
+        let nonneg = FilterExpr::parse("timings.dns >= 0").unwrap();
+        assert!(!nonneg.matches(&not_applicable));
+        assert!(nonneg.matches(&zero));
+    }
+
+    #[test]
+    fn test_operators_inside_quoted_values() {
+        let json = fixtures::entry_with("GET", "https://example.com/?q=a==b", 200, |e| {
+            e["response"]["statusText"] = serde_json::json!("x>y");
+        });
+        let entry = fixtures::to_entry(json);
+
+        for expr in [
+            r#"url == "https://example.com/?q=a==b""#,
+            r#"url != "a==b""#,
+            r#"statusText == "x>y""#,
+            r#"statusText != "x<y""#,
+            r#"statusText != 'a!=b'"#,
+            r#"statusText >= "x>=""#,
+            r#"url.contains("a==b") && statusText == "x>y""#,
+        ] {
+            let filter = FilterExpr::parse(expr).unwrap_or_else(|e| panic!("{}: {}", expr, e));
+            assert!(filter.matches(&entry), "{}", expr);
+        }
+
+        assert!(!FilterExpr::parse(r#"statusText == "x<y""#).unwrap().matches(&entry));
+        assert!(matches!(
+            FilterExpr::parse(r#"statusText != "a>b""#).unwrap(),
+            FilterExpr::Ne(Field::StatusText, Value::String(ref s)) if s == "a>b"
+        ));
+    }
+
+    /// Entry where each of `a`..`d` below is true or false as given
+    fn truth_entry(a: bool, b: bool, c: bool, d: bool) -> Entry {
+        let method = if a { "GET" } else { "POST" };
+        let url = if c { "https://example.com/c" } else { "https://example.com/x" };
+        let json = fixtures::entry_with(method, url, if b { 200 } else { 404 }, |e| {
+            e["time"] = serde_json::json!(if d { 500.0 } else { 50.0 });
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    /// Parse `template` with a..d replaced by comparisons, then check it
+    /// against `expected` for all 16 combinations
+    fn check_truth_table(template: &str, expected: fn(bool, bool, bool, bool) -> bool) {
+        let expr: String = template
+            .chars()
+            .map(|ch| match ch {
+                'a' => r#"method == "GET""#.to_string(),
+                'b' => "status == 200".to_string(),
+                'c' => r#"url.endsWith("/c")"#.to_string(),
+                'd' => "time > 100".to_string(),
+                _ => ch.to_string(),
+            })
+            .collect();
+        let filter = FilterExpr::parse(&expr).unwrap();
+
+        for bits in 0..16u8 {
+            let [a, b, c, d] = [bits & 8 != 0, bits & 4 != 0, bits & 2 != 0, bits & 1 != 0];
+            assert_eq!(
+                filter.matches(&truth_entry(a, b, c, d)),
+                expected(a, b, c, d),
+                "{} with a={} b={} c={} d={}", template, a, b, c, d
+            );
+        }
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        check_truth_table("a || b && c", |a, b, c, _| a || (b && c));
+        check_truth_table("a && b || c", |a, b, c, _| (a && b) || c);
+        check_truth_table("a && b || c && d", |a, b, c, d| (a && b) || (c && d));
+        check_truth_table("a || b && c || d", |a, b, c, d| a || (b && c) || d);
+        check_truth_table("!a && b || !c", |a, b, c, _| (!a && b) || !c);
+        check_truth_table("(a || b) && (c || d)", |a, b, c, d| (a || b) && (c || d));
+    }
+
+    #[test]
+    fn test_chains_are_left_associative() {
+        let parsed = FilterExpr::parse("isFailed || isXhr || isGraphQL").unwrap();
+        assert!(matches!(
+            parsed,
+            FilterExpr::Or(ref left, ref right)
+                if matches!(**left, FilterExpr::Or(..)) && matches!(**right, FilterExpr::Bool(Field::IsGraphQL))
+        ));
+
+        let parsed = FilterExpr::parse("isFailed && isXhr && isGraphQL").unwrap();
+        assert!(matches!(parsed, FilterExpr::And(ref left, _) if matches!(**left, FilterExpr::And(..))));
+
+        // Operators inside strings and parentheses are not split points
+        let parsed = FilterExpr::parse(r#"url.contains("a||b") || (isXhr || isGraphQL)"#).unwrap();
+        assert!(matches!(parsed, FilterExpr::Or(ref left, _) if matches!(**left, FilterExpr::Contains(..))));
+    }
+
+    #[test]
+    fn test_null_rejects_ordering_operators() {
+        assert!(FilterExpr::parse("timings.dns > null").is_err());
+        assert!(FilterExpr::parse("has(bogus)").is_err());
+    }
+
+    #[test]
+    fn test_resource_type_from_custom_field() {
+        let json = fixtures::entry_with("GET", "https://example.com/app", 200, |e| {
+            e["_resourceType"] = serde_json::json!("Script");
+            e["response"]["content"]["mimeType"] = serde_json::json!("text/plain");
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert!(FilterExpr::parse(r#"resourceType == "script""#).unwrap().matches(&entry));
+        assert!(!FilterExpr::parse("isXhr").unwrap().matches(&entry));
+    }
+
+    #[test]
+    fn test_resource_type_content_type_fallback() {
+        let with_mime = |mime: &str| -> Entry {
+            let json = fixtures::entry_with("GET", "https://example.com/x", 200, |e| {
+                e["response"]["content"]["mimeType"] = serde_json::json!(mime);
+            });
+            serde_json::from_value(json).unwrap()
+        };
+
+        assert_eq!(resource_type(&with_mime("text/html; charset=utf-8")), "document");
+        assert_eq!(resource_type(&with_mime("text/css")), "stylesheet");
+        assert_eq!(resource_type(&with_mime("application/javascript")), "script");
+        assert_eq!(resource_type(&with_mime("image/png")), "image");
+        assert_eq!(resource_type(&with_mime("font/woff2")), "font");
+        assert_eq!(resource_type(&with_mime("application/octet-stream")), "other");
+
+        let api = with_mime("application/json");
+        assert_eq!(resource_type(&api), "xhr");
+        assert!(FilterExpr::parse("isXhr").unwrap().matches(&api));
+    }
+
+    #[test]
+    fn test_status_zero_is_failed() {
+        let aborted = fixtures::entry("GET", "https://example.com/", 0);
+        let ok = fixtures::entry("GET", "https://example.com/", 200);
+        let expr = FilterExpr::parse("isFailed").unwrap();
+        assert!(expr.matches(&aborted));
+        assert!(!expr.matches(&ok));
+        assert!(FilterExpr::parse("!isFailed").unwrap().matches(&ok));
+    }
+
+    #[test]
+    fn test_regex_escaped_slash() {
+        let re = extract_regex_arg(r"/a\/b/").unwrap();
+        assert!(re.is_match("a/b"));
+        assert!(!re.is_match("A/B"));
+    }
+
+    #[test]
+    fn test_regex_case_insensitive_flag() {
+        let re = extract_regex_arg("/abc/i").unwrap();
+        assert!(re.is_match("xABCx"));
+    }
+
+    #[test]
+    fn test_regex_without_flags() {
+        let re = extract_regex_arg("/abc/").unwrap();
+        assert!(re.is_match("abc"));
+        assert!(!re.is_match("ABC"));
+    }
+
+    #[test]
+    fn test_regex_trailing_escaped_slash_i_is_literal() {
+        let re = extract_regex_arg(r"/path\/i/").unwrap();
+        assert!(re.is_match("/path/i"));
+        assert!(!re.is_match("/PATH"));
+    }
+
+    #[test]
+    fn test_regex_combined_flags() {
+        let re = extract_regex_arg("/^abc$/im").unwrap();
+        assert!(re.is_match("x\nABC\ny"));
+        assert!(extract_regex_arg("/abc/q").is_err());
+    }
+
+    #[test]
+    fn test_infix_match_and_contains() {
+        let expr = FilterExpr::parse(r"url ~= /\/api\/v\d+/").unwrap();
+        assert!(matches!(expr, FilterExpr::Matches(Field::Url, ref re) if re.as_str() == r"\/api\/v\d+"));
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/api/v2/users", 200)));
+        assert!(!expr.matches(&fixtures::entry("GET", "https://example.com/api/beta", 200)));
+
+        let expr = FilterExpr::parse(r#"contentType *= "json""#).unwrap();
+        assert!(matches!(expr, FilterExpr::Contains(Field::ContentType, ref s) if s == "json"));
+
+        // Flags and quoted patterns work as in matches(); quoted operators stay literal
+        assert!(matches!(FilterExpr::parse("path ~= /USERS/i").unwrap(), FilterExpr::Matches(Field::Path, ref re) if re.as_str() == "(?i)USERS"));
+        assert!(matches!(FilterExpr::parse(r#"url *= "a==b""#).unwrap(), FilterExpr::Contains(Field::Url, ref s) if s == "a==b"));
+        assert!(matches!(
+            FilterExpr::parse(r#"method == "GET" && url *= "/api""#).unwrap(),
+            FilterExpr::And(_, ref right) if matches!(**right, FilterExpr::Contains(Field::Url, _))
+        ));
+
+        assert!(FilterExpr::parse("url ~=").is_err());
+        assert!(FilterExpr::parse("url ~= /(/").is_err());
+    }
+
+    #[test]
+    fn test_infix_regex_keeps_logical_operators() {
+        for (expr, pattern) in [("url ~= /a||b/", "a||b"), ("url ~= /a&&b/i", "(?i)a&&b")] {
+            let parsed = FilterExpr::parse(expr).unwrap();
+            assert!(matches!(parsed, FilterExpr::Matches(Field::Url, ref re) if re.as_str() == pattern), "{}", expr);
+        }
+
+        let expr = FilterExpr::parse("url ~= /a||b/ && status == 200").unwrap();
+        assert!(matches!(expr, FilterExpr::And(ref left, _) if matches!(**left, FilterExpr::Matches(..))));
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/b", 200)));
+
+        // A slash in an unquoted value elsewhere is still plain text
+        let expr = FilterExpr::parse("path == /a || path == /b").unwrap();
+        assert!(matches!(expr, FilterExpr::Or(..)));
+        assert!(expr.matches(&fixtures::entry("GET", "https://example.com/b", 200)));
+    }
+
+    #[test]
+    fn test_bare_boolean_field() {
+        let gql = graphql_entry(serde_json::json!({}));
+
+        assert!(matches!(FilterExpr::parse("isGraphQL").unwrap(), FilterExpr::Bool(Field::IsGraphQL)));
+        assert!(FilterExpr::parse("isGraphQL").unwrap().matches(&gql));
+        assert!(!FilterExpr::parse("!isGraphQL").unwrap().matches(&gql));
+    }
+
+    #[test]
+    fn test_typo_in_field_name_shows_expression() {
+        let err = FilterExpr::parse("stat == 200").unwrap_err().to_string();
+        assert!(err.contains("Unknown field: stat"), "{}", err);
+        assert!(err.contains("`stat == 200`"), "{}", err);
+        assert!(err.contains("hint:"), "{}", err);
+
+        let err = FilterExpr::parse("isGraphQl2").unwrap_err().to_string();
+        assert!(err.contains("Unknown field: isGraphQl2"), "{}", err);
+        assert!(err.contains("boolean field"), "{}", err);
+    }
+
+    #[test]
+    fn test_broken_comparison_is_not_a_field() {
+        for expr in ["garbage <>", "status =", "status = 200", "== 200"] {
+            let err = FilterExpr::parse(expr).unwrap_err().to_string();
+            assert!(err.contains(&format!("`{}`", expr)), "{}", err);
+            assert!(!err.contains("Unknown field"), "{}", err);
+            assert!(err.contains("hint:"), "{}", err);
+        }
+    }
 }
@@ -0,0 +1,263 @@
+use anyhow::Result;
+use regex::Regex;
+use std::ops::Range;
+
+const JSON_PUNCT: &[char] = &['{', '}', '[', ']', ':', ','];
+
+/// Text, regex or glob pattern matched against URLs, headers and bodies
+pub enum Matcher {
+    /// `spans` finds the pattern in the original text, for highlighting
+    Text { pattern: String, ignore_case: bool, normalize_ws: bool, spans: Option<Regex> },
+    Regex(Regex),
+    /// Shell-style wildcards, compiled to a regex
+    Glob(Regex),
+}
+
+impl Matcher {
+    /// Substring matcher, optionally ignoring case and whitespace differences
+    pub fn text(pattern: &str, ignore_case: bool, normalize_ws: bool) -> Self {
+        let mut pattern = if ignore_case {
+            pattern.to_lowercase()
+        } else {
+            pattern.to_string()
+        };
+        if normalize_ws {
+            pattern = normalize_whitespace(&pattern);
+        }
+        let spans = text_regex(&pattern, ignore_case, normalize_ws);
+        Matcher::Text { pattern, ignore_case, normalize_ws, spans }
+    }
+
+    /// Regex matcher; `ignore_case` prefixes the pattern with `(?i)`
+    pub fn regex(pattern: &str, ignore_case: bool) -> Result<Self> {
+        let pattern = if ignore_case {
+            format!("(?i){}", pattern)
+        } else {
+            pattern.to_string()
+        };
+        Ok(Matcher::Regex(Regex::new(&pattern)?))
+    }
+
+    /// Glob matcher for `*`, `?` and `[abc]`/`[!abc]`; `anchored` requires the
+    /// glob to cover the whole text instead of any part of it
+    pub fn glob(pattern: &str, ignore_case: bool, anchored: bool) -> Result<Self> {
+        let mut re = String::from(if ignore_case { "(?is)" } else { "(?s)" });
+        if anchored {
+            re.push('^');
+        }
+        re.push_str(&glob_to_regex(pattern));
+        if anchored {
+            re.push('$');
+        }
+        Ok(Matcher::Glob(Regex::new(&re)?))
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Matcher::Text { pattern, ignore_case, normalize_ws, .. } => {
+                let text = if *normalize_ws {
+                    std::borrow::Cow::Owned(normalize_whitespace(text))
+                } else {
+                    std::borrow::Cow::Borrowed(text)
+                };
+
+                if *ignore_case {
+                    text.to_lowercase().contains(pattern)
+                } else {
+                    text.contains(pattern.as_str())
+                }
+            }
+            Matcher::Regex(re) | Matcher::Glob(re) => re.is_match(text),
+        }
+    }
+
+    /// Byte ranges of every non-empty match in `text`, for highlighting
+    pub fn find_spans(&self, text: &str) -> Vec<Range<usize>> {
+        let re = match self {
+            Matcher::Regex(re) | Matcher::Glob(re) => re,
+            Matcher::Text { spans: Some(re), .. } => re,
+            Matcher::Text { spans: None, .. } => return Vec::new(),
+        };
+
+        re.find_iter(text)
+            .map(|m| m.range())
+            .filter(|r| !r.is_empty())
+            .collect()
+    }
+}
+
+/// Regex finding a text pattern in the original (un-normalized) text.
+///
+/// With `normalize_ws` the pattern is already normalized, so each space
+/// stands for any whitespace run and JSON punctuation may have whitespace
+/// on either side.
+fn text_regex(pattern: &str, ignore_case: bool, normalize_ws: bool) -> Option<Regex> {
+    let mut re = String::from(if ignore_case { "(?i)" } else { "" });
+    if normalize_ws {
+        for c in pattern.chars() {
+            if c == ' ' {
+                re.push_str(r"\s+");
+            } else if JSON_PUNCT.contains(&c) {
+                re.push_str(&format!(r"\s*{}\s*", regex::escape(&c.to_string())));
+            } else {
+                re.push_str(&regex::escape(&c.to_string()));
+            }
+        }
+    } else {
+        re.push_str(&regex::escape(pattern));
+    }
+    Regex::new(&re).ok()
+}
+
+/// Translate a glob into an unanchored regex. A `[` without a closing `]`
+/// and a trailing `\` are taken literally.
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut re = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                re.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            '[' => match glob_class(&chars[i + 1..]) {
+                Some((class, len)) => {
+                    re.push_str(&class);
+                    i += len;
+                }
+                None => re.push_str(r"\["),
+            },
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    re
+}
+
+/// Regex class for the glob class starting after a `[`, with the number of
+/// chars it used up to and including the `]`
+fn glob_class(chars: &[char]) -> Option<(String, usize)> {
+    let mut class = String::from("[");
+    let mut i = 0;
+    if matches!(chars.first(), Some('!') | Some('^')) {
+        class.push('^');
+        i += 1;
+    }
+
+    // A `]` right after the opening bracket is a literal member
+    let start = i;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == ']' && i > start {
+            class.push(']');
+            return Some((class, i + 1));
+        }
+        match c {
+            '-' if i > start && chars.get(i + 1) != Some(&']') => class.push('-'),
+            // Escaping all punctuation also disarms regex class operators like `&&`
+            _ if c.is_ascii_punctuation() => {
+                class.push('\\');
+                class.push(c);
+            }
+            _ => class.push(c),
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Collapse whitespace runs to one space and drop whitespace around JSON
+/// punctuation, so minified and pretty-printed bodies compare equal
+fn normalize_whitespace(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut pending_space = false;
+
+    for c in s.chars() {
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && !out.is_empty() && !JSON_PUNCT.contains(&c) && !out.ends_with(JSON_PUNCT) {
+            out.push(' ');
+        }
+        pending_space = false;
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ws_matches_across_formatting() {
+        let matcher = Matcher::text(r#""foo": "bar""#, false, true);
+        assert!(matcher.matches(r#"{"foo":"bar"}"#));
+        assert!(matcher.matches("{\n  \"foo\" :\t\"bar\"\n}"));
+        assert!(!Matcher::text(r#""foo": "bar""#, false, false).matches(r#"{"foo":"bar"}"#));
+    }
+
+    #[test]
+    fn test_find_spans() {
+        let text = "GET /API/users?api=1";
+        assert_eq!(Matcher::text("api", false, false).find_spans(text), vec![15..18]);
+        assert_eq!(Matcher::text("api", true, false).find_spans(text), [5..8, 15..18]);
+        assert_eq!(Matcher::regex(r"\d", false).unwrap().find_spans("a1b22"), [1..2, 3..4, 4..5]);
+        assert!(Matcher::regex("x*", false).unwrap().find_spans("abc").is_empty());
+
+        let body = "{\n  \"foo\" :\t\"bar\"\n}";
+        let spans = Matcher::text(r#""foo": "bar""#, false, true).find_spans(body);
+        assert_eq!(spans.len(), 1);
+        assert!(body[spans[0].clone()].contains("\"foo\" :\t\"bar\""));
+    }
+
+    #[test]
+    fn test_glob_anchored_and_contains() {
+        let api = Matcher::glob("*/api/*", false, true).unwrap();
+        assert!(api.matches("https://example.com/api/users"));
+        assert!(!api.matches("https://example.com/apiv2/users"));
+
+        let scheme = Matcher::glob("?ttps://*", false, true).unwrap();
+        assert!(scheme.matches("https://example.com/"));
+        assert!(!scheme.matches("http://example.com/"));
+        // Anchored: the glob must cover the whole text
+        assert!(!Matcher::glob("example.com", false, true).unwrap().matches("https://example.com/"));
+        assert!(Matcher::glob("example.com", false, false).unwrap().matches("https://example.com/"));
+
+        let host = Matcher::glob("*.example.com/api/*", true, true).unwrap();
+        assert!(host.matches("https://CDN.Example.com/api/x"));
+        assert!(!host.matches("https://example.com/api/x"));
+    }
+
+    #[test]
+    fn test_glob_classes_and_literals() {
+        let version = Matcher::glob("*/v[12]/*", false, true).unwrap();
+        assert!(version.matches("/v1/users") && version.matches("/v2/users"));
+        assert!(!version.matches("/v3/users"));
+
+        let not_digit = Matcher::glob("id=[!0-9]*", false, true).unwrap();
+        assert!(not_digit.matches("id=abc") && !not_digit.matches("id=42"));
+
+        // Regex metacharacters and unclosed brackets are literal
+        assert!(Matcher::glob("a.b(c)+[d", false, true).unwrap().matches("a.b(c)+[d"));
+        assert!(!Matcher::glob("a.b", false, true).unwrap().matches("axb"));
+        assert!(Matcher::glob(r"\*literal", false, true).unwrap().matches("*literal"));
+        assert!(Matcher::glob("[&&-]", false, true).unwrap().matches("-"));
+
+        let spans = Matcher::glob("api/*", false, false).unwrap().find_spans("/api/users");
+        assert_eq!(spans, vec![1..10]);
+    }
+
+    #[test]
+    fn test_normalize_ws_keeps_word_boundaries() {
+        let matcher = Matcher::text("hello   world", true, true);
+        assert!(matcher.matches("Hello\n\tWorld"));
+        assert!(!matcher.matches("helloworld"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Newline-delimited entries: one JSON `Entry` per line, as some proxies
+//! write while recording
+
+use super::{Entry, Har};
+use anyhow::{Context, Result};
+use std::io::{BufRead, ErrorKind};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::time::Duration;
+
+/// How long to wait before checking a followed file for new lines
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Whether a path names a JSONL entry stream, by its `.jsonl` or `.ndjson` extension
+pub fn is_jsonl_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("ndjson"))
+}
+
+/// Read every entry into a placeholder HAR log
+pub fn parse_reader<R: BufRead>(reader: R) -> Result<Har> {
+    let mut entries = Vec::new();
+    read_entries(reader, None, |entry| {
+        entries.push(entry);
+        ControlFlow::Continue(())
+    })?;
+    Ok(Har::from_entries(entries))
+}
+
+/// Call `f` with each entry, in order. Blank lines are skipped.
+///
+/// At end of input, stop unless `poll` is given; then wait that long and read
+/// again, like `tail -f`, until `f` breaks. A last line without a newline is
+/// only parsed once it's complete, or at the final end of input.
+pub fn read_entries<R, F>(mut reader: R, poll: Option<Duration>, mut f: F) -> Result<()>
+where
+    R: BufRead,
+    F: FnMut(Entry) -> ControlFlow<()>,
+{
+    let mut line = String::new();
+    let mut line_no = 0usize;
+    loop {
+        let read = match reader.read_line(&mut line) {
+            Ok(read) => read,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err).context("Failed to read JSONL input"),
+        };
+
+        let at_end = read == 0 || !line.ends_with('\n');
+        if at_end {
+            if let Some(interval) = poll {
+                // Keep a partial line and wait for the writer to finish it
+                std::thread::sleep(interval);
+                continue;
+            }
+        }
+
+        if !line.trim().is_empty() {
+            line_no += 1;
+            let mut entry: Entry = serde_json::from_str(&line)
+                .with_context(|| format!("Failed to parse entry on line {} of JSONL input", line_no))?;
+            entry.resolve_url();
+            if f(entry).is_break() {
+                return Ok(());
+            }
+        }
+        line.clear();
+
+        if read == 0 {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+    use std::io::{BufReader, Write};
+
+    fn line(url: &str) -> String {
+        format!("{}\n", fixtures::entry_json("GET", url, 200))
+    }
+
+    #[test]
+    fn test_jsonl_paths() {
+        assert!(is_jsonl_path("proxy.jsonl"));
+        assert!(is_jsonl_path("logs/live.NDJSON"));
+        assert!(!is_jsonl_path("recording.har"));
+        assert!(!is_jsonl_path("-"));
+    }
+
+    #[test]
+    fn test_reads_lines_and_reports_bad_ones() {
+        let input = format!("{}\n{}", line("https://example.com/a"), line("https://example.com/b").trim_end());
+        let mut urls = Vec::new();
+        read_entries(input.as_bytes(), None, |e| {
+            urls.push(e.request.url);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(urls, ["https://example.com/a", "https://example.com/b"]);
+
+        let input = format!("{}{{\"not\": \"an entry\"}}\n", line("https://example.com/a"));
+        let err = read_entries(input.as_bytes(), None, |_| ControlFlow::Continue(())).unwrap_err();
+        assert_eq!(err.to_string(), "Failed to parse entry on line 2 of JSONL input");
+    }
+
+    #[test]
+    fn test_follow_picks_up_appended_lines_from_a_pipe() {
+        let (reader, mut writer) = std::io::pipe().unwrap();
+        let first = line("https://example.com/1");
+        let second = line("https://example.com/2");
+        let (head, rest) = second.split_at(20);
+        let (head, rest) = (head.to_string(), rest.to_string());
+
+        let appender = std::thread::spawn(move || {
+            writer.write_all(first.as_bytes()).unwrap();
+            std::thread::sleep(Duration::from_millis(20));
+            // A line written in two parts is only read once complete
+            writer.write_all(head.as_bytes()).unwrap();
+            writer.flush().unwrap();
+            std::thread::sleep(Duration::from_millis(20));
+            writer.write_all(rest.as_bytes()).unwrap();
+            writer.write_all(line("https://example.com/3").as_bytes()).unwrap();
+        });
+
+        let mut urls = Vec::new();
+        read_entries(BufReader::new(reader), Some(Duration::from_millis(5)), |e| {
+            urls.push(e.request.url);
+            if urls.len() == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })
+        .unwrap();
+        appender.join().unwrap();
+
+        assert_eq!(urls, ["https://example.com/1", "https://example.com/2", "https://example.com/3"]);
+    }
+}
@@ -1,10 +1,19 @@
+pub mod jsonl;
+pub mod progress;
+pub mod source;
 pub mod types;
 
 pub use types::*;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs::File;
 use std::io::{BufReader, Read};
+use std::ops::ControlFlow;
 use std::path::Path;
 
 /// Parse a HAR file from path
@@ -16,14 +25,127 @@ pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Har> {
     parse_reader(reader)
 }
 
-/// Parse HAR from a reader
+/// Parse HAR from a reader.
+///
+/// Besides a full HAR, a bare array of entries (`jq '.log.entries'` output)
+/// or a single entry object is accepted and wrapped in a placeholder log.
 pub fn parse_reader<R: Read>(reader: R) -> Result<Har> {
-    serde_json::from_reader(reader).context("Failed to parse HAR file")
+    let AnyHar(har) = serde_json::from_reader(reader).map_err(parse_error)?;
+    Ok(normalize(har))
 }
 
-/// Parse HAR from a string
-pub fn parse_str(s: &str) -> Result<Har> {
-    serde_json::from_str(s).context("Failed to parse HAR JSON")
+/// A full HAR, a bare entry array or a single entry, told apart in one pass
+/// over the input
+struct AnyHar(Har);
+
+impl<'de> Deserialize<'de> for AnyHar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnyHarVisitor)
+    }
+}
+
+struct AnyHarVisitor;
+
+impl<'de> Visitor<'de> for AnyHarVisitor {
+    type Value = AnyHar;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a HAR object or an array of HAR entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<AnyHar, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = seq.next_element::<Entry>()? {
+            entries.push(entry);
+        }
+        Ok(AnyHar(Har::from_entries(entries)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<AnyHar, A::Error> {
+        let mut log = None;
+        // As in `stream_entries`, keys beside `log` are kept in case the
+        // object is a single entry
+        let mut rest = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "log" {
+                log = Some(map.next_value::<Log>()?);
+            } else {
+                rest.insert(key, map.next_value()?);
+            }
+        }
+        if let Some(log) = log {
+            return Ok(AnyHar(Har { log }));
+        }
+
+        serde_json::from_value::<Entry>(serde_json::Value::Object(rest))
+            .map(|entry| AnyHar(Har::from_entries(vec![entry])))
+            .map_err(|_| de::Error::missing_field("log"))
+    }
+}
+
+/// Wrap a JSON error with where in the input it happened, so truncated or
+/// hand-edited files point at the broken spot
+fn parse_error(err: serde_json::Error) -> anyhow::Error {
+    // I/O errors have no position
+    let message = if err.line() == 0 {
+        "Failed to parse HAR file".to_string()
+    } else {
+        format!("Failed to parse HAR at line {} column {}", err.line(), err.column())
+    };
+    anyhow::Error::new(err).context(message)
+}
+
+/// Post-parse fixups applied to every entry, so later code sees absolute URLs
+fn normalize(mut har: Har) -> Har {
+    for entry in &mut har.log.entries {
+        entry.resolve_url();
+    }
+    har
+}
+
+/// Fill in a response body stored outside the HAR from `dir`.
+///
+/// Does nothing when the entry has no external body reference. References
+/// must stay inside `dir`. Non-UTF-8 files are stored base64-encoded.
+pub fn load_external_body(entry: &mut Entry, dir: &Path) -> Result<()> {
+    let Some(name) = entry.external_body_ref() else {
+        return Ok(());
+    };
+
+    let relative = Path::new(name);
+    if !relative.components().all(|c| matches!(c, std::path::Component::Normal(_) | std::path::Component::CurDir)) {
+        bail!("External body reference '{}' points outside the body directory", name);
+    }
+
+    let path = dir.join(relative);
+    let bytes = std::fs::read(&path)
+        .with_context(|| format!("Failed to read external body: {}", path.display()))?;
+
+    let content = &mut entry.response.content;
+    match String::from_utf8(bytes) {
+        Ok(text) => {
+            content.text = Some(text);
+            content.encoding = None;
+        }
+        Err(err) => {
+            use base64::{Engine as _, engine::general_purpose::STANDARD};
+            content.text = Some(STANDARD.encode(err.into_bytes()));
+            content.encoding = Some("base64".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// An entry with its external body loaded when `body_dir` is set
+pub fn with_external_body<'a>(entry: &'a Entry, body_dir: Option<&str>) -> Result<Cow<'a, Entry>> {
+    match body_dir {
+        Some(dir) if entry.external_body_ref().is_some() => {
+            let mut entry = entry.clone();
+            load_external_body(&mut entry, Path::new(dir))?;
+            Ok(Cow::Owned(entry))
+        }
+        _ => Ok(Cow::Borrowed(entry)),
+    }
 }
 
 /// Parse HAR from stdin
@@ -32,3 +154,661 @@ pub fn parse_stdin() -> Result<Har> {
     let reader = stdin.lock();
     parse_reader(reader)
 }
+
+/// Combine several HARs into one, keeping entries in file order.
+///
+/// Version and creator come from the first HAR; `comment` records the
+/// source names. Page ids already taken by an earlier HAR (per-tab DevTools
+/// exports all use `page_1`) get the file number as a prefix, and the
+/// entries' `pageref` follow. With `sort_by_start`, entries are stably
+/// ordered by when they started.
+pub fn merge(hars: Vec<(String, Har)>, sort_by_start: bool) -> Option<Har> {
+    let names: Vec<String> = hars.iter().map(|(name, _)| name.clone()).collect();
+    let mut hars = hars.into_iter().map(|(_, har)| har);
+    let mut merged = hars.next()?;
+    let mut page_ids: HashSet<String> = merged.log.pages.iter().flatten().map(|p| p.id.clone()).collect();
+
+    for (file, mut har) in hars.enumerate() {
+        let mut renamed = HashMap::new();
+        for page in har.log.pages.iter_mut().flatten() {
+            let mut id = page.id.clone();
+            while page_ids.contains(&id) {
+                id = format!("{}_{}", file + 2, id);
+            }
+            page_ids.insert(id.clone());
+            if id != page.id {
+                renamed.insert(std::mem::replace(&mut page.id, id.clone()), id);
+            }
+        }
+        for entry in &mut har.log.entries {
+            if let Some(id) = entry.pageref.as_ref().and_then(|r| renamed.get(r)) {
+                entry.pageref = Some(id.clone());
+            }
+        }
+
+        merged.log.entries.extend(har.log.entries);
+        if let Some(pages) = har.log.pages {
+            merged.log.pages.get_or_insert_with(Vec::new).extend(pages);
+        }
+    }
+
+    if sort_by_start {
+        merged.log.entries.sort_by(|a, b| {
+            a.started_at()
+                .cmp(&b.started_at())
+                .then_with(|| a.started_date_time.cmp(&b.started_date_time))
+        });
+    }
+
+    let note = format!("Merged from {}", names.join(", "));
+    merged.log.comment = Some(match merged.log.comment.take() {
+        Some(comment) => format!("{}\n{}", comment, note),
+        None => note,
+    });
+
+    Some(merged)
+}
+
+/// Open a HAR file for reading (use "-" for stdin, `@clipboard` for the
+/// clipboard, or an http(s) URL).
+///
+/// Large files show a progress line on stderr while read, if it is a terminal.
+pub fn open(path: &str) -> Result<BufReader<Box<dyn Read>>> {
+    // Buffer outside the box so per-byte reads don't go through dynamic dispatch
+    let reader = source::Source::parse(path).open_with(source::fetch, source::paste)?;
+    Ok(BufReader::new(reader))
+}
+
+/// Stream `log.entries` from a reader one entry at a time.
+///
+/// Only the entry currently being handled is kept in memory; everything
+/// else in the HAR is skipped. A bare array of entries streams the same
+/// way, and a single entry object is handed over on its own. Input that is
+/// none of these, such as a log without `entries`, is an error. Return `ControlFlow::Break` from the callback to stop reading early.
+pub fn stream_entries<R, F>(reader: R, mut f: F) -> Result<()>
+where
+    R: Read,
+    F: FnMut(Entry) -> ControlFlow<()>,
+{
+    let mut stopped = false;
+    let mut de = serde_json::Deserializer::from_reader(reader);
+    let result = de.deserialize_any(HarVisitor {
+        on_entry: &mut f,
+        stopped: &mut stopped,
+    });
+
+    match result {
+        Err(_) if stopped => Ok(()),
+        other => other.map_err(parse_error),
+    }
+}
+
+/// Entry callback threaded through the streaming visitors
+struct HarVisitor<'a, F> {
+    on_entry: &'a mut F,
+    stopped: &'a mut bool,
+}
+
+impl<'de, F: FnMut(Entry) -> ControlFlow<()>> Visitor<'de> for HarVisitor<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a HAR object or an array of HAR entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<(), A::Error> {
+        EntriesSeed {
+            on_entry: self.on_entry,
+            stopped: self.stopped,
+        }
+        .visit_seq(seq)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut seen_log = false;
+        // Keys beside `log` are kept in case the object is a single entry;
+        // a real HAR has no others of any size
+        let mut rest = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "log" {
+                map.next_value_seed(LogSeed {
+                    on_entry: &mut *self.on_entry,
+                    stopped: &mut *self.stopped,
+                })?;
+                seen_log = true;
+            } else {
+                rest.insert(key, map.next_value()?);
+            }
+        }
+        if seen_log {
+            return Ok(());
+        }
+
+        match serde_json::from_value::<Entry>(serde_json::Value::Object(rest)) {
+            Ok(mut entry) => {
+                entry.resolve_url();
+                let _ = (self.on_entry)(entry);
+                Ok(())
+            }
+            Err(_) => Err(de::Error::missing_field("log")),
+        }
+    }
+}
+
+struct LogSeed<'a, F> {
+    on_entry: &'a mut F,
+    stopped: &'a mut bool,
+}
+
+impl<'de, F: FnMut(Entry) -> ControlFlow<()>> DeserializeSeed<'de> for LogSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(Entry) -> ControlFlow<()>> Visitor<'de> for LogSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a HAR log object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut seen_entries = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "entries" {
+                map.next_value_seed(EntriesSeed {
+                    on_entry: &mut *self.on_entry,
+                    stopped: &mut *self.stopped,
+                })?;
+                seen_entries = true;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        if !seen_entries {
+            return Err(de::Error::missing_field("entries"));
+        }
+        Ok(())
+    }
+}
+
+struct EntriesSeed<'a, F> {
+    on_entry: &'a mut F,
+    stopped: &'a mut bool,
+}
+
+impl<'de, F: FnMut(Entry) -> ControlFlow<()>> DeserializeSeed<'de> for EntriesSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F: FnMut(Entry) -> ControlFlow<()>> Visitor<'de> for EntriesSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of HAR entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(mut entry) = seq.next_element::<Entry>()? {
+            entry.resolve_url();
+            if (self.on_entry)(entry).is_break() {
+                // Abort the parse; stream_entries treats this error as success
+                *self.stopped = true;
+                return Err(de::Error::custom("stopped"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Minimal HAR builders for unit tests
+#[cfg(test)]
+pub mod fixtures {
+    use super::{Entry, Har};
+    use serde_json::{json, Value};
+
+    /// JSON for an entry with all required HAR fields filled in
+    pub fn entry_json(method: &str, url: &str, status: i32) -> Value {
+        json!({
+            "startedDateTime": "2024-01-01T00:00:00.000Z",
+            "time": 100.0,
+            "request": {
+                "method": method,
+                "url": url,
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": [],
+                "queryString": [],
+                "headersSize": -1,
+                "bodySize": 0
+            },
+            "response": {
+                "status": status,
+                "statusText": "",
+                "httpVersion": "HTTP/1.1",
+                "content": { "size": 0 }
+            },
+            "cache": {},
+            "timings": {}
+        })
+    }
+
+    pub fn entry(method: &str, url: &str, status: i32) -> Entry {
+        serde_json::from_value(entry_json(method, url, status)).unwrap()
+    }
+
+    /// `entry_json` with `edit` applied to the JSON, for the fields a test cares about
+    pub fn entry_with(method: &str, url: &str, status: i32, edit: impl FnOnce(&mut Value)) -> Value {
+        let mut json = entry_json(method, url, status);
+        edit(&mut json);
+        json
+    }
+
+    /// Parse entry JSON built by `entry_json` or `entry_with`
+    pub fn to_entry(json: Value) -> Entry {
+        serde_json::from_value(json).unwrap()
+    }
+
+    /// A HAR of these entries serialized, as streaming commands read it
+    pub fn har_bytes(entries: Vec<Value>) -> Vec<u8> {
+        serde_json::to_vec(&har(entries)).unwrap()
+    }
+
+    /// Wrap entry JSON values into a HAR log
+    pub fn har(entries: Vec<Value>) -> Har {
+        serde_json::from_value(json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "test", "version": "1" },
+                "entries": entries
+            }
+        }))
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relative_entry(url: &str, headers: serde_json::Value) -> serde_json::Value {
+        fixtures::entry_with("GET", url, 200, |e| {
+            e["request"]["headers"] = headers;
+        })
+    }
+
+    #[test]
+    fn test_relative_urls_resolved_on_parse() {
+        let har = fixtures::har(vec![
+            relative_entry("/api/users?id=1", serde_json::json!([
+                {"name": ":authority", "value": "api.example.com"},
+                {"name": ":scheme", "value": "https"}
+            ])),
+            relative_entry("/login", serde_json::json!([{"name": "Host", "value": "localhost:8080"}])),
+            relative_entry("//cdn.example.com/app.js", serde_json::json!([])),
+            relative_entry("/orphan", serde_json::json!([])),
+        ]);
+        let text = serde_json::to_string(&har).unwrap();
+
+        let expected = [
+            "https://api.example.com/api/users?id=1",
+            "https://localhost:8080/login",
+            "https://cdn.example.com/app.js",
+            "/orphan",
+        ];
+
+        let parsed = parse_reader(text.as_bytes()).unwrap();
+        let urls: Vec<&str> = parsed.log.entries.iter().map(|e| e.request.url.as_str()).collect();
+        assert_eq!(urls, expected);
+
+        let mut streamed = Vec::new();
+        stream_entries(text.as_bytes(), |e| {
+            streamed.push(e.request.url);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(streamed, expected);
+
+        assert_eq!(crate::output::extract_host(&parsed.log.entries[1].request.url), "localhost");
+    }
+
+    #[test]
+    fn test_gzip_base64_body_decompressed() {
+        use base64::{Engine as _, engine::general_purpose::STANDARD};
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"ok":true}"#).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([{"name": "Content-Encoding", "value": "gzip"}]);
+            e["response"]["content"] = serde_json::json!({
+                "size": 11,
+                "mimeType": "application/json",
+                "text": STANDARD.encode(&gzipped),
+                "encoding": "base64"
+            });
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert_eq!(entry.response_text().as_deref(), Some(r#"{"ok":true}"#));
+        assert_eq!(entry.response_body(false), Some(gzipped));
+    }
+
+    #[test]
+    fn test_plain_text_body_not_decompressed() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([{"name": "Content-Encoding", "value": "gzip"}]);
+            e["response"]["content"] = serde_json::json!({"size": 5, "mimeType": "text/plain", "text": "hello"});
+        });
+        let entry = fixtures::to_entry(json);
+
+        assert_eq!(entry.response_text().as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn test_external_body_loaded_from_dir() {
+        let dir = std::env::temp_dir().join(format!("harq-body-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("body-1.json"), r#"{"id":1}"#).unwrap();
+
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["content"] = serde_json::json!({"size": 8, "mimeType": "application/json", "_file": "body-1.json"});
+        });
+        let mut entry: Entry = serde_json::from_value(json).unwrap();
+        let mut untouched = entry.clone();
+        untouched.response.content.custom.clear();
+
+        load_external_body(&mut entry, &dir).unwrap();
+        load_external_body(&mut untouched, &dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(entry.response_text().as_deref(), Some(r#"{"id":1}"#));
+        assert!(untouched.response_text().is_none());
+    }
+
+    #[test]
+    fn test_external_body_outside_dir_rejected() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["content"] = serde_json::json!({"size": 0, "_content": "../secret"});
+        });
+        let mut entry: Entry = serde_json::from_value(json).unwrap();
+
+        let err = load_external_body(&mut entry, Path::new("bodies")).unwrap_err();
+        assert!(err.to_string().contains("outside"));
+    }
+
+    #[test]
+    fn test_lenient_status() {
+        let with_status = |status: serde_json::Value| {
+            let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+                e["response"]["status"] = status;
+            });
+            serde_json::from_value::<Entry>(json).unwrap().response.status
+        };
+
+        assert_eq!(with_status(serde_json::json!(200)), 200);
+        assert_eq!(with_status(serde_json::json!("200")), 200);
+        assert_eq!(with_status(serde_json::json!(" 404 ")), 404);
+        assert_eq!(with_status(serde_json::json!(301.0)), 301);
+        assert_eq!(with_status(serde_json::json!("unknown")), 0);
+        assert_eq!(with_status(serde_json::json!(null)), 0);
+        assert_eq!(with_status(serde_json::json!(1e12)), 0);
+
+        let mut json = fixtures::entry_json("GET", "https://example.com/", 200);
+        json["response"].as_object_mut().unwrap().remove("status");
+        assert_eq!(serde_json::from_value::<Entry>(json).unwrap().response.status, 0);
+
+        // One bad entry no longer fails the file
+        let bad = fixtures::entry_with("GET", "https://example.com/b", 200, |e| {
+            e["response"]["status"] = serde_json::json!("n/a");
+        });
+        let har = serde_json::to_vec(&serde_json::json!({
+            "log": {"version": "1.2", "creator": {"name": "t", "version": "1"},
+                    "entries": [fixtures::entry_json("GET", "https://example.com/a", 200), bad]}
+        }))
+        .unwrap();
+        let har = parse_reader(har.as_slice()).unwrap();
+        assert_eq!(har.log.entries[1].response.status, 0);
+    }
+
+    #[test]
+    fn test_transfer_and_decoded_sizes() {
+        let sized = |content: serde_json::Value, headers_size: i64, body_size: i64| {
+            let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+                e["response"]["content"] = content;
+                e["response"]["headersSize"] = serde_json::json!(headers_size);
+                e["response"]["bodySize"] = serde_json::json!(body_size);
+            });
+            serde_json::from_value::<Entry>(json).unwrap()
+        };
+
+        // Compressed: 10 000 bytes decoded, 7 000 saved, 3 000 on the wire
+        let entry = sized(serde_json::json!({"size": 10000, "compression": 7000}), 200, 3000);
+        assert_eq!(entry.transfer_size(), Some(3200));
+        assert_eq!(entry.content_decoded_size(), Some(10000));
+
+        // bodySize missing: worked out from content.size - compression
+        let entry = sized(serde_json::json!({"size": 10000, "compression": 7000}), -1, -1);
+        assert_eq!(entry.transfer_size(), Some(3000));
+
+        // Uncompressed: wire and decoded body are the same
+        let entry = sized(serde_json::json!({"size": 500}), 100, 500);
+        assert_eq!(entry.transfer_size(), Some(600));
+        assert_eq!(entry.content_decoded_size(), Some(500));
+
+        // content.size missing: bodySize plus compression
+        let entry = sized(serde_json::json!({"size": -1, "compression": 50}), -1, 150);
+        assert_eq!(entry.content_decoded_size(), Some(200));
+
+        let entry = sized(serde_json::json!({"size": -1}), 100, -1);
+        assert_eq!(entry.transfer_size(), None);
+        assert_eq!(entry.content_decoded_size(), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_forms() {
+        let utc = parse_timestamp("2024-01-01T08:00:00Z").unwrap();
+        assert_eq!(parse_timestamp("2024-01-01T10:00:00.000+02:00"), Some(utc));
+        assert_eq!(parse_timestamp("2024-01-01T10:00:00+0200"), Some(utc));
+        assert_eq!(parse_timestamp("2024-01-01").unwrap(), parse_timestamp("2024-01-01T00:00:00Z").unwrap());
+        assert_eq!(parse_timestamp("2024-01-01T08:00:00"), None);
+        assert_eq!(parse_timestamp(""), None);
+    }
+
+    #[test]
+    fn test_merge_two_hars() {
+        let late = fixtures::entry_with("GET", "https://a.example.com/late", 200, |e| {
+            e["startedDateTime"] = serde_json::json!("2024-01-01T00:00:05.000Z");
+        });
+        let first = fixtures::har(vec![late, fixtures::entry_json("GET", "https://a.example.com/early", 200)]);
+        let second = fixtures::har(vec![fixtures::entry_json("POST", "https://b.example.com/", 201)]);
+        let inputs = || vec![("a.har".to_string(), first.clone()), ("b.har".to_string(), second.clone())];
+
+        let merged = merge(inputs(), false).unwrap();
+        let urls: Vec<&str> = merged.log.entries.iter().map(|e| e.request.url.as_str()).collect();
+        assert_eq!(urls, ["https://a.example.com/late", "https://a.example.com/early", "https://b.example.com/"]);
+        assert_eq!(merged.log.creator.name, "test");
+        assert_eq!(merged.log.comment.as_deref(), Some("Merged from a.har, b.har"));
+
+        let sorted = merge(inputs(), true).unwrap();
+        assert_eq!(sorted.log.entries.last().unwrap().request.url, "https://a.example.com/late");
+
+        // Sorting compares instants: 10:00+02:00 is 08:00Z, before 09:00Z
+        let started = |url: &str, at: &str| {
+            let at = at.to_string();
+            fixtures::entry_with("GET", url, 200, move |e| e["startedDateTime"] = serde_json::json!(at))
+        };
+        let utc = fixtures::har(vec![started("https://a.example.com/nine", "2024-01-01T09:00:00Z")]);
+        let offset = fixtures::har(vec![started("https://b.example.com/eight", "2024-01-01T10:00:00+02:00")]);
+        let sorted = merge(vec![("a.har".to_string(), utc), ("b.har".to_string(), offset)], true).unwrap();
+        let urls: Vec<&str> = sorted.log.entries.iter().map(|e| e.request.url.as_str()).collect();
+        assert_eq!(urls, ["https://b.example.com/eight", "https://a.example.com/nine"]);
+
+        assert!(merge(Vec::new(), false).is_none());
+    }
+
+    #[test]
+    fn test_merge_renames_colliding_page_ids() {
+        let tab = |url: &str| {
+            let mut har = fixtures::har(vec![fixtures::entry_with("GET", url, 200, |e| e["pageref"] = serde_json::json!("page_1"))]);
+            har.log.pages = Some(vec![Page {
+                started_date_time: "2024-01-01T00:00:00.000Z".to_string(),
+                id: "page_1".to_string(),
+                title: url.to_string(),
+                page_timings: None,
+                comment: None,
+            }]);
+            har
+        };
+        let inputs = ["https://a.example.com/", "https://b.example.com/", "https://c.example.com/"]
+            .iter()
+            .map(|url| (format!("{}.har", url), tab(url)))
+            .collect();
+
+        let merged = merge(inputs, false).unwrap();
+        let ids: Vec<&str> = merged.log.pages.iter().flatten().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["page_1", "2_page_1", "3_page_1"]);
+        for (page, entry) in merged.log.pages.iter().flatten().zip(&merged.log.entries) {
+            assert_eq!(entry.pageref.as_deref(), Some(page.id.as_str()));
+            assert_eq!(entry.request.url, page.title);
+        }
+    }
+
+    /// Synthetic HAR with `n` entries and some non-entry fields around them
+    fn large_har_json(n: usize) -> String {
+        let entries: Vec<serde_json::Value> = (0..n)
+            .map(|i| fixtures::entry_json("GET", &format!("https://example.com/{}", i), 200))
+            .collect();
+        serde_json::json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "test", "version": "1" },
+                "pages": [],
+                "entries": entries,
+                "comment": "trailing"
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_stream_entries_visits_all() {
+        let json = large_har_json(20_000);
+        let mut count = 0;
+        let mut last_url = String::new();
+
+        stream_entries(json.as_bytes(), |entry| {
+            count += 1;
+            last_url = entry.request.url;
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(count, 20_000);
+        assert_eq!(last_url, "https://example.com/19999");
+        assert_eq!(count, parse_reader(json.as_bytes()).unwrap().log.entries.len());
+    }
+
+    #[test]
+    fn test_stream_entries_stops_early() {
+        let json = large_har_json(20_000);
+        let mut seen = 0;
+
+        stream_entries(json.as_bytes(), |_| {
+            seen += 1;
+            if seen == 10 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })
+        .unwrap();
+
+        assert_eq!(seen, 10);
+    }
+
+    #[test]
+    fn test_stream_entries_reports_parse_errors() {
+        let result = stream_entries(&b"{\"log\": {\"entries\": [{]}}"[..], |_| ControlFlow::Continue(()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_error_reports_position() {
+        let broken = "{\n  \"log\": {\n    \"version\": \"1.2\",\n    \"entries\": [\n      {\"startedDateTime\": }\n";
+        let err = parse_reader(broken.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "Failed to parse HAR at line 5 column 27");
+        assert!(err.chain().nth(1).is_some());
+
+        let err = parse_reader("{\"log\": {\"version\": \"1.2\"".as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to parse HAR at line 1 column "), "{}", err);
+
+        let err = stream_entries(broken.as_bytes(), |_| ControlFlow::Continue(())).unwrap_err();
+        assert!(err.to_string().contains("at line 5 column"), "{}", err);
+    }
+
+    #[test]
+    fn test_accepts_har_entry_array_and_single_entry() {
+        let entry = fixtures::entry_json("GET", "https://example.com/", 200);
+        let har = serde_json::to_string(&fixtures::har(vec![entry.clone()])).unwrap();
+        let array = serde_json::to_string(&serde_json::json!([entry.clone(), entry.clone()])).unwrap();
+        let single = serde_json::to_string(&entry).unwrap();
+
+        assert_eq!(parse_reader(har.as_bytes()).unwrap().log.entries.len(), 1);
+
+        let wrapped = parse_reader(format!("\n  {}", array).as_bytes()).unwrap();
+        assert_eq!(wrapped.log.entries.len(), 2);
+        assert_eq!(wrapped.log.version, "1.2");
+        assert_eq!(wrapped.log.creator.name, "harq");
+
+        let wrapped = parse_reader(single.as_bytes()).unwrap();
+        assert_eq!(wrapped.log.entries[0].request.url, "https://example.com/");
+
+        for (input, expected) in [(&array, 2), (&single, 1)] {
+            let mut streamed = Vec::new();
+            stream_entries(input.as_bytes(), |e| {
+                streamed.push(e.request.url);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            assert_eq!(streamed.len(), expected);
+            assert_eq!(streamed[0], "https://example.com/");
+        }
+    }
+
+    #[test]
+    fn test_unrecognized_shapes_report_har_error() {
+        let err = parse_reader(r#"{"foo": 1}"#.as_bytes()).unwrap_err();
+        assert!(err.chain().any(|e| e.to_string().contains("missing field `log`")), "{:#}", err);
+
+        let err = parse_reader(r#"[{"request": {}}]"#.as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to parse HAR at line 1"), "{}", err);
+    }
+
+    #[test]
+    fn test_streaming_rejects_input_without_entries() {
+        for (input, missing) in [
+            (r#"{"foo": 1}"#, "missing field `log`"),
+            (r#"{"log": {"version": "1.2"}}"#, "missing field `entries`"),
+        ] {
+            let mut seen = 0;
+            let err = stream_entries(input.as_bytes(), |_| {
+                seen += 1;
+                ControlFlow::Continue(())
+            })
+            .unwrap_err();
+            assert!(err.to_string().starts_with("Failed to parse HAR at line 1"), "{}", err);
+            assert!(err.chain().any(|e| e.to_string().contains(missing)), "{:#}", err);
+            assert_eq!(seen, 0);
+        }
+
+        let empty = serde_json::to_string(&fixtures::har(vec![])).unwrap();
+        assert!(stream_entries(empty.as_bytes(), |_| ControlFlow::Continue(())).is_ok());
+    }
+}
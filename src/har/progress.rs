@@ -0,0 +1,113 @@
+use crate::output::format_bytes;
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+/// Smallest file that gets a progress line; smaller ones load too fast to need one
+const MIN_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Reader that shows `Reading <file>: 12.0MB / 300.0MB (4%)` on one
+/// updating line, and clears it once reading is done.
+pub struct Progress<R, W: Write> {
+    inner: R,
+    out: W,
+    label: String,
+    total: u64,
+    read: u64,
+    shown_percent: Option<u64>,
+    line_len: usize,
+}
+
+/// Wrap `file` in a progress line on stderr if it is large and stderr is a
+/// terminal; otherwise read it as is
+pub fn wrap(file: File, label: &str) -> Box<dyn Read> {
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if total >= MIN_SIZE && atty::is(atty::Stream::Stderr) {
+        Box::new(Progress::new(file, io::stderr(), label, total))
+    } else {
+        Box::new(file)
+    }
+}
+
+impl<R: Read, W: Write> Progress<R, W> {
+    pub fn new(inner: R, out: W, label: &str, total: u64) -> Self {
+        Self {
+            inner,
+            out,
+            label: label.to_string(),
+            total,
+            read: 0,
+            shown_percent: None,
+            line_len: 0,
+        }
+    }
+
+    /// Redraw the line when the whole percentage changes; display errors are ignored
+    fn update(&mut self) {
+        let percent = (self.read * 100 / self.total.max(1)).min(100);
+        if self.shown_percent == Some(percent) {
+            return;
+        }
+        self.shown_percent = Some(percent);
+
+        let line = format!(
+            "Reading {}: {} / {} ({}%)",
+            self.label,
+            format_bytes(self.read as i64),
+            format_bytes(self.total as i64),
+            percent
+        );
+        let padding = self.line_len.saturating_sub(line.chars().count());
+        let _ = write!(self.out, "\r{}{}", line, " ".repeat(padding));
+        let _ = self.out.flush();
+        self.line_len = line.chars().count();
+    }
+}
+
+impl<R: Read, W: Write> Read for Progress<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n as u64;
+        self.update();
+        Ok(n)
+    }
+}
+
+impl<R, W: Write> Drop for Progress<R, W> {
+    fn drop(&mut self) {
+        if self.line_len > 0 {
+            let _ = write!(self.out, "\r{}\r", " ".repeat(self.line_len));
+            let _ = self.out.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_updates_and_clears() {
+        let data = vec![b'x'; 4096];
+        let mut out = Vec::new();
+        let mut copy = Vec::new();
+        {
+            let mut reader = Progress::new(data.as_slice(), &mut out, "big.har", data.len() as u64);
+            let mut buf = [0u8; 1024];
+            loop {
+                let n = reader.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                copy.extend_from_slice(&buf[..n]);
+            }
+        }
+
+        assert_eq!(copy, data);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\rReading big.har: 1.0KB / 4.0KB (25%)"), "{:?}", out);
+        assert!(out.contains("(50%)") && out.contains("4.0KB / 4.0KB (100%)"));
+        // The final EOF read doesn't redraw, and the line is blanked at the end
+        assert_eq!(out.matches('%').count(), 4);
+        assert!(out.ends_with(&format!("\r{}\r", " ".repeat("Reading big.har: 4.0KB / 4.0KB (100%)".len()))));
+    }
+}
@@ -0,0 +1,124 @@
+//! Where a HAR argument points: stdin, a file, the clipboard or a URL
+
+use super::progress;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Cursor, Read};
+
+/// Argument that reads the HAR from the system clipboard
+pub const CLIPBOARD: &str = "@clipboard";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source<'a> {
+    Stdin,
+    Clipboard,
+    Url(&'a str),
+    File(&'a str),
+}
+
+impl<'a> Source<'a> {
+    pub fn parse(path: &'a str) -> Self {
+        let scheme = path.split_once("://").map(|(scheme, _)| scheme.to_ascii_lowercase());
+        match path {
+            "-" => Source::Stdin,
+            CLIPBOARD => Source::Clipboard,
+            _ if matches!(scheme.as_deref(), Some("http" | "https")) => Source::Url(path),
+            _ => Source::File(path),
+        }
+    }
+
+    /// Open a reader, using `fetch` for URLs and `paste` for the clipboard
+    pub fn open_with(
+        &self,
+        fetch: impl FnOnce(&str) -> Result<Box<dyn Read>>,
+        paste: impl FnOnce() -> Result<String>,
+    ) -> Result<Box<dyn Read>> {
+        Ok(match *self {
+            Source::Stdin => Box::new(std::io::stdin()),
+            Source::Clipboard => Box::new(Cursor::new(paste()?.into_bytes())),
+            Source::Url(url) => fetch(url).with_context(|| format!("Failed to fetch {}", url))?,
+            Source::File(path) => {
+                let file = File::open(path)
+                    .with_context(|| format!("Failed to open file: {}", path))?;
+                progress::wrap(file, path)
+            }
+        })
+    }
+}
+
+/// GET a URL, failing on non-2xx responses
+#[cfg(feature = "remote")]
+pub fn fetch(url: &str) -> Result<Box<dyn Read>> {
+    match ureq::get(url).call() {
+        Ok(response) => Ok(Box::new(response.into_reader())),
+        Err(ureq::Error::Status(code, response)) => {
+            anyhow::bail!("HTTP {} {}", code, response.status_text())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(not(feature = "remote"))]
+pub fn fetch(_url: &str) -> Result<Box<dyn Read>> {
+    anyhow::bail!("Reading HARs from URLs needs harq built with `--features remote`")
+}
+
+/// Text currently on the system clipboard
+#[cfg(feature = "clipboard")]
+pub fn paste() -> Result<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .context("Failed to read the clipboard")
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn paste() -> Result<String> {
+    anyhow::bail!("Reading HARs from {} needs harq built with `--features clipboard`", CLIPBOARD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+
+    fn read(path: &str) -> Result<String> {
+        let reader = Source::parse(path).open_with(
+            |url| Ok(Box::new(Cursor::new(format!("fetched {}", url)))),
+            || Ok("pasted".to_string()),
+        )?;
+        let mut text = String::new();
+        { reader }.read_to_string(&mut text)?;
+        Ok(text)
+    }
+
+    #[test]
+    fn test_parse_dispatch() {
+        assert_eq!(Source::parse("-"), Source::Stdin);
+        assert_eq!(Source::parse("@clipboard"), Source::Clipboard);
+        assert_eq!(Source::parse("https://example.com/a.har"), Source::Url("https://example.com/a.har"));
+        assert_eq!(Source::parse("HTTP://example.com/a.har"), Source::Url("HTTP://example.com/a.har"));
+        assert_eq!(Source::parse("ftp://example.com/a.har"), Source::File("ftp://example.com/a.har"));
+        assert_eq!(Source::parse("captures/http-errors.har"), Source::File("captures/http-errors.har"));
+        assert_eq!(Source::parse("@other"), Source::File("@other"));
+    }
+
+    #[test]
+    fn test_open_uses_fetch_and_paste() {
+        assert_eq!(read("https://example.com/a.har").unwrap(), "fetched https://example.com/a.har");
+        assert_eq!(read(CLIPBOARD).unwrap(), "pasted");
+
+        let path = std::env::temp_dir().join(format!("harq-source-{}.har", std::process::id()));
+        std::fs::write(&path, "from disk").unwrap();
+        assert_eq!(read(path.to_str().unwrap()).unwrap(), "from disk");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors_name_the_url() {
+        let err = Source::parse("https://example.com/missing.har")
+            .open_with(|_| bail!("HTTP 404 Not Found"), || unreachable!())
+            .err()
+            .unwrap();
+        assert_eq!(format!("{:#}", err), "Failed to fetch https://example.com/missing.har: HTTP 404 Not Found");
+    }
+}
@@ -1,4 +1,6 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::io::Read;
 
 /// Root HAR structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,6 +8,26 @@ pub struct Har {
     pub log: Log,
 }
 
+impl Har {
+    /// Wrap entries that came without a `log` in a placeholder HAR 1.2 log
+    pub fn from_entries(entries: Vec<Entry>) -> Self {
+        Har {
+            log: Log {
+                version: "1.2".to_string(),
+                creator: Creator {
+                    name: "harq".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    comment: None,
+                },
+                browser: None,
+                pages: None,
+                entries,
+                comment: None,
+            },
+        }
+    }
+}
+
 /// Log object - the main container
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -100,6 +122,8 @@ pub struct Request {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
+    /// Numeric strings are accepted; anything non-numeric or missing becomes 0
+    #[serde(default, deserialize_with = "lenient_status")]
     pub status: i32,
     pub status_text: String,
     pub http_version: String,
@@ -147,6 +171,13 @@ pub struct Header {
     pub comment: Option<String>,
 }
 
+impl Header {
+    /// HTTP/2 and HTTP/3 pseudo-headers such as `:method` or `:path`
+    pub fn is_pseudo(&self) -> bool {
+        self.name.starts_with(':')
+    }
+}
+
 /// Query parameter
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryParam {
@@ -199,6 +230,9 @@ pub struct Content {
     pub encoding: Option<String>,
     #[serde(default)]
     pub comment: Option<String>,
+    // Custom fields (prefixed with _)
+    #[serde(flatten)]
+    pub custom: std::collections::HashMap<String, serde_json::Value>,
 }
 
 /// Cache info
@@ -252,6 +286,15 @@ pub struct Timings {
 
 // Helper implementations
 
+impl Timings {
+    /// A phase timing with HAR's `-1` ("does not apply") treated as absent.
+    ///
+    /// Other negative values are invalid HAR and are dropped the same way.
+    pub fn applicable(value: Option<f64>) -> Option<f64> {
+        value.filter(|&t| t >= 0.0)
+    }
+}
+
 impl Entry {
     /// Get a header value from request
     pub fn request_header(&self, name: &str) -> Option<&str> {
@@ -271,6 +314,94 @@ impl Entry {
             .map(|h| h.value.as_str())
     }
 
+    /// Every value of a request header, in order, for headers that repeat
+    pub fn request_headers(&self, name: &str) -> Vec<&str> {
+        header_values(&self.request.headers, name)
+    }
+
+    /// Every value of a response header, in order, e.g. each `Set-Cookie`
+    pub fn response_headers(&self, name: &str) -> Vec<&str> {
+        header_values(&self.response.headers, name)
+    }
+
+    /// Make a relative request URL absolute using the request's pseudo-headers.
+    ///
+    /// HTTP/2 captures can store only the path, with the host in `:authority`
+    /// (or `Host`) and the scheme in `:scheme` (default `https`). URLs that
+    /// are already absolute, or that have no host to resolve against, are
+    /// left unchanged.
+    pub fn resolve_url(&mut self) {
+        let url = &self.request.url;
+        if url.contains("://") {
+            return;
+        }
+
+        let scheme = self.request_header(":scheme").unwrap_or("https");
+        let resolved = if let Some(rest) = url.strip_prefix("//") {
+            format!("{}://{}", scheme, rest)
+        } else {
+            let Some(authority) = self
+                .request_header(":authority")
+                .or_else(|| self.request_header("host"))
+            else {
+                return;
+            };
+            let separator = if url.starts_with('/') || url.is_empty() { "" } else { "/" };
+            format!("{}://{}{}{}", scheme, authority, separator, url)
+        };
+
+        self.request.url = resolved;
+    }
+
+    /// `startedDateTime` as a timestamp, None if it can't be parsed
+    pub fn started_at(&self) -> Option<DateTime<FixedOffset>> {
+        parse_timestamp(&self.started_date_time)
+    }
+
+    /// Whether the request failed: no response (status 0) or an HTTP error status
+    pub fn is_failed(&self) -> bool {
+        self.response.status == 0 || self.response.status >= 400
+    }
+
+    /// Response body bytes with any `Content-Encoding` undone.
+    ///
+    /// Only base64 bodies are decompressed: capture tools store text bodies
+    /// already decoded even when the header is kept. If decompression fails
+    /// the bytes are returned as recorded.
+    pub fn response_body(&self, decompress: bool) -> Option<Vec<u8>> {
+        let bytes = self.response.content.decoded_text()?;
+        if !decompress || self.response.content.encoding.as_deref() != Some("base64") {
+            return Some(bytes);
+        }
+
+        match self.response_header("content-encoding") {
+            Some(encoding) => Some(decompress_body(&bytes, encoding).unwrap_or(bytes)),
+            None => Some(bytes),
+        }
+    }
+
+    /// File name of a response body stored outside the HAR.
+    ///
+    /// Read from `_file` or `_content` on the content object, falling back
+    /// to the entry, and only used while `content.text` is empty.
+    pub fn external_body_ref(&self) -> Option<&str> {
+        let content = &self.response.content;
+        if content.text.as_deref().is_some_and(|t| !t.is_empty()) {
+            return None;
+        }
+
+        ["_file", "_content"]
+            .iter()
+            .find_map(|key| content.custom.get(*key).or_else(|| self.custom.get(*key)))
+            .and_then(|value| value.as_str())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Response body as UTF-8 text, decoded and decompressed
+    pub fn response_text(&self) -> Option<String> {
+        String::from_utf8(self.response_body(true)?).ok()
+    }
+
     /// Get response content type
     pub fn content_type(&self) -> Option<&str> {
         self.response
@@ -279,6 +410,36 @@ impl Entry {
             .as_deref()
             .or_else(|| self.response_header("content-type"))
     }
+
+    /// Response bytes on the wire: headers plus the (possibly compressed) body.
+    ///
+    /// Uses `bodySize` when recorded, otherwise `content.size` minus
+    /// `content.compression`. Unknown header sizes count as 0; None when the
+    /// body size can't be worked out at all.
+    pub fn transfer_size(&self) -> Option<i64> {
+        let response = &self.response;
+        let body = if response.body_size >= 0 {
+            response.body_size
+        } else if response.content.size >= 0 {
+            response.content.size - response.content.compression.unwrap_or(0).max(0)
+        } else {
+            return None;
+        };
+        Some(response.headers_size.max(0) + body.max(0))
+    }
+
+    /// Uncompressed response body size: `content.size`, else `bodySize`
+    /// plus `content.compression`
+    pub fn content_decoded_size(&self) -> Option<i64> {
+        let response = &self.response;
+        if response.content.size >= 0 {
+            Some(response.content.size)
+        } else if response.body_size >= 0 {
+            Some(response.body_size + response.content.compression.unwrap_or(0).max(0))
+        } else {
+            None
+        }
+    }
 }
 
 impl Content {
@@ -293,10 +454,127 @@ impl Content {
             Some(text.as_bytes().to_vec())
         }
     }
+}
 
-    /// Get text content as string (decoding base64 if needed)
-    pub fn text_content(&self) -> Option<String> {
-        let bytes = self.decoded_text()?;
-        String::from_utf8(bytes).ok()
+/// Canonical spelling of an `httpVersion` value: `h2`, `HTTP/2` and
+/// `http/2.0` all become `HTTP/2`, `h3` and drafts like `h3-29` become
+/// `HTTP/3`. Empty values are `unknown`; anything unrecognized is kept as is.
+pub fn normalize_http_version(version: &str) -> String {
+    let v = version.trim().to_ascii_lowercase();
+    let canonical = match v.as_str() {
+        "" | "unknown" => "unknown",
+        "http/0.9" => "HTTP/0.9",
+        "http/1.0" | "http/1" => "HTTP/1.0",
+        "http/1.1" | "http1.1" => "HTTP/1.1",
+        "h2" | "h2c" | "http/2" | "http/2.0" | "http2" => "HTTP/2",
+        "h3" | "http/3" | "http/3.0" | "http3" | "quic" => "HTTP/3",
+        _ if v.starts_with("h3-") => "HTTP/3",
+        _ => return version.trim().to_string(),
+    };
+    canonical.to_string()
+}
+
+/// Headers a message may carry at most once. Others, like `Set-Cookie`,
+/// `Vary` or `Cache-Control`, can legally repeat.
+pub const SINGLETON_HEADERS: &[&str] = &[
+    "age", "authorization", "content-length", "content-location", "content-range", "content-type",
+    "date", "etag", "expires", "from", "host", "if-modified-since", "if-unmodified-since",
+    "last-modified", "location", "max-forwards", "origin", "proxy-authorization", "referer",
+    "retry-after", "server", "user-agent",
+];
+
+fn header_values<'a>(headers: &'a [Header], name: &str) -> Vec<&'a str> {
+    headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+        .collect()
+}
+
+/// Lowercased names of singleton headers that appear more than once, in
+/// order of first appearance
+pub fn duplicate_headers(headers: &[Header]) -> Vec<String> {
+    let mut seen: Vec<String> = Vec::new();
+    let mut duplicates: Vec<String> = Vec::new();
+    for h in headers {
+        let name = h.name.to_ascii_lowercase();
+        if !SINGLETON_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        if seen.contains(&name) {
+            if !duplicates.contains(&name) {
+                duplicates.push(name);
+            }
+        } else {
+            seen.push(name);
+        }
+    }
+    duplicates
+}
+
+/// Read a response status written as a number or a numeric string.
+///
+/// Some captures store `"200"` instead of `200`. Anything that isn't a
+/// whole number in range (`"unknown"`, `null`, `200.5`) is read as 0, the
+/// status HAR uses for requests without a response, so one bad entry
+/// doesn't fail the whole file.
+fn lenient_status<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let status = match value {
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64))
+            .and_then(|n| i32::try_from(n).ok()),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    Ok(status.unwrap_or(0))
+}
+
+/// Parse an ISO 8601 timestamp with a `Z` or numeric offset (`+02:00` or
+/// `+0200`), or a bare `YYYY-MM-DD` date taken as midnight UTC
+pub fn parse_timestamp(s: &str) -> Option<DateTime<FixedOffset>> {
+    let s = s.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Some(t);
+    }
+    if let Ok(t) = DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z") {
+        return Some(t);
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|t| t.and_utc().fixed_offset())
+}
+
+/// Undo a `Content-Encoding` header value such as `gzip` or `deflate, br`.
+///
+/// Codings are listed in the order they were applied, so they are removed
+/// last to first. Returns `None` for unknown codings or corrupt data.
+fn decompress_body(bytes: &[u8], content_encoding: &str) -> Option<Vec<u8>> {
+    let mut data = bytes.to_vec();
+
+    for coding in content_encoding.rsplit(',') {
+        let mut out = Vec::new();
+        match coding.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" => continue,
+            "gzip" | "x-gzip" => {
+                flate2::read::MultiGzDecoder::new(data.as_slice()).read_to_end(&mut out).ok()?;
+            }
+            "deflate" => {
+                // Servers send both zlib-wrapped and raw deflate streams
+                if flate2::read::ZlibDecoder::new(data.as_slice()).read_to_end(&mut out).is_err() {
+                    out.clear();
+                    flate2::read::DeflateDecoder::new(data.as_slice()).read_to_end(&mut out).ok()?;
+                }
+            }
+            "br" => {
+                brotli::Decompressor::new(data.as_slice(), 4096).read_to_end(&mut out).ok()?;
+            }
+            _ => return None,
+        }
+        data = out;
     }
+
+    Some(data)
 }
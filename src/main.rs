@@ -1,10 +1,11 @@
 mod har;
 mod commands;
+mod config;
 mod filter;
 mod output;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{FromArgMatches, Parser, Subcommand};
 use output::ColorWhen;
 
 #[derive(Parser)]
@@ -18,6 +19,18 @@ struct Cli {
     /// Coloring: auto, always, never
     #[arg(long, global = true, default_value = "auto")]
     color: ColorWhen,
+
+    /// Config file with default flags (default: ./harq.toml or ~/.harq.toml)
+    #[arg(long, global = true)]
+    config: Option<String>,
+
+    /// Draw tables with ASCII characters (default when the locale isn't UTF-8)
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Wrap JSON output in a {"_harq": {"version": ..}, "data": ..} envelope
+    #[arg(long, global = true)]
+    json_envelope: bool,
 }
 
 #[derive(Subcommand)]
@@ -49,16 +62,96 @@ enum Commands {
 
     /// Show headers for entries
     Headers(commands::HeadersCmd),
+
+    /// List distinct API endpoints with call counts, statuses and p95 latency
+    Endpoints(commands::EndpointsCmd),
+
+    /// Same as endpoints, with :id as the default placeholder
+    #[command(mut_arg("id_placeholder", |arg| arg.default_value(":id")))]
+    ApiSummary(commands::EndpointsCmd),
+
+    /// Merge several HAR files into one (write to stdout)
+    Merge(commands::MergeCmd),
+
+    /// Summarize requests, bytes and time per host
+    #[command(alias = "hosts")]
+    Domains(commands::DomainsCmd),
+
+    /// Live dashboard for a HAR file that is still being written
+    Top(commands::TopCmd),
+
+    /// Count/sum/min/max/mean/p50/p95 of a numeric field
+    Agg(commands::AggCmd),
+
+    /// Check a HAR file against the HAR 1.2 spec
+    Validate(commands::ValidateCmd),
+
+    /// Export an OpenAPI 3.0 skeleton of the captured REST calls
+    #[command(name = "openapi")]
+    OpenApi(commands::OpenApiCmd),
+
+    /// Show redirect chains, flagging loops and long chains
+    Redirects(commands::RedirectsCmd),
+
+    /// Summarize requests and bytes per response content type
+    ContentTypes(commands::ContentTypesCmd),
+
+    /// Scrub auth headers, cookies, tokens and body data before sharing (writes a HAR)
+    #[command(alias = "anonymize")]
+    Redact(commands::RedactCmd),
+
+    /// Show the form fields posted by an entry
+    Params(commands::ParamsCmd),
+
+    /// Report bytes saved by response compression, per content type
+    Savings(commands::SavingsCmd),
+
+    /// Split a HAR into one file per page, host or status class
+    Split(commands::SplitCmd),
+
+    /// Exit non-zero if any entry is over a time budget or matches a filter (for CI)
+    Assert(commands::AssertCmd),
+
+    /// Write a canonical HAR with sorted entries and headers, for stable diffs
+    #[command(visible_alias = "canonicalize")]
+    Normalize(commands::NormalizeCmd),
+
+    /// Pull a value out of a field with a regex capture group, per entry or as distinct counts
+    Extract(commands::ExtractCmd),
+
+    /// Capture span, requests per second and peak concurrent requests
+    Throughput(commands::ThroughputCmd),
+
+    /// Browse entries interactively (requires the `tui` feature)
+    #[cfg(feature = "tui")]
+    Browse(commands::BrowseCmd),
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let color = cli.color.should_color();
+fn main() {
+    let args: Vec<_> = std::env::args_os().collect();
+    let cli = match config::command(&args) {
+        Ok(command) => Cli::from_arg_matches(&command.get_matches_from(args)).unwrap_or_else(|err| err.exit()),
+        Err(err) => exit_with_error(&err, ColorWhen::Auto),
+    };
+    let color_when = cli.color;
 
-    // Configure colored output
-    if !color {
-        colored::control::set_override(false);
+    if let Err(err) = run(cli) {
+        exit_with_error(&err, color_when);
     }
+}
+
+fn exit_with_error(err: &anyhow::Error, color: ColorWhen) -> ! {
+    output::print_error(err, color.should_color_stderr());
+    std::process::exit(1);
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let color = cli.color.should_color();
+    output::json::set_envelope(cli.json_envelope);
+    output::set_ascii(cli.ascii || !output::locale_is_utf8());
+
+    // Configure colored output; `always` must also win over colored's own TTY check
+    colored::control::set_override(color);
 
     match cli.command {
         Commands::Info(cmd) => {
@@ -66,28 +159,31 @@ fn main() -> Result<()> {
             cmd.run(&har, color)
         }
         Commands::List(cmd) => {
+            if cmd.jsonl_input() || cmd.follow {
+                return cmd.run_jsonl(color);
+            }
+            if cmd.can_stream() {
+                return cmd.run_streaming(har::open(&cmd.file)?, color);
+            }
             let har = load_har(&cmd.file)?;
             cmd.run(&har, color)
         }
-        Commands::Count(cmd) => {
-            let har = load_har(&cmd.file)?;
-            cmd.run(&har)
-        }
+        Commands::Count(cmd) => cmd.run(har::open(&cmd.file)?),
         Commands::View(cmd) => {
             let har = load_har(&cmd.file)?;
             cmd.run(&har, color)
         }
-        Commands::Search(cmd) => {
-            let har = load_har(&cmd.file)?;
-            cmd.run(&har, color)
-        }
+        Commands::Search(cmd) => cmd.run(har::open(&cmd.file)?, color),
         Commands::Filter(cmd) => {
+            if cmd.explain {
+                return cmd.explain();
+            }
             let har = load_har(&cmd.file)?;
             cmd.run(&har)
         }
         Commands::Body(cmd) => {
             let har = load_har(&cmd.file)?;
-            cmd.run(&har)
+            cmd.run(&har, color)
         }
         Commands::Timing(cmd) => {
             let har = load_har(&cmd.file)?;
@@ -97,6 +193,58 @@ fn main() -> Result<()> {
             let har = load_har(&cmd.file)?;
             cmd.run(&har, color)
         }
+        Commands::Endpoints(cmd) | Commands::ApiSummary(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Merge(cmd) => cmd.run(),
+        Commands::Domains(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Top(cmd) => cmd.run(color),
+        Commands::Agg(cmd) => cmd.run(har::open(&cmd.file)?, color),
+        Commands::Validate(cmd) => cmd.run(har::open(&cmd.file)?, color),
+        Commands::OpenApi(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Redirects(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::ContentTypes(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Redact(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(har)
+        }
+        Commands::Params(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Savings(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Split(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(&har)
+        }
+        Commands::Assert(cmd) => cmd.run(har::open(&cmd.file)?, color),
+        Commands::Normalize(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(har)
+        }
+        Commands::Extract(cmd) => cmd.run(har::open(&cmd.file)?),
+        Commands::Throughput(cmd) => cmd.run(har::open(&cmd.file)?, color),
+        #[cfg(feature = "tui")]
+        Commands::Browse(cmd) => {
+            let har = load_har(&cmd.file)?;
+            cmd.run(har)
+        }
     }
 }
 
@@ -104,6 +252,6 @@ fn load_har(path: &str) -> Result<har::Har> {
     if path == "-" {
         har::parse_stdin()
     } else {
-        har::parse_file(path)
+        har::parse_reader(har::open(path)?)
     }
 }
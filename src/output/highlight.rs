@@ -0,0 +1,116 @@
+use colored::Colorize;
+use serde_json::Value;
+use std::ops::Range;
+
+/// Mark byte ranges of `text` (sorted, non-overlapping) as matches.
+/// Without color the text is returned as-is, with no markers.
+pub fn highlight_spans(text: &str, spans: &[Range<usize>], color: bool) -> String {
+    if !color || spans.is_empty() {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for span in spans {
+        let Some(matched) = text.get(span.clone()) else {
+            continue;
+        };
+        out.push_str(&text[last..span.start]);
+        out.push_str(&matched.black().on_yellow().to_string());
+        last = span.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Pretty-print JSON like `serde_json::to_string_pretty`, with ANSI colors
+/// for keys, strings, numbers, booleans and null when `color` is set.
+pub fn render_json(value: &Value, color: bool) -> String {
+    if !color {
+        return serde_json::to_string_pretty(value).unwrap_or_default();
+    }
+
+    let mut out = String::new();
+    write_value(&mut out, value, 0);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Null => out.push_str(&"null".dimmed().to_string()),
+        Value::Bool(b) => out.push_str(&b.to_string().magenta().to_string()),
+        Value::Number(n) => out.push_str(&n.to_string().yellow().to_string()),
+        Value::String(s) => out.push_str(&quote(s).green().to_string()),
+        Value::Array(items) if items.is_empty() => out.push_str("[]"),
+        Value::Object(map) if map.is_empty() => out.push_str("{}"),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                indent(out, depth + 1);
+                write_value(out, item, depth + 1);
+            }
+            out.push('\n');
+            indent(out, depth);
+            out.push(']');
+        }
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                indent(out, depth + 1);
+                out.push_str(&quote(key).cyan().to_string());
+                out.push_str(": ");
+                write_value(out, item, depth + 1);
+            }
+            out.push('\n');
+            indent(out, depth);
+            out.push('}');
+        }
+    }
+}
+
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+fn indent(out: &mut String, depth: usize) {
+    out.push_str(&"  ".repeat(depth));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_colored_layout_matches_pretty() {
+        let value: Value = serde_json::from_str(
+            r#"{"name": "a \"quoted\" str", "n": -1.5, "ok": true, "none": null, "list": [1, {"x": []}], "empty": {}}"#,
+        )
+        .unwrap();
+        let pretty = serde_json::to_string_pretty(&value).unwrap();
+
+        assert_eq!(strip_ansi(&render_json(&value, true)), pretty);
+        assert_eq!(render_json(&value, false), pretty);
+    }
+
+    #[test]
+    fn test_highlight_spans() {
+        let text = "/api/users/api";
+        assert_eq!(highlight_spans(text, &[1..4, 11..14], false), text);
+        assert_eq!(strip_ansi(&highlight_spans(text, &[1..4, 11..14], true)), text);
+    }
+}
@@ -1,45 +1,193 @@
-use crate::har::{Entry, Har};
+use crate::har::{Creator, Entry, Har, Page};
 use anyhow::Result;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Output entries as JSON array
-pub fn print_entries_json(entries: &[(usize, &Entry)], pretty: bool) -> Result<()> {
-    let json: Vec<&Entry> = entries.iter().map(|(_, e)| *e).collect();
+/// Version of harq's JSON output shapes, reported in the `_harq` envelope
+pub const SCHEMA_VERSION: &str = "1";
 
-    let output = if pretty {
-        serde_json::to_string_pretty(&json)?
-    } else {
-        serde_json::to_string(&json)?
-    };
+static ENVELOPE: AtomicBool = AtomicBool::new(false);
+
+/// Wrap all JSON output in a `{"_harq": {"version": ..}, "data": ..}` envelope
+pub fn set_envelope(enabled: bool) {
+    ENVELOPE.store(enabled, Ordering::Relaxed);
+}
+
+fn envelope() -> bool {
+    ENVELOPE.load(Ordering::Relaxed)
+}
+
+#[derive(Serialize)]
+struct Meta {
+    version: &'static str,
+}
 
-    println!("{}", output);
+const META: Meta = Meta { version: SCHEMA_VERSION };
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    _harq: Meta,
+    data: &'a T,
+}
+
+/// Output a value as JSON on stdout
+pub fn print_json<T: Serialize>(value: &T, pretty: bool) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_json(&mut out, value, pretty)?;
+    writeln!(out)?;
+    out.flush()?;
     Ok(())
 }
 
+/// Write entries as a JSON array, serialized one at a time
+pub fn write_entries_json<W: Write>(writer: W, entries: &[(usize, &Entry)], pretty: bool) -> Result<()> {
+    write_json(writer, &EntrySeq(entries), pretty)
+}
+
 /// Output single entry as JSON
 pub fn print_entry_json(entry: &Entry, pretty: bool) -> Result<()> {
-    let output = if pretty {
-        serde_json::to_string_pretty(entry)?
-    } else {
-        serde_json::to_string(entry)?
+    print_json(entry, pretty)
+}
+
+/// Write a HAR containing only the selected entries.
+///
+/// Entries are serialized one at a time straight into the writer, so no
+/// filtered copy of the HAR or intermediate string is built. The envelope
+/// is added as a top-level `_harq` key so the output stays a valid HAR.
+pub fn write_filtered_har<W: Write>(
+    writer: W,
+    har: &Har,
+    entries: &[(usize, &Entry)],
+    pretty: bool,
+) -> Result<()> {
+    let pages = har.log.pages.as_ref().map(|pages| pages.iter().collect());
+    write_har_subset(writer, har, entries, pages, pretty)
+}
+
+/// Like `write_filtered_har`, but keeping only the pages the selected
+/// entries refer to, so each part of a split HAR stands on its own
+pub fn write_har_part<W: Write>(
+    writer: W,
+    har: &Har,
+    entries: &[(usize, &Entry)],
+    pretty: bool,
+) -> Result<()> {
+    let pages = har.log.pages.as_ref().map(|pages| {
+        pages
+            .iter()
+            .filter(|p| entries.iter().any(|(_, e)| e.pageref.as_deref() == Some(p.id.as_str())))
+            .collect()
+    });
+    write_har_subset(writer, har, entries, pages, pretty)
+}
+
+fn write_har_subset<W: Write>(
+    writer: W,
+    har: &Har,
+    entries: &[(usize, &Entry)],
+    pages: Option<Vec<&Page>>,
+    pretty: bool,
+) -> Result<()> {
+    let filtered = FilteredHar {
+        log: FilteredLog {
+            version: &har.log.version,
+            creator: &har.log.creator,
+            browser: &har.log.browser,
+            pages,
+            entries: EntrySeq(entries),
+            comment: &har.log.comment,
+        },
+        harq: envelope().then_some(META),
     };
 
-    println!("{}", output);
+    write_plain_json(writer, &filtered, pretty)
+}
+
+/// Output entry summaries as NDJSON (one object per line)
+pub fn print_summaries_ndjson(entries: &[(usize, &Entry)]) -> Result<()> {
+    print_ndjson(entries.iter().map(|(i, e)| EntrySummary::from_entry(*i, e)))
+}
+
+/// Output items as NDJSON to stdout
+pub fn print_ndjson<T: Serialize>(items: impl IntoIterator<Item = T>) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_ndjson(&mut out, items)?;
+    out.flush()?;
     Ok(())
 }
 
-/// Output full HAR as JSON (for filtered output)
-pub fn print_har_json(har: &Har, pretty: bool) -> Result<()> {
-    let output = if pretty {
-        serde_json::to_string_pretty(har)?
+/// Write each item as compact JSON on its own line
+pub fn write_ndjson<W: Write, T: Serialize>(writer: W, items: impl IntoIterator<Item = T>) -> Result<()> {
+    write_ndjson_with(writer, items, envelope())
+}
+
+/// With the envelope, a `{"_harq": ..}` line comes before the items
+fn write_ndjson_with<W: Write, T: Serialize>(
+    mut writer: W,
+    items: impl IntoIterator<Item = T>,
+    envelope: bool,
+) -> Result<()> {
+    if envelope {
+        serde_json::to_writer(&mut writer, &serde_json::json!({ "_harq": META }))?;
+        writer.write_all(b"\n")?;
+    }
+    for item in items {
+        serde_json::to_writer(&mut writer, &item)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Write a value as JSON, wrapped in the envelope when enabled
+pub fn write_json<W: Write, T: Serialize>(writer: W, value: &T, pretty: bool) -> Result<()> {
+    write_json_with(writer, value, pretty, envelope())
+}
+
+fn write_json_with<W: Write, T: Serialize>(writer: W, value: &T, pretty: bool, envelope: bool) -> Result<()> {
+    if envelope {
+        write_plain_json(writer, &Envelope { _harq: META, data: value }, pretty)
     } else {
-        serde_json::to_string(har)?
-    };
+        write_plain_json(writer, value, pretty)
+    }
+}
 
-    println!("{}", output);
+fn write_plain_json<W: Write, T: Serialize>(writer: W, value: &T, pretty: bool) -> Result<()> {
+    if pretty {
+        serde_json::to_writer_pretty(writer, value)?;
+    } else {
+        serde_json::to_writer(writer, value)?;
+    }
     Ok(())
 }
 
+/// Borrowed mirror of `Har` with a subset of entries (field order matches `Log`)
+#[derive(Serialize)]
+struct FilteredHar<'a> {
+    log: FilteredLog<'a>,
+    #[serde(rename = "_harq", skip_serializing_if = "Option::is_none")]
+    harq: Option<Meta>,
+}
+
+#[derive(Serialize)]
+struct FilteredLog<'a> {
+    version: &'a str,
+    creator: &'a Creator,
+    browser: &'a Option<Creator>,
+    pages: Option<Vec<&'a Page>>,
+    entries: EntrySeq<'a>,
+    comment: &'a Option<String>,
+}
+
+/// Serializes selected entries as a JSON array without collecting them
+struct EntrySeq<'a>(&'a [(usize, &'a Entry)]);
+
+impl Serialize for EntrySeq<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|(_, e)| *e))
+    }
+}
+
 /// Simplified entry summary for compact JSON output
 #[derive(Serialize)]
 pub struct EntrySummary {
@@ -73,26 +221,83 @@ pub fn print_summaries_json(entries: &[(usize, &Entry)], pretty: bool) -> Result
         .map(|(i, e)| EntrySummary::from_entry(*i, e))
         .collect();
 
-    let output = if pretty {
-        serde_json::to_string_pretty(&summaries)?
-    } else {
-        serde_json::to_string(&summaries)?
-    };
-
-    println!("{}", output);
-    Ok(())
+    print_json(&summaries, pretty)
 }
 
-/// Create a filtered HAR with only selected entries
-pub fn create_filtered_har(har: &Har, entries: &[(usize, &Entry)]) -> Har {
-    Har {
-        log: crate::har::Log {
-            version: har.log.version.clone(),
-            creator: har.log.creator.clone(),
-            browser: har.log.browser.clone(),
-            pages: har.log.pages.clone(),
-            entries: entries.iter().map(|(_, e)| (*e).clone()).collect(),
-            comment: har.log.comment.clone(),
-        },
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_streamed_har_matches_buffered() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/a", 200),
+            fixtures::entry_json("POST", "https://example.com/b", 500),
+            fixtures::entry_json("GET", "https://example.com/c", 404),
+        ]);
+        let selected: Vec<(usize, &Entry)> = har.log.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.response.status >= 400)
+            .map(|(i, e)| (i + 1, e))
+            .collect();
+
+        let mut buffered = har.clone();
+        buffered.log.entries.retain(|e| e.response.status >= 400);
+
+        for pretty in [true, false] {
+            let mut streamed = Vec::new();
+            write_filtered_har(&mut streamed, &har, &selected, pretty).unwrap();
+
+            let expected = if pretty {
+                serde_json::to_string_pretty(&buffered).unwrap()
+            } else {
+                serde_json::to_string(&buffered).unwrap()
+            };
+            assert_eq!(String::from_utf8(streamed).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_ndjson_lines_parse_independently() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/a", 200),
+            fixtures::entry_json("POST", "https://example.com/b", 201),
+        ]);
+        let summaries = har.log.entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| EntrySummary::from_entry(i + 1, e));
+
+        let mut out = Vec::new();
+        write_ndjson(&mut out, summaries).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (i, line) in lines.iter().enumerate() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["index"], i + 1);
+        }
+    }
+
+    #[test]
+    fn test_envelope_wraps_output() {
+        let mut out = Vec::new();
+        write_json_with(&mut out, &vec![1, 2], false, true).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value["_harq"]["version"], SCHEMA_VERSION);
+        assert_eq!(value["data"], serde_json::json!([1, 2]));
+
+        let mut out = Vec::new();
+        write_ndjson_with(&mut out, [1, 2], true).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines, [r#"{"_harq":{"version":"1"}}"#, "1", "2"]);
+
+        let mut out = Vec::new();
+        write_json_with(&mut out, &vec![1, 2], false, false).unwrap();
+        assert_eq!(out, b"[1,2]");
     }
 }
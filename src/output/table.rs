@@ -1,8 +1,12 @@
-use crate::har::Entry;
-use crate::output::{format_bytes, format_time, truncate};
+use crate::har::{duplicate_headers, Cache, Entry, Header, Timings};
+use crate::output::highlight::render_json;
+use crate::output::{ascii, extract_host, extract_path, format_bytes, format_time, terminal_width, truncate};
+use anyhow::{Result, bail};
 use colored::Colorize;
+use std::io::{self, Write};
 use tabled::{
-    settings::Style,
+    builder::Builder,
+    settings::{style::HorizontalLine, Style},
     Table, Tabled,
 };
 
@@ -33,7 +37,7 @@ impl EntryRow {
         let status = if color {
             colorize_status(entry.response.status)
         } else {
-            entry.response.status.to_string()
+            status_label(entry.response.status)
         };
 
         let url = truncate(&entry.request.url, max_url_len);
@@ -62,9 +66,19 @@ fn colorize_method(method: &str) -> String {
     }
 }
 
-fn colorize_status(status: i32) -> String {
-    let s = status.to_string();
+/// Status code as text, with status 0 (aborted/blocked) shown as "(no response)"
+pub fn status_label(status: i32) -> String {
+    if status == 0 {
+        "(no response)".to_string()
+    } else {
+        status.to_string()
+    }
+}
+
+pub fn colorize_status(status: i32) -> String {
+    let s = status_label(status);
     match status {
+        0 => s.dimmed().to_string(),
         200..=299 => s.green().to_string(),
         300..=399 => s.yellow().to_string(),
         400..=499 => s.red().to_string(),
@@ -73,25 +87,259 @@ fn colorize_status(status: i32) -> String {
     }
 }
 
-pub fn print_entries_table(entries: &[(usize, &Entry)], color: bool, max_url_len: usize) {
+/// URL/host cut used when the terminal width doesn't apply (`--select`, `search`)
+pub const DEFAULT_MAX_URL: usize = 60;
+
+/// Narrowest a width-fitted column gets, however small the terminal
+const MIN_FLEXIBLE_WIDTH: usize = 20;
+
+/// Width tables are fitted to: the terminal, else 80
+pub fn table_width() -> usize {
+    terminal_width().unwrap_or(80)
+}
+
+/// Room left for column `flex` so a `style_table` table of `rows` fits in
+/// `term_width`, given the widest cell (header included) of every other column.
+/// Never less than `MIN_FLEXIBLE_WIDTH`.
+pub fn flexible_width<T: Tabled>(rows: &[T], flex: usize, term_width: usize) -> usize {
+    let mut widths: Vec<usize> = T::headers().iter().map(|h| visible_width(h)).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.fields()) {
+            *width = (*width).max(visible_width(&cell));
+        }
+    }
+
+    // Every column has a border and a space either side of its text; one
+    // more border closes the row
+    let others: usize = widths
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != flex)
+        .map(|(_, w)| w + 3)
+        .sum();
+    term_width.saturating_sub(1 + others + 3).max(MIN_FLEXIBLE_WIDTH)
+}
+
+/// Width of a cell as displayed, ignoring ANSI color codes
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip to the end of the escape sequence, e.g. \x1b[1;31m
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Print the default `list` table. URLs are cut at `max_url_len`, or to fit
+/// the terminal when it is None.
+pub fn print_entries_table(entries: &[(usize, &Entry)], color: bool, max_url_len: Option<usize>) {
     if entries.is_empty() {
         println!("No entries found.");
         return;
     }
 
-    let rows: Vec<EntryRow> = entries
-        .iter()
-        .map(|(i, e)| EntryRow::from_entry(*i, e, color, max_url_len))
-        .collect();
+    let rows = entry_rows(entries, color, max_url_len, table_width());
 
     let mut table = Table::new(rows);
-    table.with(Style::rounded());
+    style_table(&mut table);
 
     println!("{}", table);
 }
 
+fn entry_rows(entries: &[(usize, &Entry)], color: bool, max_url_len: Option<usize>, term_width: usize) -> Vec<EntryRow> {
+    let mut rows: Vec<EntryRow> = entries
+        .iter()
+        .map(|(i, e)| EntryRow::from_entry(*i, e, color, max_url_len.unwrap_or(usize::MAX)))
+        .collect();
+
+    if max_url_len.is_none() {
+        let max_url_len = flexible_width(&rows, 5, term_width);
+        for row in &mut rows {
+            row.url = truncate(&row.url, max_url_len);
+        }
+    }
+    rows
+}
+
+/// Totals printed under the `list` and `search` tables by `--summary`
+#[derive(Debug, PartialEq)]
+pub struct SummaryFooter {
+    /// Entries in the table, after head/tail/limit/offset
+    pub shown: usize,
+    /// Entries that matched before any limit
+    pub matched: usize,
+    /// Response body bytes of the shown entries
+    pub bytes: i64,
+    /// Total time of the shown entries
+    pub time_ms: f64,
+}
+
+impl SummaryFooter {
+    pub fn new(shown: &[(usize, &Entry)], matched: usize) -> Self {
+        Self {
+            shown: shown.len(),
+            matched,
+            bytes: shown.iter().map(|(_, e)| e.response.body_size.max(0)).sum(),
+            time_ms: shown.iter().map(|(_, e)| e.time.max(0.0)).sum(),
+        }
+    }
+
+    /// `3 shown of 10 matched, 12.3KB, 1.07s`
+    pub fn render(&self, color: bool) -> String {
+        let value = |s: String| if color { s.bold().to_string() } else { s };
+        format!(
+            "{} shown of {} matched, {}, {}",
+            value(self.shown.to_string()),
+            value(self.matched.to_string()),
+            value(format_bytes(self.bytes)),
+            value(format_time(self.time_ms))
+        )
+    }
+}
+
+/// A column selectable with `list --select`, also a `list --format` placeholder
+#[derive(Debug)]
+pub struct Column {
+    pub name: &'static str,
+    pub header: &'static str,
+    extract: fn(usize, &Entry, bool, usize) -> String,
+}
+
+impl Column {
+    /// This column's cell for an entry, with URLs and paths cut to `max_url_len`
+    pub fn value(&self, index: usize, entry: &Entry, color: bool, max_url_len: usize) -> String {
+        (self.extract)(index, entry, color, max_url_len)
+    }
+}
+
+/// All selectable columns, in default display order
+pub const COLUMNS: &[Column] = &[
+    Column { name: "index", header: "#", extract: |i, _, _, _| i.to_string() },
+    Column {
+        name: "method",
+        header: "Method",
+        extract: |_, e, color, _| {
+            if color { colorize_method(&e.request.method) } else { e.request.method.clone() }
+        },
+    },
+    Column {
+        name: "status",
+        header: "Status",
+        extract: |_, e, color, _| {
+            if color { colorize_status(e.response.status) } else { status_label(e.response.status) }
+        },
+    },
+    Column { name: "time", header: "Time", extract: |_, e, _, _| format_time(e.time) },
+    Column { name: "size", header: "Size", extract: |_, e, _, _| format_bytes(e.response.body_size) },
+    Column { name: "url", header: "URL", extract: |_, e, _, max| truncate(&e.request.url, max) },
+    Column { name: "host", header: "Host", extract: |_, e, _, _| extract_host(&e.request.url).to_string() },
+    Column { name: "path", header: "Path", extract: |_, e, _, max| truncate(extract_path(&e.request.url), max) },
+    Column {
+        name: "type",
+        header: "Type",
+        extract: |_, e, _, _| e.content_type().unwrap_or("-").to_string(),
+    },
+    Column { name: "started", header: "Started", extract: |_, e, _, _| e.started_date_time.clone() },
+    Column {
+        name: "ip",
+        header: "Server IP",
+        extract: |_, e, _, _| e.server_ip_address.clone().unwrap_or_else(|| "-".to_string()),
+    },
+];
+
+/// Resolve column names to their definitions, preserving the requested order
+pub fn parse_columns(names: &[String]) -> Result<Vec<&'static Column>> {
+    names
+        .iter()
+        .map(|name| {
+            let name = name.trim().to_lowercase();
+            match COLUMNS.iter().find(|c| c.name == name) {
+                Some(column) => Ok(column),
+                None => {
+                    let valid: Vec<&str> = COLUMNS.iter().map(|c| c.name).collect();
+                    bail!("Unknown column '{}'. Valid columns: {}", name, valid.join(", "))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Build a table with only the given columns
+pub fn build_columns_table(
+    entries: &[(usize, &Entry)],
+    columns: &[&Column],
+    color: bool,
+    max_url_len: usize,
+) -> Table {
+    let mut builder = Builder::default();
+    builder.push_record(columns.iter().map(|c| c.header));
+
+    for (i, entry) in entries {
+        builder.push_record(columns.iter().map(|c| c.value(*i, entry, color, max_url_len)));
+    }
+
+    let mut table = builder.build();
+    style_table(&mut table);
+    table
+}
+
+/// Apply the shared table style: rounded box characters, or ASCII with `--ascii`
+pub fn style_table(table: &mut Table) {
+    apply_style(table, ascii());
+}
+
+fn apply_style(table: &mut Table, ascii: bool) {
+    if ascii {
+        // Same layout as rounded: outer border plus a line under the header
+        let header_line = HorizontalLine::full('-', '+', '+', '+');
+        table.with(Style::ascii().remove_horizontal().horizontals([(1, header_line)]));
+    } else {
+        table.with(Style::rounded());
+    }
+}
+
+pub fn print_columns_table(
+    entries: &[(usize, &Entry)],
+    columns: &[String],
+    color: bool,
+    max_url_len: usize,
+) -> Result<()> {
+    let columns = parse_columns(columns)?;
+
+    if entries.is_empty() {
+        println!("No entries found.");
+        return Ok(());
+    }
+
+    println!("{}", build_columns_table(entries, &columns, color, max_url_len));
+    Ok(())
+}
+
 /// Print detailed view of an entry
-pub fn print_entry_detail(index: usize, entry: &Entry, color: bool, show_body: bool) {
+pub fn print_entry_detail(index: usize, entry: &Entry, color: bool, show_body: bool, verbose: bool) {
+    let mut out = io::stdout().lock();
+    let _ = write_entry_detail(&mut out, index, entry, color, show_body, verbose);
+}
+
+/// Write the detailed view of an entry, as shown by `view`. `verbose` adds
+/// the connection id, cache info and custom fields when present.
+pub fn write_entry_detail<W: Write>(
+    out: &mut W,
+    index: usize,
+    entry: &Entry,
+    color: bool,
+    show_body: bool,
+    verbose: bool,
+) -> io::Result<()> {
     let label = |s: &str| {
         if color {
             s.bold().to_string()
@@ -100,106 +348,353 @@ pub fn print_entry_detail(index: usize, entry: &Entry, color: bool, show_body: b
         }
     };
 
-    println!("{}", "=".repeat(60));
-    println!("{} Entry #{}", label(">>>"), index);
-    println!("{}", "=".repeat(60));
+    writeln!(out, "{}", "=".repeat(60))?;
+    writeln!(out, "{} Entry #{}", label(">>>"), index)?;
+    writeln!(out, "{}", "=".repeat(60))?;
 
     // Request section
-    println!("\n{}", label("REQUEST"));
-    println!("  {} {} {}",
+    writeln!(out, "\n{}", label("REQUEST"))?;
+    writeln!(out, "  {} {} {}",
         colorize_method(&entry.request.method),
         entry.request.url,
         entry.request.http_version.dimmed()
-    );
+    )?;
 
     if !entry.request.headers.is_empty() {
-        println!("\n  {}:", label("Headers"));
-        for h in &entry.request.headers {
-            println!("    {}: {}", h.name.cyan(), h.value);
-        }
+        write_headers(out, &entry.request.headers, &label)?;
     }
 
     if let Some(ref post_data) = entry.request.post_data {
-        println!("\n  {}: {}", label("Content-Type"), post_data.mime_type);
+        writeln!(out, "\n  {}: {}", label("Content-Type"), post_data.mime_type)?;
         if show_body {
             if let Some(ref text) = post_data.text {
-                println!("  {}:", label("Body"));
-                print_body_preview(text, 500);
+                writeln!(out, "  {}:", label("Body"))?;
+                write_body_preview(out, text, 500, color)?;
             }
         }
     }
 
     // Response section
-    println!("\n{}", label("RESPONSE"));
-    println!("  {} {} {}",
+    writeln!(out, "\n{}", label("RESPONSE"))?;
+    writeln!(out, "  {} {} {}",
         colorize_status(entry.response.status),
         entry.response.status_text,
         entry.response.http_version.dimmed()
-    );
+    )?;
 
     if !entry.response.headers.is_empty() {
-        println!("\n  {}:", label("Headers"));
-        for h in &entry.response.headers {
-            println!("    {}: {}", h.name.cyan(), h.value);
-        }
+        write_headers(out, &entry.response.headers, &label)?;
     }
 
     if show_body {
-        if let Some(text) = entry.response.content.text_content() {
-            println!("\n  {}:", label("Body"));
-            print_body_preview(&text, 1000);
+        if let Some(text) = entry.response_text() {
+            writeln!(out, "\n  {}:", label("Body"))?;
+            write_body_preview(out, &text, 1000, color)?;
         }
     }
 
     // Timing section
-    println!("\n{}", label("TIMING"));
-    println!("  Total: {}", format_time(entry.time).yellow());
-    print_timing_detail(&entry.timings);
+    writeln!(out, "\n{}", label("TIMING"))?;
+    writeln!(out, "  Total: {}", format_time(entry.time).yellow())?;
+    write_timing_detail(out, &entry.timings)?;
 
     // Metadata
     if let Some(ref ip) = entry.server_ip_address {
-        println!("\n{}: {}", label("Server IP"), ip);
+        writeln!(out, "\n{}: {}", label("Server IP"), ip)?;
+    }
+    if verbose {
+        if let Some(ref connection) = entry.connection {
+            writeln!(out, "{}: {}", label("Connection"), connection)?;
+        }
+    }
+    writeln!(out, "{}: {}", label("Started"), entry.started_date_time)?;
+    if verbose {
+        write_cache(out, &entry.cache, &label)?;
+        write_custom_fields(out, entry, &label)?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// CACHE section, skipped when the entry has no cache info
+fn write_cache<W: Write>(out: &mut W, cache: &Cache, label: &dyn Fn(&str) -> String) -> io::Result<()> {
+    let sides = [("Before request", &cache.before_request), ("After request", &cache.after_request)];
+    if sides.iter().all(|(_, c)| c.is_none()) && cache.comment.is_none() {
+        return Ok(());
+    }
+
+    writeln!(out, "\n{}", label("CACHE"))?;
+    for (name, cached) in sides {
+        let Some(cached) = cached else { continue };
+        let mut parts = Vec::new();
+        if let Some(ref expires) = cached.expires {
+            parts.push(format!("expires: {}", expires));
+        }
+        if let Some(ref last_access) = cached.last_access {
+            parts.push(format!("last access: {}", last_access));
+        }
+        if let Some(ref e_tag) = cached.e_tag {
+            parts.push(format!("etag: {}", e_tag));
+        }
+        if let Some(hit_count) = cached.hit_count {
+            parts.push(format!("hits: {}", hit_count));
+        }
+        if let Some(ref comment) = cached.comment {
+            parts.push(format!("comment: {}", comment));
+        }
+        writeln!(out, "  {}: {}", name, if parts.is_empty() { "-".to_string() } else { parts.join(" | ") })?;
+    }
+    if let Some(ref comment) = cache.comment {
+        writeln!(out, "  Comment: {}", comment)?;
+    }
+    Ok(())
+}
+
+/// CUSTOM section with the entry's and its content's extra fields (`_initiator`,
+/// `_fromCache`, ...), sorted by name; strings are shown bare, other values as JSON
+fn write_custom_fields<W: Write>(out: &mut W, entry: &Entry, label: &dyn Fn(&str) -> String) -> io::Result<()> {
+    let mut fields: Vec<(String, &serde_json::Value)> = entry
+        .custom
+        .iter()
+        .map(|(k, v)| (k.clone(), v))
+        .chain(entry.response.content.custom.iter().map(|(k, v)| (format!("content.{}", k), v)))
+        .collect();
+    if fields.is_empty() {
+        return Ok(());
     }
-    println!("{}: {}", label("Started"), entry.started_date_time);
-    println!();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+
+    writeln!(out, "\n{}", label("CUSTOM"))?;
+    for (name, value) in fields {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        writeln!(out, "  {}: {}", name.cyan(), value)?;
+    }
+    Ok(())
 }
 
-fn print_body_preview(text: &str, max_len: usize) {
+/// Header block with a count, flagging repeated singleton headers such as `Content-Length`
+fn write_headers<W: Write>(out: &mut W, headers: &[Header], label: &dyn Fn(&str) -> String) -> io::Result<()> {
+    let duplicates = duplicate_headers(headers);
+    writeln!(out, "\n  {} ({}):", label("Headers"), headers.len())?;
+    for h in headers {
+        if duplicates.contains(&h.name.to_ascii_lowercase()) {
+            writeln!(out, "    {}: {} {}", h.name.yellow().bold(), h.value, "[duplicate]".yellow())?;
+        } else {
+            writeln!(out, "    {}: {}", h.name.cyan(), h.value)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_body_preview<W: Write>(out: &mut W, text: &str, max_len: usize, color: bool) -> io::Result<()> {
     let preview = if text.len() > max_len {
-        format!("{}... ({} bytes total)", &text[..max_len], text.len())
+        // Cut on a character boundary so multibyte text can't split mid-character
+        let mut end = max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}... ({} bytes total)", &text[..end], text.len())
     } else {
         text.to_string()
     };
 
     // Try to pretty-print JSON
     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&preview) {
-        if let Ok(pretty) = serde_json::to_string_pretty(&json) {
-            for line in pretty.lines().take(30) {
-                println!("    {}", line);
-            }
-            return;
+        for line in render_json(&json, color).lines().take(30) {
+            writeln!(out, "    {}", line)?;
         }
+        return Ok(());
     }
 
     for line in preview.lines().take(30) {
-        println!("    {}", line);
+        writeln!(out, "    {}", line)?;
     }
+    Ok(())
 }
 
-fn print_timing_detail(timings: &crate::har::Timings) {
+fn write_timing_detail<W: Write>(out: &mut W, timings: &Timings) -> io::Result<()> {
     let fmt = |v: Option<f64>| -> String {
-        v.map(|t| format_time(t)).unwrap_or_else(|| "-".to_string())
+        Timings::applicable(v).map(format_time).unwrap_or_else(|| "-".to_string())
     };
 
-    println!("  blocked: {} | dns: {} | connect: {} | ssl: {}",
+    writeln!(out, "  blocked: {} | dns: {} | connect: {} | ssl: {}",
         fmt(timings.blocked),
         fmt(timings.dns),
         fmt(timings.connect),
         fmt(timings.ssl)
-    );
-    println!("  send: {} | wait: {} | receive: {}",
+    )?;
+    writeln!(out, "  send: {} | wait: {} | receive: {}",
         fmt(timings.send),
         fmt(timings.wait),
         fmt(timings.receive)
-    );
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_summary_footer_totals() {
+        let big = fixtures::entry_with("GET", "https://example.com/a", 200, |e| {
+            e["response"]["bodySize"] = serde_json::json!(2048);
+            e["time"] = serde_json::json!(1500.0);
+        });
+        let unknown = fixtures::entry_with("GET", "https://example.com/b", 200, |e| {
+            e["response"]["bodySize"] = serde_json::json!(-1);
+            e["time"] = serde_json::json!(-1.0);
+        });
+        let har = fixtures::har(vec![big, unknown]);
+        let entries: Vec<(usize, &Entry)> = har.log.entries.iter().enumerate().map(|(i, e)| (i + 1, e)).collect();
+
+        let footer = SummaryFooter::new(&entries, 7);
+        assert_eq!(footer, SummaryFooter { shown: 2, matched: 7, bytes: 2048, time_ms: 1500.0 });
+        assert_eq!(footer.render(false), "2 shown of 7 matched, 2.0KB, 1.50s");
+    }
+
+    #[test]
+    fn test_body_preview_cuts_multibyte_text_on_a_boundary() {
+        let text = format!("a{}", "é".repeat(600));
+        let mut out = Vec::new();
+        write_body_preview(&mut out, &text, 500, false).unwrap();
+        let preview = String::from_utf8(out).unwrap();
+
+        assert_eq!(preview, format!("    a{}... (1201 bytes total)\n", "é".repeat(249)));
+    }
+
+    #[test]
+    fn test_detail_counts_headers_and_flags_duplicates() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["response"]["headers"] = serde_json::json!([
+                {"name": "Content-Length", "value": "10"},
+                {"name": "Set-Cookie", "value": "a=1"},
+                {"name": "set-cookie", "value": "b=2"},
+                {"name": "content-length", "value": "12"},
+            ]);
+        });
+        let entry = fixtures::to_entry(json);
+
+        let mut out = Vec::new();
+        write_entry_detail(&mut out, 1, &entry, false, false, false).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.contains("Headers (4):"));
+        let flagged: Vec<&str> = text.lines().filter(|l| l.ends_with("[duplicate]")).map(str::trim).collect();
+        assert_eq!(flagged, ["Content-Length: 10 [duplicate]", "content-length: 12 [duplicate]"]);
+    }
+
+    #[test]
+    fn test_verbose_detail_shows_cache_connection_and_custom_fields() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["connection"] = serde_json::json!("4711");
+            e["_fromCache"] = serde_json::json!("disk");
+            e["_initiator"] = serde_json::json!({"type": "parser"});
+            e["response"]["content"]["_transferSize"] = serde_json::json!(0);
+            e["cache"] = serde_json::json!({"beforeRequest": {"lastAccess": "2024-01-01T00:00:00Z", "eTag": "\"abc\"", "hitCount": 3}});
+        });
+        let entry = fixtures::to_entry(json);
+
+        let detail = |verbose| {
+            let mut out = Vec::new();
+            write_entry_detail(&mut out, 1, &entry, false, false, verbose).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let compact = detail(false);
+        assert!(!compact.contains("Connection") && !compact.contains("CACHE") && !compact.contains("_fromCache"));
+
+        let verbose = detail(true);
+        assert!(verbose.contains("Connection: 4711"));
+        assert!(verbose.contains("  Before request: last access: 2024-01-01T00:00:00Z | etag: \"abc\" | hits: 3"));
+        assert!(!verbose.contains("After request"));
+        let custom: Vec<&str> = verbose.lines().skip_while(|l| *l != "CUSTOM").skip(1).take(3).map(str::trim).collect();
+        assert_eq!(custom, ["_fromCache: disk", "_initiator: {\"type\":\"parser\"}", "content._transferSize: 0"]);
+    }
+
+    #[test]
+    fn test_timing_detail_dashes_missing_and_not_applicable() {
+        let timings: Timings = serde_json::from_value(serde_json::json!({
+            "blocked": -1, "dns": 0, "ssl": null, "send": 1, "wait": 20, "receive": -1
+        }))
+        .unwrap();
+
+        let mut out = Vec::new();
+        write_timing_detail(&mut out, &timings).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("blocked: - | dns: 0ms | connect: - | ssl: -"));
+        assert!(text.contains("send: 1ms | wait: 20ms | receive: -"));
+    }
+
+    #[test]
+    fn test_select_columns_in_order() {
+        let har = fixtures::har(vec![fixtures::entry_json("GET", "https://example.com/a", 200)]);
+        let entries: Vec<(usize, &Entry)> = vec![(1, &har.log.entries[0])];
+        let columns = parse_columns(&["host".to_string(), "method".to_string()]).unwrap();
+
+        let rendered = build_columns_table(&entries, &columns, false, 60).to_string();
+        let header = rendered.lines().nth(1).unwrap();
+        assert!(header.find("Host").unwrap() < header.find("Method").unwrap());
+        assert!(!rendered.contains("URL"));
+        assert!(rendered.contains("example.com"));
+    }
+
+    #[test]
+    fn test_ascii_table_has_no_box_characters() {
+        let har = fixtures::har(vec![
+            fixtures::entry_json("GET", "https://example.com/a", 200),
+            fixtures::entry_json("POST", "https://example.com/b", 0),
+        ]);
+        let entries: Vec<(usize, &Entry)> = har.log.entries.iter().enumerate().map(|(i, e)| (i + 1, e)).collect();
+        let columns = parse_columns(&["index".to_string(), "method".to_string(), "status".to_string(), "url".to_string()]).unwrap();
+
+        let mut table = build_columns_table(&entries, &columns, false, 60);
+        apply_style(&mut table, true);
+        let rendered = table.to_string();
+        assert!(rendered.is_ascii(), "{}", rendered);
+        assert!(rendered.starts_with('+'));
+
+        apply_style(&mut table, false);
+        assert!(!table.to_string().is_ascii());
+    }
+
+    #[test]
+    fn test_unknown_column_lists_valid_names() {
+        let err = parse_columns(&["bogus".to_string()]).err().unwrap().to_string();
+        assert!(err.contains("bogus"));
+        assert!(err.contains("method"));
+    }
+
+    #[test]
+    fn test_status_zero_renders_as_no_response() {
+        let har = fixtures::har(vec![fixtures::entry_json("GET", "https://example.com/", 0)]);
+        let row = EntryRow::from_entry(1, &har.log.entries[0], false, 60);
+        assert_eq!(row.status, "(no response)");
+        assert_eq!(status_label(404), "404");
+    }
+
+    #[test]
+    fn test_url_column_fits_terminal_width() {
+        let url = format!("https://example.com/{}", "a".repeat(200));
+        let entry = fixtures::entry("GET", &url, 200);
+        let entries = [(1, &entry)];
+
+        // Other columns: # (1), Method (6), Status (6), Time (5 for "100ms"), Size (4)
+        let rows = entry_rows(&entries, false, None, 100);
+        let others = (1 + 3) + (6 + 3) + (6 + 3) + (5 + 3) + (4 + 3);
+        assert_eq!(rows[0].url.chars().count(), 100 - 1 - others - 3);
+
+        let mut table = Table::new(&rows);
+        apply_style(&mut table, false);
+        assert_eq!(table.to_string().lines().next().unwrap().chars().count(), 100);
+
+        // Color codes don't count, tiny terminals keep a usable minimum, --max-url wins
+        assert_eq!(entry_rows(&entries, true, None, 100)[0].url, rows[0].url);
+        assert_eq!(entry_rows(&entries, false, None, 30)[0].url.chars().count(), MIN_FLEXIBLE_WIDTH);
+        assert_eq!(entry_rows(&entries, false, Some(40), 100)[0].url.chars().count(), 40);
+    }
 }
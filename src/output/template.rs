@@ -0,0 +1,112 @@
+use crate::har::Entry;
+use crate::output::table::{Column, COLUMNS};
+use anyhow::{Result, bail};
+
+/// A `list --format` line template such as `{method} {status} {url}`.
+///
+/// Placeholders are the `--select` column names; `{{` and `}}` stand for
+/// literal braces.
+#[derive(Debug, Clone)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Literal(String),
+    Column(&'static Column),
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => bail!("Unclosed '{{' in format template: {}", template),
+                        }
+                    }
+                    let name = name.trim().to_lowercase();
+                    let Some(column) = COLUMNS.iter().find(|c| c.name == name) else {
+                        let valid: Vec<&str> = COLUMNS.iter().map(|c| c.name).collect();
+                        bail!("Unknown placeholder '{{{}}}'. Valid placeholders: {}", name, valid.join(", "));
+                    };
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Column(column));
+                }
+                '}' => bail!("Unmatched '}}' in format template (use '}}}}' for a literal brace): {}", template),
+                _ => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// The line for one entry; URLs and paths are never truncated
+    pub fn render(&self, index: usize, entry: &Entry, color: bool) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => text.clone(),
+                Part::Column(column) => column.value(index, entry, color, usize::MAX),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    #[test]
+    fn test_render_placeholders() {
+        let entry = fixtures::entry("POST", "https://api.example.com/v1/login?next=%2F", 401);
+        let template = Template::parse("{index} {method} {status} {host} {url}").unwrap();
+        assert_eq!(
+            template.render(7, &entry, false),
+            "7 POST 401 api.example.com https://api.example.com/v1/login?next=%2F"
+        );
+
+        let template = Template::parse("{METHOD}\t{ path }").unwrap();
+        assert_eq!(template.render(1, &entry, false), "POST\t/v1/login?next=%2F");
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        let entry = fixtures::entry("GET", "https://example.com/", 200);
+        let template = Template::parse("{{\"status\": {status}}} {{{{}}}}").unwrap();
+        assert_eq!(template.render(1, &entry, false), "{\"status\": 200} {{}}");
+        assert_eq!(Template::parse("plain text").unwrap().render(1, &entry, false), "plain text");
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        let err = Template::parse("{method} {bogus}").unwrap_err().to_string();
+        assert!(err.contains("Unknown placeholder '{bogus}'"), "{}", err);
+        assert!(err.contains("method"), "{}", err);
+        assert!(Template::parse("{method").is_err());
+        assert!(Template::parse("a } b").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::har::{Entry, Timings};
+use crate::output::{ascii, extract_host, format_time, truncate};
+use colored::{Color, Colorize};
+
+/// A timing phase drawn as one bar segment
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Blocked,
+    Dns,
+    Connect,
+    Ssl,
+    Send,
+    Wait,
+    Receive,
+}
+
+impl Phase {
+    const ALL: [Phase; 7] = [
+        Phase::Blocked,
+        Phase::Dns,
+        Phase::Connect,
+        Phase::Ssl,
+        Phase::Send,
+        Phase::Wait,
+        Phase::Receive,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Blocked => "blocked",
+            Phase::Dns => "dns",
+            Phase::Connect => "connect",
+            Phase::Ssl => "ssl",
+            Phase::Send => "send",
+            Phase::Wait => "wait",
+            Phase::Receive => "receive",
+        }
+    }
+
+    /// Character used without color, so phases stay distinguishable
+    fn symbol(self) -> char {
+        match self {
+            Phase::Blocked => '.',
+            Phase::Dns => 'd',
+            Phase::Connect => 'c',
+            Phase::Ssl => 's',
+            Phase::Send => '>',
+            Phase::Wait => '=',
+            Phase::Receive => '#',
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Phase::Blocked => Color::BrightBlack,
+            Phase::Dns => Color::Cyan,
+            Phase::Connect => Color::Yellow,
+            Phase::Ssl => Color::Magenta,
+            Phase::Send => Color::Green,
+            Phase::Wait => Color::BrightGreen,
+            Phase::Receive => Color::Blue,
+        }
+    }
+
+    /// Phase duration in ms; `connect` excludes the `ssl` time it contains
+    fn duration(self, t: &Timings) -> f64 {
+        let known = |v: Option<f64>| v.filter(|v| *v > 0.0).unwrap_or(0.0);
+        match self {
+            Phase::Blocked => known(t.blocked),
+            Phase::Dns => known(t.dns),
+            Phase::Connect => (known(t.connect) - known(t.ssl)).max(0.0),
+            Phase::Ssl => known(t.ssl),
+            Phase::Send => known(t.send),
+            Phase::Wait => known(t.wait),
+            Phase::Receive => known(t.receive),
+        }
+    }
+}
+
+/// Fixed columns around the bar: index, host, two bar borders, total time
+const INDEX_WIDTH: usize = 4;
+const TIME_WIDTH: usize = 8;
+const MIN_BAR_WIDTH: usize = 10;
+
+/// Print entries as a waterfall: one bar per entry, offset by its start time
+/// relative to the earliest entry and split into colored timing phases
+pub fn print_waterfall(entries: &[(usize, &Entry)], width: usize, color: bool, max_host: usize) {
+    if entries.is_empty() {
+        println!("No entries found.");
+        return;
+    }
+
+    let starts: Vec<Option<f64>> = entries.iter().map(|(_, e)| start_ms(e)).collect();
+    let first = starts.iter().flatten().copied().reduce(f64::min).unwrap_or(0.0);
+    let offsets: Vec<f64> = starts.iter().map(|s| s.map_or(0.0, |s| s - first)).collect();
+    let span = entries
+        .iter()
+        .zip(&offsets)
+        .map(|((_, e), offset)| offset + e.time.max(0.0))
+        .fold(0.0, f64::max);
+
+    let host_width = entries
+        .iter()
+        .map(|(_, e)| extract_host(&e.request.url).chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(max_host);
+    let bar_width = width
+        .saturating_sub(INDEX_WIDTH + host_width + TIME_WIDTH + 4)
+        .max(MIN_BAR_WIDTH);
+    let (left, right) = if ascii() { ('|', '|') } else { ('│', '│') };
+
+    for ((index, entry), offset) in entries.iter().zip(&offsets) {
+        let bar = render_bar(*offset, entry, span, bar_width);
+        println!("{:>w$} {:<hw$} {}{}{} {:>tw$}",
+            index,
+            truncate(extract_host(&entry.request.url), host_width),
+            left,
+            paint(&bar, color),
+            right,
+            format_time(entry.time),
+            w = INDEX_WIDTH - 1,
+            hw = host_width,
+            tw = TIME_WIDTH
+        );
+    }
+
+    println!();
+    println!("{}  total span {}", legend(color), format_time(span));
+}
+
+/// Bar cells for one entry: None is empty space, Some(phase) a filled cell
+fn render_bar(offset: f64, entry: &Entry, span: f64, width: usize) -> Vec<Option<Phase>> {
+    let mut cells = vec![None; width];
+    if span <= 0.0 {
+        return cells;
+    }
+
+    let scale = width as f64 / span;
+    let column = |ms: f64| ((ms * scale).round() as usize).min(width);
+    let start = column(offset).min(width - 1);
+
+    let mut elapsed = offset;
+    let mut cursor = start;
+    for phase in Phase::ALL {
+        elapsed += phase.duration(&entry.timings);
+        let end = column(elapsed);
+        for cell in cells.iter_mut().take(end).skip(cursor) {
+            *cell = Some(phase);
+        }
+        cursor = cursor.max(end);
+    }
+
+    // Without usable timings, still show the request's total time
+    if cursor == start {
+        let end = column(offset + entry.time.max(0.0)).max(start + 1);
+        for cell in cells.iter_mut().take(end).skip(start) {
+            *cell = Some(Phase::Wait);
+        }
+    }
+
+    cells
+}
+
+fn paint(cells: &[Option<Phase>], color: bool) -> String {
+    let block = if ascii() { None } else { Some('█') };
+    cells
+        .iter()
+        .map(|cell| match cell {
+            None => " ".to_string(),
+            Some(phase) if color => block
+                .unwrap_or(phase.symbol())
+                .to_string()
+                .color(phase.color())
+                .to_string(),
+            Some(phase) => phase.symbol().to_string(),
+        })
+        .collect()
+}
+
+fn legend(color: bool) -> String {
+    Phase::ALL
+        .iter()
+        .map(|phase| format!("{} {}", paint(&[Some(*phase)], color), phase.name()))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Milliseconds since the epoch from `startedDateTime`
+fn start_ms(entry: &Entry) -> Option<f64> {
+    entry.started_at().map(|t| t.timestamp_millis() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::har::fixtures;
+
+    fn symbols(cells: &[Option<Phase>]) -> String {
+        cells.iter().map(|c| c.map_or(' ', Phase::symbol)).collect()
+    }
+
+    #[test]
+    fn test_bar_segments_follow_phases() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["time"] = serde_json::json!(100.0);
+            e["timings"] = serde_json::json!({
+                "blocked": -1, "dns": 10, "connect": 30, "ssl": 20, "send": 0, "wait": 50, "receive": 10
+            });
+        });
+        let entry = fixtures::to_entry(json);
+
+        // 200ms span in 20 cells: 10ms per cell, starting 100ms in
+        let bar = render_bar(100.0, &entry, 200.0, 20);
+        assert_eq!(symbols(&bar), "          dcss=====#");
+    }
+
+    #[test]
+    fn test_bar_without_timings_uses_total_time() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["time"] = serde_json::json!(50.0);
+        });
+        let entry = fixtures::to_entry(json);
+
+        let bar = render_bar(0.0, &entry, 100.0, 10);
+        assert_eq!(symbols(&bar), "=====     ");
+    }
+
+    #[test]
+    fn test_start_offset_from_timestamp() {
+        let json = fixtures::entry_with("GET", "https://example.com/", 200, |e| {
+            e["startedDateTime"] = serde_json::json!("2024-01-01T00:00:01.250+00:00");
+        });
+        let later = fixtures::to_entry(json);
+        let first = fixtures::entry("GET", "https://example.com/", 200);
+
+        assert_eq!(start_ms(&later).unwrap() - start_ms(&first).unwrap(), 1250.0);
+    }
+}
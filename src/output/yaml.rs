@@ -0,0 +1,23 @@
+use crate::har::Entry;
+use crate::output::json::EntrySummary;
+use anyhow::Result;
+use serde::Serialize;
+use std::io::{self, BufWriter, Write};
+
+/// Output a value as a YAML document on stdout
+pub fn print_yaml<T: Serialize>(value: &T) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    serde_yaml_ng::to_writer(&mut out, value)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Output entry summaries as a YAML list
+pub fn print_summaries_yaml(entries: &[(usize, &Entry)]) -> Result<()> {
+    let summaries: Vec<EntrySummary> = entries
+        .iter()
+        .map(|(i, e)| EntrySummary::from_entry(*i, e))
+        .collect();
+
+    print_yaml(&summaries)
+}